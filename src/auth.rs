@@ -14,7 +14,7 @@ impl AuthenticationState for UnAuthenticated {}
 pub trait AuthFlow: private::Sealed {
     fn client_id(&self) -> ClientId;
     fn client_secret(&self) -> Option<ClientSecret>;
-    fn scopes(self) -> Option<Vec<oauth2::Scope>>;
+    fn scopes(&self) -> Option<Vec<oauth2::Scope>>;
 }
 
 pub trait Refreshable: private::Sealed {}
@@ -24,6 +24,7 @@ impl Refreshable for AuthCodePkceFlow {}
 pub trait Authorised: private::Sealed {}
 impl Authorised for AuthCodeFlow {}
 impl Authorised for AuthCodePkceFlow {}
+impl Authorised for ImplicitGrantFlow {}
 
 pub trait Verifier: private::Sealed {}
 impl Verifier for NoVerifier {}
@@ -32,8 +33,8 @@ impl Verifier for PkceVerifier {}
 
 mod private {
     use super::{
-        AuthCodeFlow, AuthCodePkceFlow, ClientCredsFlow, CsrfVerifier, NoVerifier, PkceVerifier,
-        Token, UnAuthenticated,
+        AuthCodeFlow, AuthCodePkceFlow, ClientCredsFlow, CsrfVerifier, ImplicitGrantFlow,
+        NoVerifier, PkceVerifier, Token, UnAuthenticated,
     };
 
     pub trait Sealed {}
@@ -43,25 +44,69 @@ mod private {
     impl Sealed for AuthCodeFlow {}
     impl Sealed for AuthCodePkceFlow {}
     impl Sealed for ClientCredsFlow {}
+    impl Sealed for ImplicitGrantFlow {}
     impl Sealed for NoVerifier {}
     impl Sealed for CsrfVerifier {}
     impl Sealed for PkceVerifier {}
 }
 
+/// A secret string, such as a token or a client secret.
+///
+/// It's left out of the `Debug` output, and with the `zeroize` feature, it's overwritten
+/// with zeroes when it's dropped.
+///
+/// ```
+/// use spotify_rs::ClientCredsFlow;
+///
+/// let ClientCredsFlow { client_secret, .. } = ClientCredsFlow::new("client_id", "client_secret");
+///
+/// assert_eq!(client_secret.secret(), "client_secret");
+/// assert_eq!(format!("{client_secret:?}"), "[redacted]");
+/// ```
+#[derive(Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct Secret(String);
+
+impl Secret {
+    pub fn new(secret: String) -> Self {
+        Self(secret)
+    }
+
+    /// Get the secret.
+    pub fn secret(&self) -> &String {
+        &self.0
+    }
+}
+
+impl std::fmt::Debug for Secret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("[redacted]")
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Secret {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.0);
+    }
+}
+
 /// A Spotify token.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Token {
     /// The token used for authenticating every single request.
-    pub access_token: AccessToken,
+    pub access_token: Secret,
     /// The token used for requesting a new access token when the current one expires.
-    pub refresh_token: Option<RefreshToken>,
+    pub refresh_token: Option<Secret>,
     /// How long until the current token expires, in seconds.
     pub expires_in: u64,
 
-    #[serde(skip)]
+    // These aren't part of the token response, but they're (de)serialised so a saved
+    // token (see `TokenStore`) still knows when it expires.
+    #[serde(default)]
     /// The UTC date and time when the token was created.
     pub created_at: DateTime<Utc>,
-    #[serde(skip)]
+    #[serde(default)]
     /// The UTC date and time when the token will expire.
     pub expires_at: DateTime<Utc>,
 
@@ -75,13 +120,47 @@ pub struct Token {
     pub(crate) scopes: Option<Vec<oauth2::Scope>>,
 }
 
+// The token response, as oauth2 deserialises it, which is turned into a `Token` so that
+// the secrets are kept in `Secret`s.
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct TokenResponseBody {
+    access_token: AccessToken,
+    refresh_token: Option<RefreshToken>,
+    expires_in: u64,
+    #[serde(deserialize_with = "oauth2::helpers::deserialize_untagged_enum_case_insensitive")]
+    token_type: BasicTokenType,
+    #[serde(rename = "scope")]
+    #[serde(deserialize_with = "oauth2::helpers::deserialize_space_delimited_vec")]
+    #[serde(serialize_with = "oauth2::helpers::serialize_space_delimited_vec")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    scopes: Option<Vec<oauth2::Scope>>,
+}
+
+impl TokenResponseBody {
+    pub(crate) fn into_token(self) -> Token {
+        Token {
+            access_token: Secret::new(self.access_token.secret().clone()),
+            refresh_token: self
+                .refresh_token
+                .map(|token| Secret::new(token.secret().clone())),
+            expires_in: self.expires_in,
+            created_at: DateTime::default(),
+            expires_at: DateTime::default(),
+            token_type: self.token_type,
+            scopes: self.scopes,
+        }
+        .set_timestamps()
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct UnAuthenticated;
 
 #[derive(Clone, Debug)]
 pub struct AuthCodeFlow {
     pub client_id: String,
-    pub client_secret: String,
+    pub client_secret: Secret,
     pub scopes: Vec<Scope>,
 }
 
@@ -94,13 +173,18 @@ pub struct AuthCodePkceFlow {
 #[derive(Clone, Debug)]
 pub struct ClientCredsFlow {
     pub client_id: String,
-    pub client_secret: String,
+    pub client_secret: Secret,
+}
+
+#[derive(Clone, Debug)]
+pub struct ImplicitGrantFlow {
+    pub client_id: String,
+    pub scopes: Vec<Scope>,
 }
 
 #[derive(Debug)]
 pub struct NoVerifier;
 #[derive(Debug)]
-
 pub struct CsrfVerifier(pub(crate) CsrfToken);
 
 #[derive(Debug)]
@@ -115,11 +199,11 @@ impl AuthFlow for AuthCodeFlow {
     }
 
     fn client_secret(&self) -> Option<ClientSecret> {
-        Some(ClientSecret::new(self.client_secret.clone()))
+        Some(ClientSecret::new(self.client_secret.secret().clone()))
     }
 
-    fn scopes(self) -> Option<Vec<oauth2::Scope>> {
-        Some(self.scopes)
+    fn scopes(&self) -> Option<Vec<oauth2::Scope>> {
+        Some(self.scopes.clone())
     }
 }
 
@@ -132,8 +216,8 @@ impl AuthFlow for AuthCodePkceFlow {
         None
     }
 
-    fn scopes(self) -> Option<Vec<oauth2::Scope>> {
-        Some(self.scopes)
+    fn scopes(&self) -> Option<Vec<oauth2::Scope>> {
+        Some(self.scopes.clone())
     }
 }
 
@@ -143,15 +227,47 @@ impl AuthFlow for ClientCredsFlow {
     }
 
     fn client_secret(&self) -> Option<ClientSecret> {
-        Some(ClientSecret::new(self.client_secret.clone()))
+        Some(ClientSecret::new(self.client_secret.secret().clone()))
+    }
+
+    fn scopes(&self) -> Option<Vec<oauth2::Scope>> {
+        None
+    }
+}
+
+impl AuthFlow for ImplicitGrantFlow {
+    fn client_id(&self) -> ClientId {
+        ClientId::new(self.client_id.clone())
     }
 
-    fn scopes(self) -> Option<Vec<oauth2::Scope>> {
+    fn client_secret(&self) -> Option<ClientSecret> {
         None
     }
+
+    fn scopes(&self) -> Option<Vec<oauth2::Scope>> {
+        Some(self.scopes.clone())
+    }
 }
 
 impl Token {
+    // A bearer token that was obtained without the client, e.g. from the implicit grant flow.
+    pub(crate) fn new(
+        access_token: String,
+        expires_in: u64,
+        refresh_token: Option<String>,
+    ) -> Self {
+        Self {
+            access_token: Secret::new(access_token),
+            refresh_token: refresh_token.map(Secret::new),
+            expires_in,
+            created_at: DateTime::default(),
+            expires_at: DateTime::default(),
+            token_type: BasicTokenType::Bearer,
+            scopes: None,
+        }
+        .set_timestamps()
+    }
+
     pub(crate) fn set_timestamps(self) -> Self {
         let created_at = Utc::now();
 
@@ -159,11 +275,10 @@ impl Token {
         let expires_at = created_at
             + chrono::Duration::seconds(i64::try_from(self.expires_in).unwrap_or(i64::MAX));
 
-        Self {
-            created_at,
-            expires_at,
-            ..self
-        }
+        let mut token = self;
+        token.created_at = created_at;
+        token.expires_at = expires_at;
+        token
     }
 
     /// Returns `true` if the access token has expired.
@@ -175,9 +290,22 @@ impl Token {
     pub fn is_refreshable(&self) -> bool {
         self.refresh_token.is_some()
     }
+
+    /// The scopes the user granted, as listed in the token response.
+    ///
+    /// This is empty if the response didn't list them, e.g. for the client credentials
+    /// flow, or for a token from [`Client::from_access_token`](crate::client::Client::from_access_token).
+    pub fn granted_scopes(&self) -> &[Scope] {
+        self.scopes.as_deref().unwrap_or_default()
+    }
+
+    /// Returns `true` if the user granted the given scope, e.g. `user-library-read`.
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.granted_scopes().iter().any(|s| s.as_str() == scope)
+    }
 }
 
-impl TokenResponse<BasicTokenType> for Token {
+impl TokenResponse<BasicTokenType> for TokenResponseBody {
     fn access_token(&self) -> &AccessToken {
         &self.access_token
     }
@@ -207,7 +335,7 @@ impl AuthCodeFlow {
     {
         Self {
             client_id: client_id.into(),
-            client_secret: client_secret.into(),
+            client_secret: Secret::new(client_secret.into()),
             scopes: scopes.into_iter().map(|s| Scope::new(s.into())).collect(),
         }
     }
@@ -226,11 +354,24 @@ impl AuthCodePkceFlow {
     }
 }
 
+impl ImplicitGrantFlow {
+    pub fn new<I>(client_id: impl Into<String>, scopes: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        Self {
+            client_id: client_id.into(),
+            scopes: scopes.into_iter().map(|s| Scope::new(s.into())).collect(),
+        }
+    }
+}
+
 impl ClientCredsFlow {
     pub fn new(client_id: impl Into<String>, client_secret: impl Into<String>) -> Self {
         Self {
             client_id: client_id.into(),
-            client_secret: client_secret.into(),
+            client_secret: Secret::new(client_secret.into()),
         }
     }
 }
@@ -0,0 +1,174 @@
+//! Running large jobs, such as getting the audio features of thousands of tracks,
+//! spread out over time so they stay within a request budget.
+//!
+//! A [`BatchScheduler`] splits the items of a job into batches and hands them out one at
+//! a time, waiting whenever the budget for the current time window has been used up.
+//! The requests themselves are made by the caller, so any endpoint can be used, and
+//! the job's [`BatchProgress`] can be saved after each batch, to resume it later if
+//! the job is interrupted.
+//!
+//! Like the client, the scheduler isn't tied to an async runtime, so it's given the
+//! function to wait with, such as `tokio::time::sleep`.
+//!
+//! ```no_run
+//! use std::time::Duration;
+//! use spotify_rs::{batch::{BatchProgress, BatchScheduler}, ClientCredsClient};
+//! # use std::error::Error;
+//! # async fn sleep(_: Duration) {}
+//! # fn load_progress() -> Option<BatchProgress> { None }
+//! # fn save_progress(_: &BatchProgress) {}
+//!
+//! # async fn example(spotify: &ClientCredsClient, track_ids: Vec<String>) -> Result<(), Box<dyn Error>> {
+//! let mut scheduler = BatchScheduler::new(track_ids, sleep)
+//!     .batch_size(100)
+//!     .budget(20, Duration::from_secs(30));
+//!
+//! if let Some(progress) = load_progress() {
+//!     scheduler = scheduler.resume(&progress);
+//! }
+//!
+//! while let Some(ids) = scheduler.next_batch().await {
+//!     let features = spotify.get_tracks_audio_features(ids).await?;
+//!     // Store the features...
+//!
+//!     scheduler.complete();
+//!     save_progress(&scheduler.progress());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{collections::VecDeque, future::Future, time::Duration};
+
+use serde::{Deserialize, Serialize};
+use web_time::Instant;
+
+/// How far a batch job has got, which can be saved to resume the job later.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchProgress {
+    /// The number of items that have been completed.
+    pub completed: usize,
+    /// The total number of items in the job.
+    pub total: usize,
+}
+
+impl BatchProgress {
+    /// Whether every item has been completed.
+    pub fn is_done(&self) -> bool {
+        self.completed >= self.total
+    }
+}
+
+/// Hands out the items of a job in batches, at a rate that stays within a request budget.
+///
+/// Every call to [`next_batch`](Self::next_batch) counts as one request. If a batch
+/// fails, don't call [`complete`](Self::complete), and the next call returns the same
+/// batch again, so it can be retried.
+#[derive(Debug)]
+pub struct BatchScheduler<I, S> {
+    items: Vec<I>,
+    completed: usize,
+    batch_size: usize,
+    budget: usize,
+    window: Duration,
+    sent: VecDeque<Instant>,
+    sleep: S,
+}
+
+impl<I, S, Fut> BatchScheduler<I, S>
+where
+    S: Fn(Duration) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    /// Create a scheduler for the given items, which waits using `sleep`.
+    ///
+    /// By default, the batches have 20 items and the budget is 30 requests every 30 seconds.
+    pub fn new(items: impl IntoIterator<Item = I>, sleep: S) -> Self {
+        Self {
+            items: items.into_iter().collect(),
+            completed: 0,
+            batch_size: 20,
+            budget: 30,
+            window: Duration::from_secs(30),
+            sent: VecDeque::new(),
+            sleep,
+        }
+    }
+
+    /// The number of items in each batch (at least 1), which should be the maximum
+    /// the endpoint accepts in a single request.
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Allow at most `requests` (at least 1) in any `window` of time.
+    pub fn budget(mut self, requests: usize, window: Duration) -> Self {
+        self.budget = requests.max(1);
+        self.window = window;
+        self
+    }
+
+    /// Skip the items that were completed before the job was interrupted.
+    ///
+    /// The scheduler must be created with the same items, in the same order.
+    pub fn resume(mut self, progress: &BatchProgress) -> Self {
+        self.completed = progress.completed.min(self.items.len());
+        self
+    }
+
+    /// Get how far the job has got.
+    pub fn progress(&self) -> BatchProgress {
+        BatchProgress {
+            completed: self.completed,
+            total: self.items.len(),
+        }
+    }
+
+    /// Wait until the budget allows another request, then get the next batch of items,
+    /// or `None` if every item has been completed.
+    pub async fn next_batch(&mut self) -> Option<&[I]> {
+        if self.completed >= self.items.len() {
+            return None;
+        }
+
+        self.acquire().await;
+
+        let end = (self.completed + self.batch_size).min(self.items.len());
+        Some(&self.items[self.completed..end])
+    }
+
+    /// Mark the current batch as completed, so the next call to
+    /// [`next_batch`](Self::next_batch) moves on to the following one.
+    pub fn complete(&mut self) {
+        self.completed = (self.completed + self.batch_size).min(self.items.len());
+    }
+
+    /// Wait until the budget allows another request, and count it.
+    ///
+    /// This is done by [`next_batch`](Self::next_batch), so it's only needed for
+    /// batches that take more than one request.
+    pub async fn acquire(&mut self) {
+        loop {
+            let now = Instant::now();
+
+            while self
+                .sent
+                .front()
+                .is_some_and(|sent| now.duration_since(*sent) >= self.window)
+            {
+                self.sent.pop_front();
+            }
+
+            match self.sent.front() {
+                Some(oldest) if self.sent.len() >= self.budget => {
+                    let wait = self.window.saturating_sub(now.duration_since(*oldest));
+                    (self.sleep)(wait).await;
+                }
+                _ => break,
+            }
+        }
+
+        self.sent.push_back(Instant::now());
+    }
+}
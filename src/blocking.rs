@@ -0,0 +1,99 @@
+//! A runtime helper for using the async client from synchronous code, such as CLI tools
+//! and scripts that don't otherwise use an async runtime.
+//!
+//! This isn't a separate blocking client: [`Runtime`] wraps the async client and a
+//! private, single-threaded Tokio runtime, and [`call`](Runtime::call) runs a request
+//! made with the async client's builders and methods to completion, the same way
+//! `reqwest::blocking` runs its requests. The requests are written exactly like with the
+//! async client, inside a closure that's given the client:
+//!
+//! ```no_run
+//! use spotify_rs::{blocking::Runtime, market::Market, ClientCredsClient, ClientCredsFlow};
+//!
+//! # fn main() -> spotify_rs::SpotifyResult<()> {
+//! let auth_flow = ClientCredsFlow::new("client_id", "client_secret");
+//! let spotify = Runtime::new(|| ClientCredsClient::authenticate(auth_flow))?;
+//!
+//! let album = spotify.call(|spotify| spotify.album("album_id").market(Market::GB))?;
+//! let markets = spotify.call(|spotify| spotify.get_available_markets())?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Like with `reqwest::blocking`, the runtime must not be used from inside an async runtime,
+//! which panics.
+
+use std::future::{Future, IntoFuture};
+
+use tokio::runtime::{Builder, Runtime as TokioRuntime};
+
+use crate::{
+    auth::{AuthFlow, Token},
+    client,
+    error::{Error, Result},
+};
+
+/// An async client and a runtime to run its requests on, blocking the current thread
+/// until they're done.
+///
+/// See the [module documentation](self) for an example.
+#[derive(Debug)]
+pub struct Runtime<F: AuthFlow> {
+    inner: client::Client<Token, F>,
+    runtime: TokioRuntime,
+}
+
+impl<F: AuthFlow> Runtime<F> {
+    /// Create the runtime, and the client by running `authenticate`, which returns the
+    /// future that creates the async client, such as
+    /// [`ClientCredsClient::authenticate`](crate::ClientCredsClient::authenticate) or
+    /// [`ClientBuilder::from_refresh_token`](crate::ClientBuilder::from_refresh_token).
+    pub fn new<Fut>(authenticate: impl FnOnce() -> Fut) -> Result<Self>
+    where
+        Fut: Future<Output = Result<client::Client<Token, F>>>,
+    {
+        let runtime = runtime()?;
+        let inner = runtime.block_on(authenticate())?;
+
+        Ok(Self { inner, runtime })
+    }
+
+    /// Wrap an existing async client.
+    pub fn from_async(client: client::Client<Token, F>) -> Result<Self> {
+        Ok(Self {
+            inner: client,
+            runtime: runtime()?,
+        })
+    }
+
+    /// Make a request with the async client and wait for it to finish.
+    ///
+    /// `request` is given the async client and returns the request to make: a builder
+    /// or any other future, such as one returned by a method of the client.
+    pub fn call<'a, T>(
+        &'a self,
+        request: impl FnOnce(&'a client::Client<Token, F>) -> T,
+    ) -> T::Output
+    where
+        T: IntoFuture,
+    {
+        self.runtime.block_on(request(&self.inner).into_future())
+    }
+
+    /// Get the async client, e.g. to read its token.
+    pub fn as_async(&self) -> &client::Client<Token, F> {
+        &self.inner
+    }
+
+    /// Get the async client back.
+    pub fn into_async(self) -> client::Client<Token, F> {
+        self.inner
+    }
+}
+
+fn runtime() -> Result<TokioRuntime> {
+    Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| Error::Configuration(e.to_string()))
+}
@@ -0,0 +1,246 @@
+//! Caching the responses of the API, so that repeated reads don't all count towards the
+//! rate limit.
+//!
+//! Once a client is given a [`CacheBackend`] (with [`ClientBuilder::cache`]), the responses
+//! to its `GET` requests are stored along with their `ETag` and `Cache-Control` headers.
+//! A response that's still fresh according to its `max-age` is served from the cache
+//! without a request, and a stale one is revalidated by sending its ETag in an
+//! `If-None-Match` header, which Spotify answers with a body-less `304 Not Modified` if
+//! it hasn't changed.
+//!
+//! ```no_run
+//! use spotify_rs::{cache::MemoryCache, ClientBuilder, ClientCredsFlow};
+//!
+//! # async fn example() -> spotify_rs::SpotifyResult<()> {
+//! let auth_flow = ClientCredsFlow::new("client_id", "client_secret");
+//!
+//! let spotify = ClientBuilder::new(auth_flow)
+//!     .cache(MemoryCache::new(1000))
+//!     .authenticate()
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! As a cache can be shared by clients for different users, only responses that are the
+//! same for everyone are cached. Responses to requests about the current user (the `/me`
+//! endpoints), responses marked `Cache-Control: private`, and requests sent with a token
+//! other than the client's own (see
+//! [`Builder::with_token`](crate::endpoint::Builder::with_token)) are never cached.
+//!
+//! [`ClientBuilder::cache`]: crate::ClientBuilder::cache
+
+use std::{
+    collections::HashMap,
+    fmt,
+    future::Future,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, CACHE_CONTROL, ETAG};
+
+use crate::endpoint::BoxFuture;
+
+/// A response stored in a [`CacheBackend`].
+#[derive(Clone, Debug)]
+pub struct CachedResponse {
+    /// The response's `ETag`, used to revalidate it once it's stale.
+    pub etag: Option<String>,
+    /// When the response stops being fresh, according to its `Cache-Control` header.
+    pub fresh_until: Option<DateTime<Utc>>,
+    /// The response's body.
+    pub body: Bytes,
+}
+
+impl CachedResponse {
+    /// Returns `true` if the response can be used without revalidating it.
+    pub fn is_fresh(&self) -> bool {
+        self.fresh_until.is_some_and(|t| Utc::now() < t)
+    }
+
+    // The response to cache, unless its headers say it mustn't be stored, or it can
+    // neither be reused nor revalidated.
+    pub(crate) fn from_response(headers: &HeaderMap, body: Bytes) -> Option<Self> {
+        let response = Self {
+            etag: None,
+            fresh_until: None,
+            body,
+        };
+
+        response.revalidated(headers)
+    }
+
+    // The response updated with the headers of a later response to the same request,
+    // e.g. a `304 Not Modified`.
+    pub(crate) fn revalidated(mut self, headers: &HeaderMap) -> Option<Self> {
+        let mut max_age = None;
+
+        let directives = headers
+            .get_all(CACHE_CONTROL)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .map(str::trim);
+
+        for directive in directives {
+            match directive.split_once('=') {
+                Some(("max-age", secs)) => max_age = secs.trim_matches('"').parse::<i64>().ok(),
+                // A private response is meant for a single user, and a cache can be shared
+                // by the clients of several.
+                None if directive == "no-store" || directive == "private" => return None,
+                None if directive == "no-cache" => max_age = Some(0),
+                _ => {}
+            }
+        }
+
+        if let Some(etag) = headers.get(ETAG).and_then(|e| e.to_str().ok()) {
+            self.etag = Some(etag.to_owned());
+        }
+
+        self.fresh_until = max_age
+            .filter(|&secs| secs > 0)
+            .map(|secs| Utc::now() + chrono::Duration::seconds(secs));
+
+        (self.etag.is_some() || self.fresh_until.is_some()).then_some(self)
+    }
+}
+
+/// Somewhere the client's responses are cached.
+///
+/// The key of a response is the full URL of its request. A cache that fails to load or
+/// store a response should behave as if it didn't have it, so that requests still succeed.
+pub trait CacheBackend: Send + Sync {
+    /// Get the response cached under `key`, if there is one.
+    fn get(&self, key: &str) -> impl Future<Output = Option<CachedResponse>> + Send;
+
+    /// Cache `response` under `key`, replacing any response already cached under it.
+    fn put(&self, key: String, response: CachedResponse) -> impl Future<Output = ()> + Send;
+}
+
+// See `ErasedTokenStore`.
+trait ErasedCacheBackend: Send + Sync {
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Option<CachedResponse>>;
+    fn put(&self, key: String, response: CachedResponse) -> BoxFuture<'_, ()>;
+}
+
+impl<T: CacheBackend> ErasedCacheBackend for T {
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Option<CachedResponse>> {
+        Box::pin(CacheBackend::get(self, key))
+    }
+
+    fn put(&self, key: String, response: CachedResponse) -> BoxFuture<'_, ()> {
+        Box::pin(CacheBackend::put(self, key, response))
+    }
+}
+
+/// The response cache of a client.
+#[derive(Clone)]
+pub(crate) struct SharedCache(Arc<dyn ErasedCacheBackend>);
+
+impl SharedCache {
+    pub(crate) fn new(cache: impl CacheBackend + 'static) -> Self {
+        Self(Arc::new(cache))
+    }
+
+    pub(crate) async fn get(&self, key: &str) -> Option<CachedResponse> {
+        self.0.get(key).await
+    }
+
+    pub(crate) async fn put(&self, key: String, response: CachedResponse) {
+        self.0.put(key, response).await;
+    }
+}
+
+impl fmt::Debug for SharedCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedCache").finish_non_exhaustive()
+    }
+}
+
+/// Keeps up to a fixed number of responses in memory, evicting the least recently used
+/// one when it's full.
+///
+/// Clones of the cache share the same responses.
+#[derive(Clone, Debug)]
+pub struct MemoryCache {
+    inner: Arc<Mutex<Lru>>,
+}
+
+#[derive(Debug)]
+struct Lru {
+    capacity: usize,
+    // The responses, along with when they were last used.
+    entries: HashMap<String, (u64, CachedResponse)>,
+    clock: u64,
+}
+
+impl MemoryCache {
+    /// Create an empty cache that holds up to `capacity` responses.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Lru {
+                capacity,
+                entries: HashMap::new(),
+                clock: 0,
+            })),
+        }
+    }
+
+    /// The number of cached responses.
+    pub fn len(&self) -> usize {
+        self.lru().entries.len()
+    }
+
+    /// Returns `true` if no responses are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove every cached response.
+    pub fn clear(&self) {
+        self.lru().entries.clear();
+    }
+
+    fn lru(&self) -> std::sync::MutexGuard<'_, Lru> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl CacheBackend for MemoryCache {
+    async fn get(&self, key: &str) -> Option<CachedResponse> {
+        let mut lru = self.lru();
+        lru.clock += 1;
+        let clock = lru.clock;
+
+        let (last_used, response) = lru.entries.get_mut(key)?;
+        *last_used = clock;
+
+        Some(response.clone())
+    }
+
+    async fn put(&self, key: String, response: CachedResponse) {
+        let mut lru = self.lru();
+
+        if lru.capacity == 0 {
+            return;
+        }
+
+        if lru.entries.len() >= lru.capacity && !lru.entries.contains_key(&key) {
+            let oldest = lru
+                .entries
+                .iter()
+                .min_by_key(|(_, (last_used, _))| *last_used)
+                .map(|(key, _)| key.clone());
+
+            if let Some(oldest) = oldest {
+                lru.entries.remove(&oldest);
+            }
+        }
+
+        lru.clock += 1;
+        let clock = lru.clock;
+        lru.entries.insert(key, (clock, response));
+    }
+}
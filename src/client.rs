@@ -1,58 +1,99 @@
-use std::marker::PhantomData;
+use std::{
+    collections::HashMap,
+    marker::PhantomData,
+    sync::{Arc, Mutex, PoisonError, RwLock, RwLockReadGuard},
+    time::Duration,
+};
+#[cfg(feature = "player")]
+use web_time::Instant;
 
-use base64::{engine::general_purpose, Engine};
+use bytes::Bytes;
+use chrono::FixedOffset;
+use futures_util::{lock::Mutex as AsyncMutex, stream, StreamExt};
 use oauth2::{
     basic::{
         BasicErrorResponse, BasicRevocationErrorResponse, BasicTokenIntrospectionResponse,
         BasicTokenType,
     },
-    reqwest::async_http_client,
-    AuthUrl, AuthorizationCode, ClientId, ClientSecret, CsrfToken, PkceCodeChallenge, RedirectUrl,
-    RefreshToken, StandardRevocableToken, TokenUrl,
+    AuthorizationCode, HttpRequest, HttpResponse, PkceCodeVerifier, RedirectUrl, RefreshToken,
+    Scope, StandardRevocableToken,
+};
+use reqwest::{
+    header::{HeaderMap, CONTENT_LENGTH, CONTENT_TYPE, IF_NONE_MATCH},
+    Method, StatusCode, Url,
 };
-use reqwest::{header::CONTENT_LENGTH, Method, Url};
 use serde::{de::DeserializeOwned, Serialize};
+#[cfg(feature = "playlists")]
 use serde_json::json;
 
 use crate::{
     auth::{
         AuthCodeFlow, AuthCodePkceFlow, AuthFlow, AuthenticationState, Authorised, ClientCredsFlow,
-        CsrfVerifier, NoVerifier, PkceVerifier, Token, UnAuthenticated, Verifier,
+        CsrfVerifier, ImplicitGrantFlow, NoVerifier, PkceVerifier, Secret, Token,
+        TokenResponseBody, UnAuthenticated, Verifier,
     },
     body_list,
-    endpoint::{
-        album::*,
-        artist::ArtistEndpoint,
-        audiobook::*,
-        category::{BrowseCategoriesEndpoint, BrowseCategoryEndpoint},
-        player::*,
-        playlist::*,
-        search::SearchEndpoint,
-        show::*,
-        track::*,
-        user::*,
-        Builder, Endpoint,
-    },
-    error::{Error, Result, SpotifyError},
+    cache::{CachedResponse, SharedCache},
+    chunked,
+    endpoint::{album::*, artist::*, track::*, Builder, ClientRef, Endpoint, FetchById, Limit},
+    error::{Error, Kind, Result},
+    id::*,
+    locale::Locale,
+    market::Market,
+    middleware::MiddlewareChain,
     model::{
-        artist::{Artist, Artists},
+        artist::Artist,
         audio::{AudioAnalysis, AudioFeatures, AudioFeaturesResult},
         market::Markets,
-        player::{Device, Devices, PlaybackState, Queue},
         recommendation::Genres,
-        search::Item,
-        user::{User, UserItemType},
-        Image,
+        user::User,
     },
-    query_list, Nil,
+    query_list,
+    token_store::{SharedTokenStore, TokenStore},
+    Nil,
+};
+
+#[cfg(feature = "audiobooks")]
+use crate::endpoint::audiobook::*;
+#[cfg(feature = "shows")]
+use crate::endpoint::show::*;
+#[cfg(feature = "browse")]
+use crate::{
+    endpoint::category::{BrowseCategoriesEndpoint, BrowseCategoryEndpoint},
+    model::category::Category,
+};
+#[cfg(feature = "player")]
+use crate::{
+    endpoint::player::*,
+    model::player::{Device, Devices, PlaybackState, Queue},
+};
+#[cfg(feature = "playlists")]
+use crate::{
+    endpoint::playlist::*,
+    model::{playlist::Playlist, Image},
+};
+#[cfg(feature = "search")]
+use crate::{
+    endpoint::search::{SearchEndpoint, SearchQuery},
+    model::{album::SimplifiedAlbum, search::Item, track::Track},
 };
+#[cfg(feature = "user")]
+use crate::{endpoint::user::*, model::user::UserItemType};
 
-const AUTHORISATION_URL: &str = "https://accounts.spotify.com/authorize";
-const TOKEN_URL: &str = "https://accounts.spotify.com/api/token";
+mod builder;
+#[cfg(feature = "playlists")]
+mod cover;
+mod rate_limit;
+mod retry;
+mod scope;
+
+pub use builder::ClientBuilder;
+pub use rate_limit::RateLimiter;
+pub use retry::RetryPolicy;
 
 pub(crate) type OAuthClient = oauth2::Client<
     BasicErrorResponse,
-    Token,
+    TokenResponseBody,
     BasicTokenType,
     BasicTokenIntrospectionResponse,
     StandardRevocableToken,
@@ -60,34 +101,241 @@ pub(crate) type OAuthClient = oauth2::Client<
 >;
 
 /// A client created using the Authorisation Code Flow.
-pub type AuthCodeClient<V = NoVerifier> = Client<UnAuthenticated, AuthCodeFlow, V>;
+///
+/// Without any type parameters, this refers to an authenticated client,
+/// which makes it convenient to store and pass around.
+pub type AuthCodeClient<A = Token, V = NoVerifier> = Client<A, AuthCodeFlow, V>;
 
 /// A client created using the Authorisation Code with PKCE Flow.
-pub type AuthCodePkceClient<V = NoVerifier> = Client<UnAuthenticated, AuthCodePkceFlow, V>;
+///
+/// Without any type parameters, this refers to an authenticated client,
+/// which makes it convenient to store and pass around.
+pub type AuthCodePkceClient<A = Token, V = NoVerifier> = Client<A, AuthCodePkceFlow, V>;
 
 /// A client created using the Client Credentials Flow.
-pub type ClientCredsClient<V = NoVerifier> = Client<UnAuthenticated, ClientCredsFlow, V>;
+///
+/// Without any type parameters, this refers to an authenticated client,
+/// which makes it convenient to store and pass around.
+pub type ClientCredsClient<A = Token> = Client<A, ClientCredsFlow, NoVerifier>;
+
+/// A client created using the Implicit Grant Flow.
+///
+/// Without any type parameters, this refers to an authenticated client,
+/// which makes it convenient to store and pass around.
+pub type ImplicitGrantClient<A = Token, V = NoVerifier> = Client<A, ImplicitGrantFlow, V>;
 
 #[doc(hidden)]
+#[cfg_attr(not(feature = "playlists"), allow(dead_code))]
 pub(crate) enum Body<P: Serialize = ()> {
     Json(P),
     File(Vec<u8>),
 }
 
+/// Sources of responses used instead of the Spotify API, for testing and offline use.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Local {
+    #[cfg(feature = "mock")]
+    pub(crate) mock: Option<crate::mock::Mock>,
+    #[cfg(feature = "offline")]
+    pub(crate) store: Option<std::sync::Arc<dyn crate::offline::FixtureStore>>,
+    #[cfg(feature = "vcr")]
+    pub(crate) cassette: Option<crate::vcr::Cassette>,
+}
+
+/// Recent playback state responses, which are reused until they're older than `max_age`.
+#[cfg(feature = "player")]
+#[derive(Clone, Debug, Default)]
+pub(crate) struct PlaybackCache {
+    pub(crate) max_age: Option<std::time::Duration>,
+    // Keyed by the path and market of the request.
+    entries: HashMap<(&'static str, Option<Market>), (Instant, PlaybackState)>,
+}
+
+#[cfg(feature = "player")]
+impl PlaybackCache {
+    fn get(&self, key: &(&'static str, Option<Market>)) -> Option<PlaybackState> {
+        let max_age = self.max_age?;
+
+        self.entries
+            .get(key)
+            .filter(|(fetched_at, _)| fetched_at.elapsed() < max_age)
+            .map(|(_, state)| state.clone())
+    }
+
+    fn insert(&mut self, key: (&'static str, Option<Market>), state: &PlaybackState) {
+        if self.max_age.is_some() {
+            self.entries.insert(key, (Instant::now(), state.clone()));
+        }
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+// Called with every new token the client gets by refreshing it.
+// See `ClientBuilder::on_token_refresh`.
+#[derive(Clone)]
+pub(crate) struct TokenRefreshHook(Arc<dyn Fn(&Token) + Send + Sync>);
+
+impl TokenRefreshHook {
+    pub(crate) fn new(hook: impl Fn(&Token) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(hook))
+    }
+}
+
+impl std::fmt::Debug for TokenRefreshHook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TokenRefreshHook").finish_non_exhaustive()
+    }
+}
+
+/// A device that player commands are sent to when they don't specify one.
+///
+/// Device IDs can change, e.g. when a device restarts, so if the device has a name,
+/// [`Client::find_preferred_device`] can find it again by name.
+///
+/// It can be serialised, to be stored along with the rest of an app's settings.
+#[cfg(feature = "player")]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, serde::Deserialize)]
+pub struct PreferredDevice {
+    /// The ID of the device.
+    pub id: String,
+    /// The name of the device, used to find it if its ID changes.
+    pub name: Option<String>,
+}
+
+#[cfg(feature = "player")]
+impl PreferredDevice {
+    /// Create a preferred device with the given ID.
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            name: None,
+        }
+    }
+
+    /// Set the name of the device, used to find it if its ID changes.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Create a preferred device from a device returned by the API, if it has an ID.
+    pub fn from_device(device: &Device) -> Option<Self> {
+        device
+            .id
+            .as_ref()
+            .map(|id| Self::new(id.clone()).name(device.name.clone()))
+    }
+}
+
+/// A response of the API as raw JSON, for reading fields the models don't include (yet).
+#[derive(Clone, Debug)]
+pub struct RawResponse {
+    /// The response's headers.
+    ///
+    /// These are empty for responses served from a cache, mock or recording, rather than
+    /// by Spotify.
+    pub headers: HeaderMap,
+    /// The response's body, which is `null` if it was empty.
+    pub body: serde_json::Value,
+}
+
+/// The status and headers of a response of the API, e.g. to log Spotify's request IDs or
+/// monitor rate limiting.
+#[derive(Clone, Debug)]
+pub struct ResponseMeta {
+    /// The response's status.
+    pub status: StatusCode,
+    /// The response's headers.
+    ///
+    /// These are empty for responses served from a cache, mock or recording, rather than
+    /// by Spotify.
+    pub headers: HeaderMap,
+}
+
+impl ResponseMeta {
+    /// How long Spotify asked to wait before sending another request, from the
+    /// `Retry-After` header.
+    pub fn retry_after(&self) -> Option<Duration> {
+        retry::retry_after(&self.headers)
+    }
+
+    /// The ID Spotify gave the request, from the `x-request-id` or `sp-trace-id` header,
+    /// which is worth including when reporting an issue with the API.
+    pub fn request_id(&self) -> Option<&str> {
+        ["x-request-id", "sp-trace-id"]
+            .into_iter()
+            .find_map(|name| self.headers.get(name)?.to_str().ok())
+    }
+}
+
+// What a single request is sent with instead of the client's own, as set on its `Builder`.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Overrides {
+    pub(crate) token: Option<Secret>,
+    pub(crate) retry_policy: Option<RetryPolicy>,
+    // Where to keep the status and headers of the last response, for `get_with_meta`.
+    pub(crate) meta: Option<Arc<Mutex<Option<ResponseMeta>>>>,
+}
+
+impl Overrides {
+    fn record(&self, status: StatusCode, headers: &HeaderMap) {
+        if let Some(meta) = &self.meta {
+            *meta.lock().unwrap_or_else(PoisonError::into_inner) = Some(ResponseMeta {
+                status,
+                headers: headers.clone(),
+            });
+        }
+    }
+}
+
+/// A request body that has already been serialised.
+#[derive(Clone)]
+enum RawBody {
+    Json(Vec<u8>),
+    File(Vec<u8>),
+}
+
 /// The client which handles the authentication and all the Spotify API requests.
 ///
 /// It is recommended to use one of the following: [`AuthCodeClient`], [`AuthCodePkceClient`] or [`ClientCredsClient`],
 /// depending on the chosen auth flow.
 #[derive(Debug)]
-pub struct Client<A: AuthenticationState, F: AuthFlow, V: Verifier> {
+pub struct Client<A: AuthenticationState, F: AuthFlow, V: Verifier = NoVerifier> {
     /// Dictates whether or not the client will request a new token when the
     /// current one is about the expire.
     ///
     /// It will check if the token has expired in every request.
     pub auto_refresh: bool,
-    pub(crate) auth: A,
+    // See `ClientBuilder::refresh_on_unauthorised`.
+    pub(crate) refresh_on_unauthorised: bool,
+    pub(crate) auth: RwLock<A>,
     pub(crate) oauth: OAuthClient,
     pub(crate) http: reqwest::Client,
+    pub(crate) api_url: String,
+    #[cfg_attr(
+        not(any(feature = "mock", feature = "offline", feature = "vcr")),
+        allow(dead_code)
+    )]
+    pub(crate) local: Local,
+    pub(crate) default_market: RwLock<Option<Market>>,
+    pub(crate) default_locale: RwLock<Option<Locale>>,
+    pub(crate) utc_offset: Option<FixedOffset>,
+    pub(crate) default_limit: Option<u32>,
+    pub(crate) retry_policy: Option<RetryPolicy>,
+    pub(crate) rate_limiter: Option<RateLimiter>,
+    pub(crate) token_store: Option<SharedTokenStore>,
+    pub(crate) on_token_refresh: Option<TokenRefreshHook>,
+    // Held while refreshing the token, so concurrent requests refresh it only once.
+    pub(crate) refresh_lock: AsyncMutex<()>,
+    pub(crate) cache: Option<SharedCache>,
+    pub(crate) middleware: MiddlewareChain,
+    #[cfg(feature = "player")]
+    pub(crate) playback_cache: Mutex<PlaybackCache>,
+    #[cfg(feature = "player")]
+    pub(crate) preferred_device: RwLock<Option<PreferredDevice>>,
     pub(crate) verifier: V,
     marker: PhantomData<F>,
 }
@@ -99,39 +347,21 @@ impl Client<UnAuthenticated, AuthCodeFlow, CsrfVerifier> {
     /// the `redirect_uri` you provided, along with a `code` and `state` parameter in the URl.
     ///
     /// They are required for the next step in the auth process.
+    ///
+    /// Use a [`ClientBuilder`] to configure the client further.
     pub fn new(
-        AuthCodeFlow {
-            client_id,
-            client_secret,
-            scopes,
-        }: AuthCodeFlow,
+        auth_flow: AuthCodeFlow,
         redirect_uri: RedirectUrl,
         auto_refresh: bool,
     ) -> (Self, Url) {
-        let oauth = OAuthClient::new(
-            ClientId::new(client_id),
-            Some(ClientSecret::new(client_secret)),
-            AuthUrl::new(AUTHORISATION_URL.to_owned()).unwrap(),
-            Some(TokenUrl::new(TOKEN_URL.to_owned()).unwrap()),
-        )
-        .set_redirect_uri(redirect_uri);
-
-        let (auth_url, csrf_token) = oauth
-            .authorize_url(CsrfToken::new_random)
-            .add_scopes(scopes)
-            .url();
-
-        (
-            Client {
-                auto_refresh,
-                auth: UnAuthenticated,
-                oauth,
-                http: reqwest::Client::new(),
-                verifier: CsrfVerifier(csrf_token),
-                marker: PhantomData,
-            },
-            auth_url,
-        )
+        // The redirect URI is set, and the base URLs are either the defaults or URLs from
+        // the environment that were checked to be valid, so this can only fail if the HTTP
+        // client can't be initialised, in which case `reqwest::Client::new` would panic too.
+        ClientBuilder::new(auth_flow)
+            .redirect_uri(redirect_uri)
+            .auto_refresh(auto_refresh)
+            .build()
+            .expect("failed to create the client")
     }
 }
 
@@ -142,143 +372,644 @@ impl Client<UnAuthenticated, AuthCodePkceFlow, PkceVerifier> {
     /// the redirect URI you provided, along with a `code` and `state` parameter in the URl.
     ///
     /// They are required for the next step in the auth process.
+    ///
+    /// Use a [`ClientBuilder`] to configure the client further.
     pub fn new(
-        AuthCodePkceFlow { client_id, scopes }: AuthCodePkceFlow,
+        auth_flow: AuthCodePkceFlow,
         redirect_uri: RedirectUrl,
         auto_refresh: bool,
     ) -> (Self, Url) {
-        let oauth = OAuthClient::new(
-            ClientId::new(client_id),
-            None,
-            AuthUrl::new(AUTHORISATION_URL.to_owned()).unwrap(),
-            Some(TokenUrl::new(TOKEN_URL.to_owned()).unwrap()),
-        )
-        .set_redirect_uri(redirect_uri);
-
-        let (pkce_challenge, pkce_verifier) = PkceCodeChallenge::new_random_sha256();
-
-        let (auth_url, csrf_token) = oauth
-            .authorize_url(CsrfToken::new_random)
-            .add_scopes(scopes)
-            .set_pkce_challenge(pkce_challenge)
-            .url();
-
-        (
-            Client {
-                auto_refresh,
-                auth: UnAuthenticated,
-                oauth,
-                http: reqwest::Client::new(),
-                verifier: PkceVerifier {
-                    csrf_token,
-                    pkce_verifier,
-                },
-                marker: PhantomData,
-            },
-            auth_url,
-        )
+        // See the comment in `AuthCodeClient::new`.
+        ClientBuilder::new(auth_flow)
+            .redirect_uri(redirect_uri)
+            .auto_refresh(auto_refresh)
+            .build()
+            .expect("failed to create the client")
+    }
+}
+
+impl Client<UnAuthenticated, ImplicitGrantFlow, CsrfVerifier> {
+    /// Create a new client and generate an authorisation URL.
+    ///
+    /// You must redirect the user to the returned URL, which in turn redirects them to
+    /// the `redirect_uri` you provided, with the access token and a `state` parameter in
+    /// the URL's fragment (the part after `#`).
+    ///
+    /// Use a [`ClientBuilder`] to configure the client further.
+    pub fn new(auth_flow: ImplicitGrantFlow, redirect_uri: RedirectUrl) -> (Self, Url) {
+        // See the comment in `AuthCodeClient::new`.
+        ClientBuilder::new(auth_flow)
+            .redirect_uri(redirect_uri)
+            .build()
+            .expect("failed to create the client")
     }
 }
 
-impl<F: AuthFlow> Client<Token, F, NoVerifier> {
+impl<F: AuthFlow> Client<Token, F> {
     /// Create a new authenticated and authorised client from a refresh token.
     /// It's still required to specify an auth flow.
     ///
     /// This method will fail if the refresh token is invalid or a new one cannot be obtained.
+    ///
+    /// Use a [`ClientBuilder`] to configure the client further.
     pub async fn from_refresh_token(
         auth_flow: F,
         auto_refresh: bool,
         refresh_token: String,
-    ) -> Result<Client<Token, F, NoVerifier>> {
-        let oauth_client = OAuthClient::new(
-            auth_flow.client_id(),
-            auth_flow.client_secret(),
-            AuthUrl::new(AUTHORISATION_URL.to_owned()).unwrap(),
-            Some(TokenUrl::new(TOKEN_URL.to_owned()).unwrap()),
-        );
+    ) -> Result<Client<Token, F>> {
+        ClientBuilder::new(auth_flow)
+            .auto_refresh(auto_refresh)
+            .from_refresh_token(refresh_token)
+            .await
+    }
+
+    /// Create a new authenticated client from a token that was obtained elsewhere, e.g. by
+    /// a web framework or another service that handled the authorisation, without going
+    /// through the auth flow again.
+    ///
+    /// `expires_in` is the number of seconds until the access token expires, as returned
+    /// with it. If a `refresh_token` is given, the token is refreshed automatically once it
+    /// expires; otherwise, requests fail with [`Error::ExpiredToken`] from then on.
+    ///
+    /// Use a [`ClientBuilder`] to configure the client further.
+    pub async fn from_access_token(
+        auth_flow: F,
+        access_token: impl Into<String>,
+        expires_in: u64,
+        refresh_token: Option<String>,
+    ) -> Result<Client<Token, F>> {
+        ClientBuilder::new(auth_flow)
+            .auto_refresh(refresh_token.is_some())
+            .from_access_token(access_token, expires_in, refresh_token)
+            .await
+    }
+}
 
-        let refresh_token = RefreshToken::new(refresh_token);
+#[cfg(any(feature = "mock", feature = "offline", feature = "vcr"))]
+impl<F: AuthFlow> Client<Token, F> {
+    /// Create a client that responds to requests using the given [`Mock`](crate::mock::Mock),
+    /// without ever sending them to Spotify.
+    ///
+    /// The client's token never expires. See the [`mock`](crate::mock) module for an example.
+    #[cfg(feature = "mock")]
+    pub fn mock(mock: crate::mock::Mock) -> Self {
+        let mut client = Self::local();
+        client.local.mock = Some(mock);
+        client
+    }
 
-        let mut req = oauth_client.exchange_refresh_token(&refresh_token);
+    /// Create a client that serves responses from the given
+    /// [`FixtureStore`](crate::offline::FixtureStore), without ever sending requests to Spotify.
+    ///
+    /// The client's token never expires. See the [`offline`](crate::offline) module for an example.
+    #[cfg(feature = "offline")]
+    pub fn offline(store: impl crate::offline::FixtureStore + 'static) -> Self {
+        let mut client = Self::local();
+        client.local.store = Some(std::sync::Arc::new(store));
+        client
+    }
 
-        if let Some(scopes) = auth_flow.scopes() {
-            req = req.add_scopes(scopes);
-        }
+    /// Create a client that replays the interactions recorded in the given
+    /// [`Cassette`](crate::vcr::Cassette), without ever sending requests to Spotify.
+    ///
+    /// The client's token never expires. See the [`vcr`](crate::vcr) module for an example.
+    #[cfg(feature = "vcr")]
+    pub fn replay(cassette: crate::vcr::Cassette) -> Self {
+        let mut client = Self::local();
+        client.local.cassette = Some(cassette);
+        client
+    }
+
+    // A client with a placeholder token that never expires, for serving requests locally.
+    fn local() -> Self {
+        let token = Token {
+            access_token: Secret::new("local".to_owned()),
+            refresh_token: None,
+            expires_in: u32::MAX.into(),
+            created_at: chrono::Utc::now(),
+            expires_at: chrono::DateTime::<chrono::Utc>::MAX_UTC,
+            token_type: oauth2::basic::BasicTokenType::Bearer,
+            scopes: None,
+        };
 
-        let token = req.request_async(async_http_client).await?.set_timestamps();
+        let oauth = OAuthClient::new(
+            oauth2::ClientId::new("local".to_owned()),
+            None,
+            oauth2::AuthUrl::new("https://accounts.spotify.com/authorize".to_owned()).unwrap(),
+            None,
+        );
 
-        Ok(Client {
-            auto_refresh,
-            auth: token,
-            oauth: oauth_client,
+        Client {
+            auto_refresh: false,
+            refresh_on_unauthorised: true,
+            auth: RwLock::new(token),
+            oauth,
             http: reqwest::Client::new(),
+            api_url: "https://api.spotify.com/v1".to_owned(),
+            local: Local::default(),
+            default_market: RwLock::default(),
+            default_locale: RwLock::default(),
+            utc_offset: None,
+            default_limit: None,
+            retry_policy: None,
+            rate_limiter: None,
+            token_store: None,
+            on_token_refresh: None,
+            refresh_lock: AsyncMutex::default(),
+            cache: None,
+            middleware: MiddlewareChain::default(),
+            #[cfg(feature = "player")]
+            playback_cache: Mutex::default(),
+            #[cfg(feature = "player")]
+            preferred_device: RwLock::default(),
             verifier: NoVerifier,
             marker: PhantomData,
-        })
+        }
     }
 }
 
-impl<F: AuthFlow, V: Verifier> Client<Token, F, V> {
+impl<F: AuthFlow> Client<Token, F> {
     /// Get the current access token.
-    pub fn access_token(&self) -> &str {
-        self.auth.access_token.secret()
+    pub fn access_token(&self) -> String {
+        self.token().access_token.secret().clone()
+    }
+
+    /// Get the market applied to requests that support one, unless another is set on the builder.
+    pub fn default_market(&self) -> Option<Market> {
+        *self
+            .default_market
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Set the market applied to requests that support one, unless another is set on the
+    /// builder, or `None` to stop applying one.
+    ///
+    /// Builders that have already been created keep the market they were created with.
+    pub fn set_default_market(&self, market: Option<Market>) {
+        *self
+            .default_market
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = market;
+    }
+
+    /// Get the locale (and country) applied to requests that support one, unless another
+    /// is set on the builder.
+    pub fn default_locale(&self) -> Option<Locale> {
+        *self
+            .default_locale
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Set the locale (and country) applied to requests that support one, unless another
+    /// is set on the builder, or `None` to stop applying one.
+    ///
+    /// Builders that have already been created keep the locale they were created with.
+    pub fn set_default_locale(&self, locale: Option<Locale>) {
+        *self
+            .default_locale
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = locale;
+    }
+
+    /// Get the user's offset from UTC, used to tell Spotify the user's local time.
+    pub fn utc_offset(&self) -> Option<FixedOffset> {
+        self.utc_offset
+    }
+
+    /// Get the page size requested by paged endpoints, unless another is set on the builder.
+    pub fn default_limit(&self) -> Option<u32> {
+        self.default_limit
+    }
+
+    /// Get the scopes the user granted the client (see [`Token::granted_scopes`]), e.g. to
+    /// only show the features of an app the user consented to.
+    pub fn granted_scopes(&self) -> Vec<Scope> {
+        self.token().granted_scopes().to_vec()
+    }
+
+    /// Returns `true` if the user granted the client the given scope, e.g. `user-library-read`.
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.token().has_scope(scope)
     }
 
     /// Get the current refresh token. Some auth flows may not provide a refresh token,
     /// in which case it's `None`.
-    pub fn refresh_token(&self) -> Option<&str> {
-        self.auth
+    pub fn refresh_token(&self) -> Option<String> {
+        self.token()
             .refresh_token
             .as_ref()
-            .map(|t| t.secret().as_str())
+            .map(|t| t.secret().clone())
+    }
+
+    // The lock is never held across an await, and a panic while holding it can't leave
+    // the token half-written, so a poisoned lock is still safe to use.
+    fn token(&self) -> RwLockReadGuard<'_, Token> {
+        self.auth.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    #[cfg(feature = "player")]
+    fn playback_cache(&self) -> std::sync::MutexGuard<'_, PlaybackCache> {
+        self.playback_cache
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
     }
 
     /// Request a new refresh token and updates it in the client.
     /// Only some auth flows allow for token refreshing.
-    pub async fn request_refresh_token(&mut self) -> Result<()> {
-        let Some(refresh_token) = &self.auth.refresh_token else {
+    ///
+    /// If the client has a [`TokenStore`], the new token is saved to it, and if it has an
+    /// [`on_token_refresh`](ClientBuilder::on_token_refresh) callback, it's called with it.
+    ///
+    /// Dropping the future before it's done can lose a refresh token Spotify has already
+    /// replaced, or leave the old token in the store, so it shouldn't be cancelled (e.g.
+    /// by a timeout) partway through. See the
+    /// [crate documentation](crate#automatic-token-refreshing) for more.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, err(Display))
+    )]
+    pub async fn request_refresh_token(&self) -> Result<()> {
+        let _refreshing = self.refresh_lock.lock().await;
+        self.refresh().await
+    }
+
+    // Refreshes the token, which the caller has to hold `refresh_lock` for.
+    async fn refresh(&self) -> Result<()> {
+        let Some(refresh_token) = self.token().refresh_token.clone() else {
             return Err(Error::RefreshUnavailable);
         };
 
-        let token = self
+        let mut token = self
             .oauth
-            .exchange_refresh_token(refresh_token)
-            .request_async(async_http_client)
+            .exchange_refresh_token(&RefreshToken::new(refresh_token.secret().clone()))
+            .request_async(|req| oauth_request(&self.http, req))
             .await?
-            .set_timestamps();
+            .into_token();
 
-        self.auth = token;
-        Ok(())
+        // Spotify doesn't always send a new refresh token, in which case the old one
+        // remains valid.
+        if token.refresh_token.is_none() {
+            token.refresh_token = Some(refresh_token);
+        }
+
+        // The same goes for the scopes, which a refresh can't change.
+        if token.scopes.is_none() {
+            token.scopes = self.token().scopes.clone();
+        }
+
+        *self.auth.write().unwrap_or_else(PoisonError::into_inner) = token.clone();
+
+        #[cfg(feature = "tracing")]
+        tracing::info!(expires_at = %token.expires_at, "access token refreshed");
+
+        if let Some(on_token_refresh) = &self.on_token_refresh {
+            (on_token_refresh.0)(&token);
+        }
+
+        self.save_token().await
+    }
+
+    /// Create a client from the token saved in `store`, which the client keeps saving
+    /// new tokens to.
+    ///
+    /// Fails with [`Error::NotAuthenticated`] if no token has been saved yet, in which
+    /// case the client has to go through the authorisation flow first, with a
+    /// [`ClientBuilder`] given the same store. See [`token_store`](crate::token_store).
+    pub async fn from_token_store(
+        auth_flow: F,
+        store: impl TokenStore + 'static,
+        auto_refresh: bool,
+    ) -> Result<Self> {
+        ClientBuilder::new(auth_flow)
+            .auto_refresh(auto_refresh)
+            .token_store(store)
+            .from_token_store()
+            .await
+    }
+
+    // Saves the current token to the token store, if there is one.
+    pub(crate) async fn save_token(&self) -> Result<()> {
+        let Some(store) = &self.token_store else {
+            return Ok(());
+        };
+
+        let token = self.token().clone();
+        store.save(&token).await
     }
 
+    // This is a thin generic wrapper that only serialises the query and body,
+    // so that the bulk of the request logic in `send_request` isn't monomorphised
+    // for every endpoint.
     pub(crate) async fn request<P: Serialize, T: DeserializeOwned>(
-        &mut self,
+        &self,
+        method: Method,
+        endpoint: String,
+        query: Option<P>,
+        body: Option<Body<P>>,
+    ) -> Result<T> {
+        self.request_as(method, endpoint, query, body, &Overrides::default())
+            .await
+    }
+
+    // Like `request`, but sent with the given overrides of the client's settings.
+    pub(crate) async fn request_as<P: Serialize, T: DeserializeOwned>(
+        &self,
         method: Method,
         endpoint: String,
         query: Option<P>,
         body: Option<Body<P>>,
+        overrides: &Overrides,
     ) -> Result<T> {
-        if self.auth.is_expired() {
-            if self.auto_refresh {
-                self.request_refresh_token().await?;
-            } else {
-                return Err(Error::ExpiredToken);
+        let (_, body) = self
+            .request_bytes(method, endpoint.clone(), query, body, overrides)
+            .await?;
+        deserialize_body(&endpoint, body)
+    }
+
+    // Like `request_as`, but returns the response's JSON as is, along with its headers.
+    pub(crate) async fn request_raw<P: Serialize>(
+        &self,
+        method: Method,
+        endpoint: String,
+        query: Option<P>,
+        body: Option<Body<P>>,
+        overrides: &Overrides,
+    ) -> Result<RawResponse> {
+        let (headers, body) = self
+            .request_bytes(method, endpoint.clone(), query, body, overrides)
+            .await?;
+
+        let body = if body.is_empty() {
+            serde_json::Value::Null
+        } else {
+            deserialize_body(&endpoint, body)?
+        };
+
+        Ok(RawResponse { headers, body })
+    }
+
+    async fn request_bytes<P: Serialize>(
+        &self,
+        method: Method,
+        endpoint: String,
+        query: Option<P>,
+        body: Option<Body<P>>,
+        overrides: &Overrides,
+    ) -> Result<(HeaderMap, Bytes)> {
+        let query = query
+            .map(|q| serde_urlencoded::to_string(q))
+            .transpose()
+            .map_err(|e| Error::Http(e.to_string()))?;
+
+        let body = body
+            .map(|b| match b {
+                Body::Json(j) => serde_json::to_vec(&j).map(RawBody::Json),
+                Body::File(f) => Ok(RawBody::File(f)),
+            })
+            .transpose()
+            .map_err(|e| Error::Http(e.to_string()))?;
+
+        self.send_request(method, endpoint, query, body, overrides)
+            .await
+    }
+
+    async fn send_request(
+        &self,
+        method: Method,
+        endpoint: String,
+        query: Option<String>,
+        body: Option<RawBody>,
+        overrides: &Overrides,
+    ) -> Result<(HeaderMap, Bytes)> {
+        // A token supplied for this request has scopes the client doesn't know about.
+        if overrides.token.is_none() {
+            self.check_scope(&method, &endpoint)?;
+        }
+
+        // Anything other than reading may change the playback state.
+        #[cfg(feature = "player")]
+        if method != Method::GET {
+            self.playback_cache().clear();
+        }
+
+        // A token supplied for this request belongs to someone else, so the client's
+        // own token doesn't need to be refreshed.
+        if overrides.token.is_none() {
+            self.refresh_if_expired().await?;
+        }
+
+        // A token that's rejected before it was due to expire (e.g. because it was revoked)
+        // is refreshed, and the request sent once more with the new one.
+        let replay = overrides.token.is_none()
+            && self.auto_refresh
+            && self.refresh_on_unauthorised
+            && self.token().is_refreshable();
+
+        if !replay {
+            return self
+                .send_authorised(method, endpoint, query, body, overrides)
+                .await;
+        }
+
+        let rejected_token = self.token().access_token.clone();
+        let result = self
+            .send_authorised(
+                method.clone(),
+                endpoint.clone(),
+                query.clone(),
+                body.clone(),
+                overrides,
+            )
+            .await;
+
+        match result {
+            // Spotify doesn't always explain a `401` in a JSON body.
+            Err(err) if err.status() == Some(401) => {
+                self.refresh_unless_changed(&rejected_token).await?;
+                self.send_authorised(method, endpoint, query, body, overrides)
+                    .await
+            }
+            result => result,
+        }
+    }
+
+    // Refreshes the token after Spotify rejected `rejected_token`, unless another request
+    // has replaced it in the meantime.
+    async fn refresh_unless_changed(&self, rejected_token: &Secret) -> Result<()> {
+        let _refreshing = self.refresh_lock.lock().await;
+
+        if self.token().access_token == *rejected_token {
+            self.refresh().await?;
+        }
+
+        Ok(())
+    }
+
+    // Fails if the token's scopes are known and don't include one the request needs,
+    // instead of sending a request Spotify would reject.
+    fn check_scope(&self, method: &Method, endpoint: &str) -> Result<()> {
+        let required = scope::required(method, endpoint);
+        let token = self.token();
+
+        if token.scopes.is_none() {
+            return Ok(());
+        }
+
+        match required
+            .iter()
+            .find(|group| !group.iter().any(|scope| token.has_scope(scope)))
+        {
+            Some(group) => Err(Error::MissingScope(group.join(" or "))),
+            None => Ok(()),
+        }
+    }
+
+    async fn refresh_if_expired(&self) -> Result<()> {
+        if !self.token().is_expired() {
+            return Ok(());
+        }
+
+        if !self.auto_refresh {
+            return Err(Error::ExpiredToken);
+        }
+
+        // When several requests find the token expired at once, the first one refreshes
+        // it while the others wait, and then find it already refreshed.
+        let _refreshing = self.refresh_lock.lock().await;
+
+        if self.token().is_expired() {
+            self.refresh().await?;
+        }
+
+        Ok(())
+    }
+
+    // Sends the request with the current token (or the given one), returning the
+    // response's headers and body.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "spotify_request",
+            level = "debug",
+            skip_all,
+            fields(%method, %endpoint, status, retries, latency_ms),
+            err(Display)
+        )
+    )]
+    async fn send_authorised(
+        &self,
+        method: Method,
+        endpoint: String,
+        query: Option<String>,
+        body: Option<RawBody>,
+        overrides: &Overrides,
+    ) -> Result<(HeaderMap, Bytes)> {
+        let mut url = Url::parse(&format!("{}{endpoint}", self.api_url))
+            .map_err(|e| Error::Http(e.to_string()))?;
+
+        if let Some(query) = query.filter(|q| !q.is_empty()) {
+            // Some endpoints already contain query parameters in their path.
+            let query = match url.query() {
+                Some(existing) => format!("{existing}&{query}"),
+                None => query,
+            };
+
+            url.set_query(Some(&query));
+        }
+
+        #[cfg(feature = "mock")]
+        if let Some(mock) = &self.local.mock {
+            let body = body.map(|b| match b {
+                RawBody::Json(b) | RawBody::File(b) => b,
+            });
+            let (status, body) = mock.handle(method, &endpoint, url.query(), body);
+
+            overrides.record(status, &HeaderMap::new());
+            return response_body(status, body).map(|b| (HeaderMap::new(), b));
+        }
+
+        #[cfg(feature = "offline")]
+        if let Some(store) = &self.local.store {
+            let (status, body) =
+                crate::offline::respond(store.as_ref(), &method, &endpoint, url.query());
+
+            overrides.record(status, &HeaderMap::new());
+            return response_body(status, body).map(|b| (HeaderMap::new(), b));
+        }
+
+        #[cfg(feature = "vcr")]
+        if let Some(cassette) = &self.local.cassette {
+            if let Some((status, body)) =
+                cassette.replay_request(&method, &endpoint, url.query())?
+            {
+                overrides.record(status, &HeaderMap::new());
+                return response_body(status, body).map(|b| (HeaderMap::new(), b));
             }
         }
 
-        let mut req = self
-            .http
-            .request(method, format!("https://api.spotify.com/v1{endpoint}"))
-            .bearer_auth(self.auth.access_token.secret());
+        #[cfg(feature = "vcr")]
+        let recorded = (method.clone(), url.query().map(ToOwned::to_owned));
+
+        // Only the client's own reads are cached, as a token given for a single request
+        // belongs to someone else, and not the ones about the current user, as the cache
+        // may be shared with clients for other users.
+        let personal =
+            endpoint == "/me" || endpoint.starts_with("/me/") || endpoint.starts_with("/me?");
+        let cached = match &self.cache {
+            Some(cache) if method == Method::GET && overrides.token.is_none() && !personal => {
+                let key = url.to_string();
+                let response = cache.get(&key).await;
+
+                if let Some(response) = response.as_ref().filter(|r| r.is_fresh()) {
+                    overrides.record(StatusCode::OK, &HeaderMap::new());
+                    return response_body(StatusCode::OK, response.body.clone())
+                        .map(|b| (HeaderMap::new(), b));
+                }
+
+                Some((cache, key, response))
+            }
+            _ => None,
+        };
+
+        #[cfg(feature = "otel")]
+        let cx = crate::otel::start(&method, &url);
 
-        if let Some(q) = query {
-            req = req.query(&q);
+        let token = match &overrides.token {
+            Some(token) => token.clone(),
+            None => self.token().access_token.clone(),
+        };
+
+        // Only requests that can safely be sent twice are retried after a server error.
+        let idempotent = method == Method::GET || method == Method::PUT;
+        let retry_policy = overrides
+            .retry_policy
+            .as_ref()
+            .or(self.retry_policy.as_ref());
+
+        let mut req = self.http.request(method, url).bearer_auth(token.secret());
+
+        if let Some((
+            _,
+            _,
+            Some(CachedResponse {
+                etag: Some(etag), ..
+            }),
+        )) = &cached
+        {
+            req = req.header(IF_NONE_MATCH, etag);
+        }
+
+        #[cfg(feature = "otel")]
+        {
+            req = req.headers(crate::otel::headers(&cx));
         }
 
         if let Some(b) = body {
             match b {
-                Body::Json(j) => req = req.json(&j),
-                Body::File(f) => req = req.body(f),
+                RawBody::Json(j) => req = req.header(CONTENT_TYPE, "application/json").body(j),
+                RawBody::File(f) => req = req.body(f),
             }
         } else {
             // Used because Spotify wants a Content-Length header for the PUT /audiobooks/me endpoint even though there is no body
@@ -287,17 +1018,117 @@ impl<F: AuthFlow, V: Verifier> Client<Token, F, V> {
             req = req.header(CONTENT_LENGTH, 0);
         }
 
-        let res = req.send().await?;
+        #[cfg(feature = "tracing")]
+        let started = web_time::Instant::now();
 
-        if res.status().is_success() {
-            Ok(res.json().await?)
-        } else {
-            Err(res.json::<SpotifyError>().await?.into())
+        let req = req.build()?;
+
+        let mut retries = 0;
+        let res = loop {
+            if let Some(limiter) = &self.rate_limiter {
+                limiter.acquire().await;
+            }
+
+            // The body is always in memory, so the request can always be cloned.
+            let res = match req.try_clone() {
+                Some(req) => self.middleware.send(&self.http, req).await,
+                None => break self.middleware.send(&self.http, req).await,
+            };
+
+            let retry = match (&res, retry_policy) {
+                (Ok(res), Some(policy)) if res.status() == StatusCode::TOO_MANY_REQUESTS => policy
+                    .wait(retries, retry::retry_after(res.headers()))
+                    .map(|wait| (policy, wait)),
+                (Ok(res), Some(policy)) if idempotent && retry::is_server_error(res.status()) => {
+                    policy.server_error_wait(retries).map(|wait| (policy, wait))
+                }
+                _ => None,
+            };
+
+            let Some((policy, wait)) = retry else {
+                break res;
+            };
+
+            retries += 1;
+
+            #[cfg(feature = "tracing")]
+            if let Ok(res) = &res {
+                tracing::warn!(
+                    attempt = retries,
+                    status = res.status().as_u16(),
+                    delay_ms = wait.as_millis() as u64,
+                    "retry"
+                );
+            }
+
+            policy.sleep(wait).await;
+        };
+
+        #[cfg(feature = "tracing")]
+        {
+            let span = tracing::Span::current();
+            span.record("retries", retries);
+            span.record("latency_ms", started.elapsed().as_millis() as u64);
+
+            if let Ok(res) = &res {
+                span.record("status", res.status().as_u16());
+                tracing::debug!(status = res.status().as_u16(), "response received");
+            }
+        }
+
+        #[cfg(feature = "otel")]
+        crate::otel::end(&cx, res.as_ref().map(|r| r.status()));
+
+        let res = res?;
+
+        // The body is read into a single buffer and deserialised from there,
+        // instead of going through `Response::json`, which buffers it again internally.
+        let status = res.status();
+
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            return Err(Error::RateLimited {
+                retry_after: retry::retry_after(res.headers()),
+            });
+        }
+
+        if let Some((cache, key, response)) = &cached {
+            if status == StatusCode::NOT_MODIFIED {
+                if let Some(response) = response.clone() {
+                    let body = response.body.clone();
+
+                    if let Some(response) = response.revalidated(res.headers()) {
+                        cache.put(key.clone(), response).await;
+                    }
+
+                    overrides.record(status, res.headers());
+                    return Ok((res.headers().clone(), body));
+                }
+            }
+        }
+
+        let headers = res.headers().clone();
+        let body = res.bytes().await?;
+
+        if let Some((cache, key, _)) = cached {
+            if status.is_success() {
+                if let Some(response) = CachedResponse::from_response(&headers, body.clone()) {
+                    cache.put(key, response).await;
+                }
+            }
+        }
+
+        #[cfg(feature = "vcr")]
+        if let Some(cassette) = &self.local.cassette {
+            let (method, query) = recorded;
+            cassette.record_response(&method, &endpoint, query.as_deref(), status, &body)?;
         }
+
+        overrides.record(status, &headers);
+        response_body(status, body).map(|b| (headers, b))
     }
 
     pub(crate) async fn get<P: Serialize, T: DeserializeOwned>(
-        &mut self,
+        &self,
         endpoint: String,
         query: impl Into<Option<P>>,
     ) -> Result<T> {
@@ -305,17 +1136,8 @@ impl<F: AuthFlow, V: Verifier> Client<Token, F, V> {
             .await
     }
 
-    pub(crate) async fn post<P: Serialize, T: DeserializeOwned>(
-        &mut self,
-        endpoint: String,
-        body: impl Into<Option<Body<P>>>,
-    ) -> Result<T> {
-        self.request(Method::POST, endpoint, None, body.into())
-            .await
-    }
-
     pub(crate) async fn put<P: Serialize, T: DeserializeOwned>(
-        &mut self,
+        &self,
         endpoint: String,
         body: impl Into<Option<Body<P>>>,
     ) -> Result<T> {
@@ -323,7 +1145,7 @@ impl<F: AuthFlow, V: Verifier> Client<Token, F, V> {
     }
 
     pub(crate) async fn delete<P: Serialize, T: DeserializeOwned>(
-        &mut self,
+        &self,
         endpoint: String,
         body: impl Into<Option<Body<P>>>,
     ) -> Result<T> {
@@ -331,334 +1153,810 @@ impl<F: AuthFlow, V: Verifier> Client<Token, F, V> {
             .await
     }
 
-    fn builder<E: Endpoint>(&mut self, endpoint: E) -> Builder<'_, F, V, E> {
+    /// Send a `GET` request to an endpoint the crate doesn't support (yet), returning the
+    /// response's JSON as is, or `null` if the response is empty.
+    ///
+    /// The request is sent like any other: the token is refreshed if needed, the client's
+    /// retry policy, rate limiter and middleware apply, and errors are returned as
+    /// [`Error`]s. The path is relative to the API's base URL (e.g. `/me/shows`), but a full
+    /// URL to the API (such as a page's `next` link) also works. Pass `()` as the query
+    /// if there is none.
+    ///
+    /// ```no_run
+    /// # async fn example(spotify: &spotify_rs::AuthCodeClient) -> spotify_rs::SpotifyResult<()> {
+    /// let shows = spotify.api_get("/me/shows", [("limit", "10")]).await?;
+    /// let first = shows["items"][0]["show"]["name"].as_str();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn api_get(
+        &self,
+        path: impl AsRef<str>,
+        query: impl Serialize,
+    ) -> Result<serde_json::Value> {
+        self.api_request(Method::GET, path.as_ref(), Some(query), None)
+            .await
+    }
+
+    /// Send a `POST` request with an optional JSON body to an endpoint the crate doesn't
+    /// support (yet), like [`api_get`](Self::api_get).
+    ///
+    /// The body can be built with [`serde_json::json!`], or converted from any
+    /// serialisable value with [`serde_json::to_value`].
+    pub async fn api_post(
+        &self,
+        path: impl AsRef<str>,
+        body: Option<serde_json::Value>,
+    ) -> Result<serde_json::Value> {
+        self.api_request(Method::POST, path.as_ref(), None::<()>, body)
+            .await
+    }
+
+    /// Send a `PUT` request with an optional JSON body to an endpoint the crate doesn't
+    /// support (yet), like [`api_get`](Self::api_get).
+    ///
+    /// ```no_run
+    /// # async fn example(spotify: &spotify_rs::AuthCodeClient) -> spotify_rs::SpotifyResult<()> {
+    /// use serde_json::json;
+    ///
+    /// spotify
+    ///     .api_put("/me/shows", Some(json!({ "ids": ["5CfCWKI5pZ28U0uOzXkDHe"] })))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn api_put(
+        &self,
+        path: impl AsRef<str>,
+        body: Option<serde_json::Value>,
+    ) -> Result<serde_json::Value> {
+        self.api_request(Method::PUT, path.as_ref(), None::<()>, body)
+            .await
+    }
+
+    /// Send a `DELETE` request with an optional JSON body to an endpoint the crate doesn't
+    /// support (yet), like [`api_get`](Self::api_get).
+    pub async fn api_delete(
+        &self,
+        path: impl AsRef<str>,
+        body: Option<serde_json::Value>,
+    ) -> Result<serde_json::Value> {
+        self.api_request(Method::DELETE, path.as_ref(), None::<()>, body)
+            .await
+    }
+
+    async fn api_request<Q: Serialize>(
+        &self,
+        method: Method,
+        path: &str,
+        query: Option<Q>,
+        body: Option<serde_json::Value>,
+    ) -> Result<serde_json::Value> {
+        let path = path.strip_prefix(self.api_url.as_str()).unwrap_or(path);
+        let path = if path.starts_with('/') {
+            path.to_owned()
+        } else {
+            format!("/{path}")
+        };
+
+        let query = query
+            .map(|q| serde_urlencoded::to_string(q))
+            .transpose()
+            .map_err(|e| Error::Http(e.to_string()))?;
+        let body = body
+            .map(|b| serde_json::to_vec(&b).map(RawBody::Json))
+            .transpose()
+            .map_err(|e| Error::Http(e.to_string()))?;
+
+        let (_, body) = self
+            .send_request(method, path.clone(), query, body, &Overrides::default())
+            .await?;
+
+        if body.is_empty() {
+            Ok(serde_json::Value::Null)
+        } else {
+            deserialize_body(&path, body)
+        }
+    }
+
+    /// Fetch multiple objects of a type that can only be requested one at a time
+    /// (playlists, users and audio analyses), running up to `concurrency` requests at once.
+    ///
+    /// The results are in the same order as `ids`. A failed request doesn't stop the
+    /// others, so each result has to be checked on its own.
+    ///
+    /// ```no_run
+    /// # async fn run(spotify: &spotify_rs::AuthCodeClient) -> spotify_rs::SpotifyResult<()> {
+    /// use spotify_rs::model::user::User;
+    ///
+    /// let users = spotify.fetch_many::<User, _>(&["user_1", "user_2"], 4).await?;
+    ///
+    /// for user in users {
+    ///     match user {
+    ///         Ok(user) => println!("{}", user.id),
+    ///         Err(err) => eprintln!("{err}"),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn fetch_many<T: FetchById, I: AsRef<str>>(
+        &self,
+        ids: &[I],
+        concurrency: usize,
+    ) -> Result<Vec<Result<T>>> {
+        // The token is refreshed once up front, rather than by every request at once.
+        self.refresh_if_expired().await?;
+
+        let results = stream::iter(ids)
+            .map(|id| self.get::<(), T>(T::path(id.as_ref()), None))
+            .buffered(concurrency.max(1))
+            .collect()
+            .await;
+
+        Ok(results)
+    }
+
+    fn builder<E: Endpoint>(&self, endpoint: E) -> Builder<'_, F, E> {
         Builder {
-            spotify: self,
+            spotify: ClientRef::new(self),
             endpoint,
         }
     }
 
-    pub fn album(&mut self, id: impl Into<String>) -> Builder<'_, F, V, AlbumEndpoint> {
+    pub fn album(&self, id: impl AsId<AlbumId>) -> Builder<'_, F, AlbumEndpoint> {
         self.builder(AlbumEndpoint {
-            id: id.into(),
-            market: None,
+            id: id.as_id().to_owned(),
+            market: self.default_market(),
         })
     }
 
-    pub fn albums<T: AsRef<str>>(&mut self, ids: &[T]) -> Builder<'_, F, V, AlbumsEndpoint> {
+    pub fn albums<T: AsId<AlbumId>>(&self, ids: &[T]) -> Builder<'_, F, AlbumsEndpoint> {
         self.builder(AlbumsEndpoint {
-            ids: query_list(ids),
-            market: None,
+            ids: query_list(&as_ids(ids)),
+            market: self.default_market(),
         })
     }
 
     pub fn album_tracks(
-        &mut self,
-        album_id: impl Into<String>,
-    ) -> Builder<'_, F, V, AlbumTracksEndpoint> {
+        &self,
+        album_id: impl AsId<AlbumId>,
+    ) -> Builder<'_, F, AlbumTracksEndpoint> {
         self.builder(AlbumTracksEndpoint {
-            id: album_id.into(),
+            id: album_id.as_id().to_owned(),
+            market: self.default_market(),
+            limit: self.default_limit.map(Limit::new),
+            ..Default::default()
+        })
+    }
+
+    #[cfg(feature = "browse")]
+    pub fn new_releases(&self) -> Builder<'_, F, NewReleasesEndpoint> {
+        self.builder(NewReleasesEndpoint {
+            limit: self.default_limit.map(Limit::new),
             ..Default::default()
         })
     }
 
-    pub fn new_releases(&mut self) -> Builder<'_, F, V, NewReleasesEndpoint> {
-        self.builder(NewReleasesEndpoint::default())
+    pub fn artist(&self, id: impl AsId<ArtistId>) -> Builder<'_, F, ArtistEndpoint> {
+        self.builder(ArtistEndpoint {
+            id: id.as_id().to_owned(),
+        })
     }
 
-    pub fn artist(&mut self, id: impl Into<String>) -> Builder<'_, F, V, ArtistEndpoint> {
-        self.builder(ArtistEndpoint { id: id.into() })
+    /// Get an artist's albums, like [`artist(id).albums()`](Builder::albums).
+    pub fn artist_albums(&self, id: impl AsId<ArtistId>) -> Builder<'_, F, ArtistAlbumsEndpoint> {
+        self.artist(id).albums()
     }
 
-    pub async fn get_artists<T: AsRef<str>>(&mut self, ids: &[T]) -> Result<Vec<Artist>> {
-        self.get("/artists".to_owned(), [("ids", query_list(ids))])
-            .await
-            .map(|a: Artists| a.artists)
+    /// Get an artist's top tracks, like [`artist(id).top_tracks()`](Builder::top_tracks).
+    pub fn artist_top_tracks(
+        &self,
+        id: impl AsId<ArtistId>,
+    ) -> Builder<'_, F, ArtistTopTracksEndpoint> {
+        self.artist(id).top_tracks()
+    }
+
+    /// Get the artists similar to an artist, based on the listening history of
+    /// Spotify's users, like [`artist(id).related_artists()`](Builder::related_artists).
+    pub fn artist_related_artists(
+        &self,
+        id: impl AsId<ArtistId>,
+    ) -> Builder<'_, F, ArtistRelatedArtistsEndpoint> {
+        self.artist(id).related_artists()
+    }
+
+    pub fn artists<T: AsId<ArtistId>>(&self, ids: &[T]) -> Builder<'_, F, ArtistsEndpoint> {
+        self.builder(ArtistsEndpoint {
+            ids: query_list(&as_ids(ids)),
+        })
+    }
+
+    /// Get multiple artists at once, like [`artists(ids).get()`](Self::artists).
+    pub async fn get_artists<T: AsId<ArtistId>>(&self, ids: &[T]) -> Result<Vec<Artist>> {
+        self.artists(ids).get().await
     }
 
-    pub fn audiobook(&mut self, id: impl Into<String>) -> Builder<'_, F, V, AudiobookEndpoint> {
+    #[cfg(feature = "audiobooks")]
+    pub fn audiobook(&self, id: impl AsId<AudiobookId>) -> Builder<'_, F, AudiobookEndpoint> {
         self.builder(AudiobookEndpoint {
-            id: id.into(),
-            market: None,
+            id: id.as_id().to_owned(),
+            market: self.default_market(),
         })
     }
 
-    pub fn audiobooks<T: AsRef<str>>(
-        &mut self,
+    #[cfg(feature = "audiobooks")]
+    pub fn audiobooks<T: AsId<AudiobookId>>(
+        &self,
         ids: &[T],
-    ) -> Builder<'_, F, V, AudiobooksEndpoint> {
+    ) -> Builder<'_, F, AudiobooksEndpoint> {
         self.builder(AudiobooksEndpoint {
-            ids: query_list(ids),
-            market: None,
+            ids: query_list(&as_ids(ids)),
+            market: self.default_market(),
         })
     }
 
+    #[cfg(feature = "audiobooks")]
     pub fn audiobook_chapters(
-        &mut self,
-        audiobook_id: impl Into<String>,
-    ) -> Builder<'_, F, V, AudiobookChaptersEndpoint> {
+        &self,
+        audiobook_id: impl AsId<AudiobookId>,
+    ) -> Builder<'_, F, AudiobookChaptersEndpoint> {
         self.builder(AudiobookChaptersEndpoint {
-            id: audiobook_id.into(),
+            id: audiobook_id.as_id().to_owned(),
+            market: self.default_market(),
+            limit: self.default_limit.map(Limit::new),
             ..Default::default()
         })
     }
 
-    pub fn browse_category(
-        &mut self,
-        id: impl Into<String>,
-    ) -> Builder<'_, F, V, BrowseCategoryEndpoint> {
+    #[cfg(feature = "browse")]
+    pub fn browse_category(&self, id: impl Into<String>) -> Builder<'_, F, BrowseCategoryEndpoint> {
+        let locale = self.default_locale();
+
         self.builder(BrowseCategoryEndpoint {
             id: id.into(),
+            country: locale.map(|l| l.country().to_string()),
+            locale: locale.map(String::from),
+        })
+    }
+
+    #[cfg(feature = "browse")]
+    pub fn browse_categories(&self) -> Builder<'_, F, BrowseCategoriesEndpoint> {
+        let locale = self.default_locale();
+
+        self.builder(BrowseCategoriesEndpoint {
+            country: locale.map(|l| l.country().to_string()),
+            locale: locale.map(String::from),
+            limit: self.default_limit.map(Limit::new),
             ..Default::default()
         })
     }
 
-    pub fn browse_categories(&mut self) -> Builder<'_, F, V, BrowseCategoriesEndpoint> {
-        self.builder(BrowseCategoriesEndpoint::default())
+    /// Get every browse category, going through all of the pages.
+    ///
+    /// If a `locale` is given (e.g. `es_MX`) and some categories don't have a name in
+    /// that locale, their names (and any categories missing from the localised list)
+    /// are taken from Spotify's default locale instead. The categories are in the order
+    /// the API returns them, so the list is stable for use in menus.
+    ///
+    /// The client's [default locale](Self::default_locale) is used for either argument
+    /// that's `None`.
+    #[cfg(feature = "browse")]
+    pub async fn get_all_browse_categories(
+        &self,
+        locale: Option<&str>,
+        country: Option<&str>,
+    ) -> Result<Vec<Category>> {
+        let default = self.default_locale();
+        let default_locale = default.map(|l| l.to_string());
+        let locale = locale.or(default_locale.as_deref());
+        let country = country.or(default.map(|l| l.country().as_str()));
+
+        let mut categories = self.all_browse_categories(locale, country).await?;
+
+        if locale.is_none() || categories.iter().all(|c| !c.name.is_empty()) {
+            return Ok(categories);
+        }
+
+        for fallback in self.all_browse_categories(None, country).await? {
+            match categories.iter_mut().find(|c| c.id == fallback.id) {
+                Some(category) if category.name.is_empty() => category.name = fallback.name,
+                Some(_) => {}
+                None => categories.push(fallback),
+            }
+        }
+
+        Ok(categories)
+    }
+
+    #[cfg(feature = "browse")]
+    async fn all_browse_categories(
+        &self,
+        locale: Option<&str>,
+        country: Option<&str>,
+    ) -> Result<Vec<Category>> {
+        let mut categories = Vec::new();
+
+        loop {
+            let page = self
+                .builder(BrowseCategoriesEndpoint {
+                    country: country.map(ToOwned::to_owned),
+                    locale: locale.map(ToOwned::to_owned),
+                    limit: Some(Limit::new(50)),
+                    offset: Some(categories.len() as u32),
+                })
+                .get()
+                .await?;
+
+            let done = page.next.is_none() || page.items.is_empty();
+            categories.extend(page.items);
+
+            if done {
+                return Ok(categories);
+            }
+        }
     }
 
     /// *Note: Spotify's API returns `500 Server error`.*
-    pub fn chapter(&mut self, id: impl Into<String>) -> Builder<'_, F, V, ChapterEndpoint> {
+    #[cfg(feature = "audiobooks")]
+    pub fn chapter(&self, id: impl AsId<ChapterId>) -> Builder<'_, F, ChapterEndpoint> {
         self.builder(ChapterEndpoint {
-            id: id.into(),
-            market: None,
+            id: id.as_id().to_owned(),
+            market: self.default_market(),
         })
     }
 
     /// *Note: Spotify's API returns `500 Server error`.*
-    pub fn chapters<T: AsRef<str>>(&mut self, ids: &[T]) -> Builder<'_, F, V, ChaptersEndpoint> {
+    #[cfg(feature = "audiobooks")]
+    pub fn chapters<T: AsId<ChapterId>>(&self, ids: &[T]) -> Builder<'_, F, ChaptersEndpoint> {
         self.builder(ChaptersEndpoint {
-            ids: query_list(ids),
-            market: None,
+            ids: query_list(&as_ids(ids)),
+            market: self.default_market(),
         })
     }
 
-    pub fn episode(&mut self, id: impl Into<String>) -> Builder<'_, F, V, EpisodeEndpoint> {
+    #[cfg(feature = "shows")]
+    pub fn episode(&self, id: impl AsId<EpisodeId>) -> Builder<'_, F, EpisodeEndpoint> {
         self.builder(EpisodeEndpoint {
-            id: id.into(),
-            market: None,
+            id: id.as_id().to_owned(),
+            market: self.default_market(),
         })
     }
 
-    pub fn episodes<T: AsRef<str>>(&mut self, ids: &[T]) -> Builder<'_, F, V, EpisodesEndpoint> {
+    #[cfg(feature = "shows")]
+    pub fn episodes<T: AsId<EpisodeId>>(&self, ids: &[T]) -> Builder<'_, F, EpisodesEndpoint> {
         self.builder(EpisodesEndpoint {
-            ids: query_list(ids),
-            market: None,
+            ids: query_list(&as_ids(ids)),
+            market: self.default_market(),
         })
     }
 
-    pub async fn get_genre_seeds(&mut self) -> Result<Vec<String>> {
+    pub async fn get_genre_seeds(&self) -> Result<Vec<String>> {
         self.get::<(), _>("/recommendations/available-genre-seeds".to_owned(), None)
             .await
             .map(|g: Genres| g.genres)
     }
 
-    pub async fn get_available_markets(&mut self) -> Result<Vec<String>> {
+    pub async fn get_available_markets(&self) -> Result<Vec<String>> {
         self.get::<(), _>("/markets".to_owned(), None)
             .await
             .map(|m: Markets| m.markets)
     }
 
-    pub fn playlist(&mut self, id: impl Into<String>) -> Builder<'_, F, V, PlaylistEndpoint> {
+    #[cfg(feature = "playlists")]
+    pub fn playlist(&self, id: impl AsId<PlaylistId>) -> Builder<'_, F, PlaylistEndpoint> {
         self.builder(PlaylistEndpoint {
-            id: id.into(),
-            ..Default::default()
+            id: id.as_id().to_owned(),
+            market: self.default_market(),
         })
     }
 
+    /// Get several playlists at once, with up to `concurrency` requests in flight, as the
+    /// API has no endpoint for getting multiple playlists.
+    ///
+    /// If `all_items` is `true`, each playlist's `tracks` contains all of its items, going
+    /// through all of the pages, instead of only the first page.
+    ///
+    /// The results are in the same order as the IDs. A playlist that can't be fetched
+    /// (e.g. because it doesn't exist) doesn't affect the others, so each result has to
+    /// be checked on its own.
+    ///
+    /// ```no_run
+    /// # async fn run(spotify: &spotify_rs::ClientCredsClient) -> spotify_rs::SpotifyResult<()> {
+    /// let ids = ["37i9dQZF1DXcBWIGoYBM5M", "37i9dQZF1DX0XUsuxWHRQd"];
+    ///
+    /// for playlist in spotify.playlists_concurrent(&ids, 4, true).await? {
+    ///     match playlist {
+    ///         Ok(playlist) => println!("{}: {} items", playlist.name, playlist.tracks.items.len()),
+    ///         Err(err) => eprintln!("{err}"),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "playlists")]
+    pub async fn playlists_concurrent<T: AsId<PlaylistId>>(
+        &self,
+        ids: &[T],
+        concurrency: usize,
+        all_items: bool,
+    ) -> Result<Vec<Result<Playlist>>> {
+        // The token is refreshed once up front, rather than by every request.
+        self.refresh_if_expired().await?;
+
+        let results = stream::iter(ids)
+            .map(|id| async move {
+                let mut playlist = self.playlist(id.as_id()).get().await?;
+
+                if all_items {
+                    self.get_remaining_playlist_items(&mut playlist).await?;
+                }
+
+                Ok(playlist)
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await;
+
+        Ok(results)
+    }
+
+    // Adds the items after the first page to the playlist's `tracks`.
+    #[cfg(feature = "playlists")]
+    pub(crate) async fn get_remaining_playlist_items(&self, playlist: &mut Playlist) -> Result<()> {
+        let items = &mut playlist.tracks;
+
+        while items.next.is_some() {
+            let page = self
+                .playlist_items(playlist.id.as_str())
+                .limit(50)
+                .offset(items.items.len() as u32)
+                .get()
+                .await?;
+
+            items.next = page.next.filter(|_| !page.items.is_empty());
+            items.items.extend(page.items);
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "playlists")]
     pub fn change_playlist_details(
-        &mut self,
-        id: impl Into<String>,
-    ) -> Builder<'_, F, V, ChangePlaylistDetailsEndpoint> {
+        &self,
+        id: impl AsId<PlaylistId>,
+    ) -> Builder<'_, F, ChangePlaylistDetailsEndpoint> {
         self.builder(ChangePlaylistDetailsEndpoint {
-            id: id.into(),
+            id: id.as_id().to_owned(),
             ..Default::default()
         })
     }
 
+    #[cfg(feature = "playlists")]
     pub fn playlist_items(
-        &mut self,
-        id: impl Into<String>,
-    ) -> Builder<'_, F, V, PlaylistItemsEndpoint> {
+        &self,
+        id: impl AsId<PlaylistId>,
+    ) -> Builder<'_, F, PlaylistItemsEndpoint> {
         self.builder(PlaylistItemsEndpoint {
-            id: id.into(),
+            id: id.as_id().to_owned(),
+            market: self.default_market(),
+            limit: self.default_limit.map(Limit::new),
             ..Default::default()
         })
     }
 
+    /// Modify a playlist's items with a chain of requests, each of which is made against
+    /// the snapshot of the playlist returned by the one before.
+    ///
+    /// See [`PlaylistEditor`].
+    #[cfg(feature = "playlists")]
+    pub fn edit_playlist(&self, id: impl AsId<PlaylistId>) -> PlaylistEditor<'_, F> {
+        PlaylistEditor {
+            spotify: self,
+            id: id.as_id().to_owned(),
+            snapshot_id: None,
+        }
+    }
+
+    #[cfg(feature = "playlists")]
     pub fn update_playlist_items(
-        &mut self,
-        id: impl Into<String>,
+        &self,
+        id: impl AsId<PlaylistId>,
         range_start: u32,
         insert_before: u32,
-    ) -> Builder<'_, F, V, UpdatePlaylistItemsEndpoint> {
+    ) -> Builder<'_, F, UpdatePlaylistItemsEndpoint> {
         self.builder(UpdatePlaylistItemsEndpoint {
-            id: id.into(),
+            id: id.as_id().to_owned(),
             range_start,
             insert_before,
             ..Default::default()
         })
     }
 
+    /// Replace all of a playlist's items with the items with the given URIs, which can
+    /// be tracks or episodes.
+    ///
+    /// Any number of URIs can be given, as they're split into as many requests as needed.
+    /// An empty list removes all of the playlist's items.
+    #[cfg(feature = "playlists")]
+    pub fn replace_playlist_items<T: ToString>(
+        &self,
+        id: impl AsId<PlaylistId>,
+        item_uris: &[T],
+    ) -> Builder<'_, F, ReplacePlaylistItemsEndpoint> {
+        self.builder(ReplacePlaylistItemsEndpoint {
+            id: id.as_id().to_owned(),
+            uris: item_uris.iter().map(ToString::to_string).collect(),
+        })
+    }
+
+    /// Add the items with the given URIs, which can be tracks or episodes, to a playlist.
+    ///
+    /// Any number of URIs can be given, as they're split into as many requests as needed.
+    #[cfg(feature = "playlists")]
     pub fn add_items_to_playlist<T: ToString>(
-        &mut self,
-        id: impl Into<String>,
+        &self,
+        id: impl AsId<PlaylistId>,
         item_uris: &[T],
-    ) -> Builder<'_, F, V, AddPlaylistItemsEndpoint> {
+    ) -> Builder<'_, F, AddPlaylistItemsEndpoint> {
         self.builder(AddPlaylistItemsEndpoint {
-            id: id.into(),
+            id: id.as_id().to_owned(),
             uris: item_uris.iter().map(ToString::to_string).collect(),
             position: None,
         })
     }
 
+    /// Remove every occurrence of the items with the given URIs from a playlist.
+    ///
+    /// Any number of URIs can be given, as they're split into as many requests as needed.
+    /// Items can also be removed only at certain positions with
+    /// [`item_at_positions`](Builder::item_at_positions), in which case `item_uris` can be empty.
+    #[cfg(feature = "playlists")]
     pub fn remove_playlist_items<T: AsRef<str>>(
-        &mut self,
-        id: impl Into<String>,
+        &self,
+        id: impl AsId<PlaylistId>,
         item_uris: &[T],
-    ) -> Builder<'_, F, V, RemovePlaylistItemsEndpoint> {
+    ) -> Builder<'_, F, RemovePlaylistItemsEndpoint> {
         let tracks = item_uris
             .iter()
             .map(|u| json!({ "uri": u.as_ref() }))
             .collect();
 
         self.builder(RemovePlaylistItemsEndpoint {
-            id: id.into(),
+            id: id.as_id().to_owned(),
             tracks,
             snapshot_id: None,
         })
     }
 
+    #[cfg(feature = "playlists")]
     pub fn user_playlists(
-        &mut self,
-        user_id: impl Into<String>,
-    ) -> Builder<'_, F, V, UserPlaylistsEndpoint> {
+        &self,
+        user_id: impl AsId<UserId>,
+    ) -> Builder<'_, F, UserPlaylistsEndpoint> {
         self.builder(UserPlaylistsEndpoint {
-            id: user_id.into(),
+            id: user_id.as_id().to_owned(),
+            limit: self.default_limit.map(Limit::new),
             ..Default::default()
         })
     }
 
+    #[cfg(feature = "playlists")]
     pub fn create_playlist(
-        &mut self,
-        user_id: impl Into<String>,
+        &self,
+        user_id: impl AsId<UserId>,
         name: impl Into<String>,
-    ) -> Builder<'_, F, V, CreatePlaylistEndpoint> {
+    ) -> Builder<'_, F, CreatePlaylistEndpoint<'_>> {
         self.builder(CreatePlaylistEndpoint {
-            user_id: user_id.into(),
+            user_id: user_id.as_id().to_owned(),
             name: name.into(),
             ..Default::default()
         })
     }
 
-    pub fn featured_playlists(&mut self) -> Builder<'_, F, V, FeaturedPlaylistsEndpoint> {
-        self.builder(FeaturedPlaylistsEndpoint::default())
+    #[cfg(all(feature = "browse", feature = "playlists"))]
+    pub fn featured_playlists(&self) -> Builder<'_, F, FeaturedPlaylistsEndpoint> {
+        let locale = self.default_locale();
+
+        self.builder(FeaturedPlaylistsEndpoint {
+            country: locale.map(|l| l.country().to_string()),
+            locale: locale.map(String::from),
+            timestamp: self
+                .utc_offset
+                .map(|offset| local_timestamp(chrono::Utc::now().with_timezone(&offset))),
+            limit: self.default_limit.map(Limit::new),
+            ..Default::default()
+        })
     }
 
+    #[cfg(all(feature = "browse", feature = "playlists"))]
     pub fn category_playlists(
-        &mut self,
+        &self,
         category_id: impl Into<String>,
-    ) -> Builder<'_, F, V, CategoryPlaylistsEndpoint> {
+    ) -> Builder<'_, F, CategoryPlaylistsEndpoint> {
         self.builder(CategoryPlaylistsEndpoint {
             id: category_id.into(),
+            country: self.default_locale().map(|l| l.country().to_string()),
+            limit: self.default_limit.map(Limit::new),
             ..Default::default()
         })
     }
 
-    pub async fn get_playlist_image(&mut self, id: impl Into<String>) -> Result<Vec<Image>> {
-        self.get::<(), _>(format!("/playlists/{}/images", id.into()), None)
+    #[cfg(feature = "playlists")]
+    pub async fn get_playlist_image(&self, id: impl AsId<PlaylistId>) -> Result<Vec<Image>> {
+        self.get::<(), _>(format!("/playlists/{}/images", id.as_id()), None)
             .await
     }
 
-    pub async fn add_playlist_image(&mut self, id: impl Into<String>, image: &[u8]) -> Result<Nil> {
-        let encoded_image = general_purpose::STANDARD.encode(image).into_bytes();
-        let body = <Body>::File(encoded_image);
+    /// Download a playlist's cover image, returning its bytes, or `None` if it has none.
+    ///
+    /// If Spotify has the cover in several sizes, the largest is downloaded.
+    #[cfg(feature = "playlists")]
+    pub async fn download_playlist_image(
+        &self,
+        id: impl AsId<PlaylistId>,
+    ) -> Result<Option<Vec<u8>>> {
+        let images = self.get_playlist_image(id).await?;
+
+        match Image::largest(&images) {
+            Some(image) => image.fetch(&self.http).await.map(Some),
+            None => Ok(None),
+        }
+    }
 
-        self.put(format!("/playlists/{}/images", id.into()), body)
+    /// Upload a JPEG image as a playlist's cover image.
+    ///
+    /// Spotify only accepts JPEGs of up to 256 KB once Base64 encoded, so other images fail
+    /// with [`Error::UnsupportedImageFormat`] and larger ones with [`Error::ImageTooLarge`],
+    /// without being sent. With the `image` feature, PNGs are converted into JPEGs instead,
+    /// and images that are too large are downscaled until they fit.
+    #[cfg(feature = "playlists")]
+    pub async fn add_playlist_image(&self, id: impl AsId<PlaylistId>, image: &[u8]) -> Result<Nil> {
+        let body = <Body>::File(cover::encode(image)?);
+
+        self.put(format!("/playlists/{}/images", id.as_id()), body)
             .await
     }
 
+    #[cfg(feature = "search")]
     pub fn search(
-        &mut self,
+        &self,
         query: impl Into<String>,
         item_types: &[Item],
-    ) -> Builder<'_, F, V, SearchEndpoint> {
+    ) -> Builder<'_, F, SearchEndpoint> {
         let r#type = query_list(item_types);
 
         self.builder(SearchEndpoint {
             query: query.into(),
             r#type,
+            market: self.default_market(),
+            limit: self.default_limit.map(Limit::new),
             ..Default::default()
         })
     }
 
-    pub fn show(&mut self, id: impl Into<String>) -> Builder<'_, F, V, ShowEndpoint> {
+    /// Find the track with the given [ISRC](https://en.wikipedia.org/wiki/International_Standard_Recording_Code),
+    /// e.g. to match tracks from another catalog.
+    ///
+    /// If several tracks have it, such as the same recording released on a single and on an
+    /// album, the one Spotify ranks as the best match is returned. Returns `None` if no
+    /// track has it.
+    #[cfg(feature = "search")]
+    pub async fn track_by_isrc(&self, isrc: impl Into<String>) -> Result<Option<Track>> {
+        let results = self
+            .search(SearchQuery::new("").isrc(isrc), &[Item::Track])
+            .limit(1)
+            .get()
+            .await?;
+
+        Ok(results
+            .tracks
+            .and_then(|page| page.items.into_iter().next()))
+    }
+
+    /// Find the album with the given [UPC](https://en.wikipedia.org/wiki/Universal_Product_Code),
+    /// e.g. to match albums from another catalog.
+    ///
+    /// Returns `None` if no album has it.
+    #[cfg(feature = "search")]
+    pub async fn album_by_upc(&self, upc: impl Into<String>) -> Result<Option<SimplifiedAlbum>> {
+        let results = self
+            .search(SearchQuery::new("").upc(upc), &[Item::Album])
+            .limit(1)
+            .get()
+            .await?;
+
+        Ok(results
+            .albums
+            .and_then(|page| page.items.into_iter().next()))
+    }
+
+    #[cfg(feature = "shows")]
+    pub fn show(&self, id: impl AsId<ShowId>) -> Builder<'_, F, ShowEndpoint> {
         self.builder(ShowEndpoint {
-            id: id.into(),
-            market: None,
+            id: id.as_id().to_owned(),
+            market: self.default_market(),
         })
     }
 
-    pub fn shows<T: AsRef<str>>(&mut self, ids: &[T]) -> Builder<'_, F, V, ShowsEndpoint> {
+    #[cfg(feature = "shows")]
+    pub fn shows<T: AsId<ShowId>>(&self, ids: &[T]) -> Builder<'_, F, ShowsEndpoint> {
         self.builder(ShowsEndpoint {
-            ids: query_list(ids),
-            market: None,
+            ids: query_list(&as_ids(ids)),
+            market: self.default_market(),
         })
     }
 
+    #[cfg(feature = "shows")]
     pub fn show_episodes(
-        &mut self,
-        show_id: impl Into<String>,
-    ) -> Builder<'_, F, V, ShowEpisodesEndpoint> {
+        &self,
+        show_id: impl AsId<ShowId>,
+    ) -> Builder<'_, F, ShowEpisodesEndpoint> {
         self.builder(ShowEpisodesEndpoint {
-            show_id: show_id.into(),
+            show_id: show_id.as_id().to_owned(),
+            market: self.default_market(),
+            limit: self.default_limit.map(Limit::new),
             ..Default::default()
         })
     }
 
-    pub fn track(&mut self, id: impl Into<String>) -> Builder<'_, F, V, TrackEndpoint> {
+    pub fn track(&self, id: impl AsId<TrackId>) -> Builder<'_, F, TrackEndpoint> {
         self.builder(TrackEndpoint {
-            id: id.into(),
-            market: None,
+            id: id.as_id().to_owned(),
+            market: self.default_market(),
         })
     }
 
-    pub fn tracks<T: AsRef<str>>(&mut self, ids: &[T]) -> Builder<'_, F, V, TracksEndpoint> {
+    pub fn tracks<T: AsId<TrackId>>(&self, ids: &[T]) -> Builder<'_, F, TracksEndpoint> {
         self.builder(TracksEndpoint {
-            ids: query_list(ids),
-            market: None,
+            ids: query_list(&as_ids(ids)),
+            market: self.default_market(),
+        })
+    }
+
+    pub async fn get_track_audio_features(&self, id: impl AsId<TrackId>) -> Result<AudioFeatures> {
+        self.get::<(), _>(format!("/audio-features/{}", id.as_id()), None)
+            .await
+    }
+
+    /// Get the audio features of multiple tracks, in the same order as `ids`.
+    ///
+    /// Any number of IDs can be given, as they're requested 100 at a time. Tracks without
+    /// audio features, such as ones that don't exist, are `None`, so that the features
+    /// still line up with the IDs.
+    pub async fn get_tracks_audio_features<T: AsId<TrackId>>(
+        &self,
+        ids: &[T],
+    ) -> Result<Vec<Option<AudioFeatures>>> {
+        chunked(&as_ids(ids), 100, |ids| {
+            self.get("/audio-features".to_owned(), [("ids", query_list(ids))])
         })
+        .await
+        .map(|a: Vec<AudioFeaturesResult>| a.into_iter().flat_map(|a| a.audio_features).collect())
     }
 
-    pub async fn get_track_audio_features(
-        &mut self,
-        id: impl Into<String>,
-    ) -> Result<AudioFeatures> {
-        self.get::<(), _>(format!("/audio-features/{}", id.into()), None)
+    pub async fn get_track_audio_analysis(&self, id: impl AsId<TrackId>) -> Result<AudioAnalysis> {
+        self.get::<(), _>(format!("/audio-analysis/{}", id.as_id()), None)
             .await
     }
 
-    pub async fn get_tracks_audio_features<T: AsRef<str>>(
-        &mut self,
+    /// Get the audio analyses of multiple tracks, running up to `concurrency` requests
+    /// at once, as the API can only get one at a time.
+    ///
+    /// The results are in the same order as `ids`, and each has to be checked on its own
+    /// (see [`fetch_many`](Self::fetch_many)).
+    pub async fn get_tracks_audio_analysis<T: AsId<TrackId>>(
+        &self,
         ids: &[T],
-    ) -> Result<Vec<AudioFeatures>> {
-        self.get("/audio-features".to_owned(), [("ids", query_list(ids))])
-            .await
-            .map(|a: AudioFeaturesResult| a.audio_features)
-    }
-
-    pub async fn get_track_audio_analysis(
-        &mut self,
-        id: impl Into<String>,
-    ) -> Result<AudioAnalysis> {
-        self.get::<(), _>(format!("/audio-analysis/{}", id.into()), None)
-            .await
+        concurrency: usize,
+    ) -> Result<Vec<Result<AudioAnalysis>>> {
+        self.fetch_many(&as_ids(ids), concurrency).await
     }
 
     pub fn recommendations<S: SeedType, T: AsRef<str>>(
-        &mut self,
+        &self,
         seed: Seed<T, S>,
-    ) -> Builder<'_, F, V, RecommendationsEndpoint<S>> {
+    ) -> Builder<'_, F, RecommendationsEndpoint<S>> {
         let (seed_artists, seed_genres, seed_tracks) = match seed {
             Seed::Artists(ids, _) => (Some(query_list(ids)), None, None),
             Seed::Genres(genres, _) => (None, Some(query_list(genres)), None),
@@ -670,242 +1968,718 @@ impl<F: AuthFlow, V: Verifier> Client<Token, F, V> {
             seed_genres,
             seed_tracks,
             limit: None,
-            market: None,
+            market: self.default_market(),
             features: None,
             marker: PhantomData,
         })
     }
 
-    pub async fn get_user(&mut self, id: impl Into<String>) -> Result<User> {
-        self.get::<(), _>(format!("/users/{}", id.into()), None)
+    /// Get recommendations seeded by any combination of artists, genres and tracks.
+    ///
+    /// See [`Seeds`] for an example.
+    pub fn recommendations_with_seeds(
+        &self,
+        seeds: Seeds,
+    ) -> Builder<'_, F, RecommendationsEndpoint<Seeds>> {
+        self.builder(RecommendationsEndpoint::with_seeds(
+            seeds,
+            self.default_market(),
+        ))
+    }
+
+    pub async fn get_user(&self, id: impl AsId<UserId>) -> Result<User> {
+        self.get::<(), _>(format!("/users/{}", id.as_id()), None)
             .await
     }
 
-    pub async fn check_if_users_follow_playlist<T: AsRef<str>>(
-        &mut self,
-        playlist_id: impl Into<String>,
+    #[cfg(feature = "playlists")]
+    pub async fn check_if_users_follow_playlist<T: AsId<UserId>>(
+        &self,
+        playlist_id: impl AsId<PlaylistId>,
         user_ids: &[T],
     ) -> Result<Vec<bool>> {
         self.get(
-            format!("/playlists/{}/followers/contains", playlist_id.into()),
-            [("ids", query_list(user_ids))],
+            format!("/playlists/{}/followers/contains", playlist_id.as_id()),
+            [("ids", query_list(&as_ids(user_ids)))],
         )
         .await
     }
 }
 
-impl<F: AuthFlow + Authorised, V: Verifier> Client<Token, F, V> {
-    pub fn saved_albums(&mut self) -> Builder<'_, F, V, SavedAlbumsEndpoint> {
-        self.builder(SavedAlbumsEndpoint::default())
+impl<F: AuthFlow + Authorised> Client<Token, F> {
+    pub fn saved_albums(&self) -> Builder<'_, F, SavedAlbumsEndpoint> {
+        self.builder(SavedAlbumsEndpoint {
+            market: self.default_market(),
+            limit: self.default_limit.map(Limit::new),
+            ..Default::default()
+        })
     }
 
-    pub async fn save_albums<T: AsRef<str>>(&mut self, ids: &[T]) -> Result<Nil> {
-        self.put("/me/albums".to_owned(), body_list("ids", ids))
-            .await
+    pub async fn save_albums<T: AsId<AlbumId>>(&self, ids: &[T]) -> Result<Nil> {
+        chunked(&as_ids(ids), 20, |ids| {
+            self.put("/me/albums".to_owned(), body_list("ids", ids))
+        })
+        .await
+        .map(|_: Vec<Nil>| Nil)
     }
 
-    pub async fn remove_saved_albums<T: AsRef<str>>(&mut self, ids: &[T]) -> Result<Nil> {
-        self.delete("/me/albums".to_owned(), body_list("ids", ids))
-            .await
+    pub async fn remove_saved_albums<T: AsId<AlbumId>>(&self, ids: &[T]) -> Result<Nil> {
+        chunked(&as_ids(ids), 20, |ids| {
+            self.delete("/me/albums".to_owned(), body_list("ids", ids))
+        })
+        .await
+        .map(|_: Vec<Nil>| Nil)
     }
 
-    pub async fn check_saved_albums<T: AsRef<str>>(&mut self, ids: &[T]) -> Result<Vec<bool>> {
-        self.get("/me/albums/contains".to_owned(), [("ids", query_list(ids))])
-            .await
+    pub async fn check_saved_albums<T: AsId<AlbumId>>(&self, ids: &[T]) -> Result<Vec<bool>> {
+        chunked(&as_ids(ids), 20, |ids| {
+            self.get("/me/albums/contains".to_owned(), [("ids", query_list(ids))])
+        })
+        .await
+        .map(|saved: Vec<Vec<bool>>| saved.concat())
     }
 
-    pub fn saved_audiobooks(&mut self) -> Builder<'_, F, V, SavedAudiobooksEndpoint> {
-        self.builder(SavedAudiobooksEndpoint::default())
+    #[cfg(feature = "audiobooks")]
+    pub fn saved_audiobooks(&self) -> Builder<'_, F, SavedAudiobooksEndpoint> {
+        self.builder(SavedAudiobooksEndpoint {
+            limit: self.default_limit.map(Limit::new),
+            ..Default::default()
+        })
     }
 
-    pub async fn save_audiobooks<T: AsRef<str>>(&mut self, ids: &[T]) -> Result<Nil> {
-        self.put::<(), _>(format!("/me/audiobooks?ids={}", query_list(ids)), None)
-            .await
+    #[cfg(feature = "audiobooks")]
+    pub async fn save_audiobooks<T: AsId<AudiobookId>>(&self, ids: &[T]) -> Result<Nil> {
+        chunked(&as_ids(ids), 50, |ids| {
+            self.put::<(), _>(format!("/me/audiobooks?ids={}", query_list(ids)), None)
+        })
+        .await
+        .map(|_: Vec<Nil>| Nil)
     }
 
-    pub async fn remove_saved_audiobooks<T: AsRef<str>>(&mut self, ids: &[T]) -> Result<Nil> {
-        self.delete::<(), _>(format!("/me/audiobooks?ids={}", query_list(ids)), None)
-            .await
+    #[cfg(feature = "audiobooks")]
+    pub async fn remove_saved_audiobooks<T: AsId<AudiobookId>>(&self, ids: &[T]) -> Result<Nil> {
+        chunked(&as_ids(ids), 50, |ids| {
+            self.delete::<(), _>(format!("/me/audiobooks?ids={}", query_list(ids)), None)
+        })
+        .await
+        .map(|_: Vec<Nil>| Nil)
     }
 
-    pub async fn check_saved_audiobooks<T: AsRef<str>>(&mut self, ids: &[T]) -> Result<Vec<bool>> {
-        self.get(
-            "/me/audiobooks/contains".to_owned(),
-            [("ids", query_list(ids))],
-        )
+    #[cfg(feature = "audiobooks")]
+    pub async fn check_saved_audiobooks<T: AsId<AudiobookId>>(
+        &self,
+        ids: &[T],
+    ) -> Result<Vec<bool>> {
+        chunked(&as_ids(ids), 50, |ids| {
+            self.get(
+                "/me/audiobooks/contains".to_owned(),
+                [("ids", query_list(ids))],
+            )
+        })
         .await
+        .map(|saved: Vec<Vec<bool>>| saved.concat())
     }
 
-    pub fn saved_episodes(&mut self) -> Builder<'_, F, V, SavedEpisodesEndpoint> {
-        self.builder(SavedEpisodesEndpoint::default())
+    #[cfg(feature = "shows")]
+    pub fn saved_episodes(&self) -> Builder<'_, F, SavedEpisodesEndpoint> {
+        self.builder(SavedEpisodesEndpoint {
+            market: self.default_market(),
+            limit: self.default_limit.map(Limit::new),
+            ..Default::default()
+        })
     }
 
-    pub async fn save_episodes<T: AsRef<str>>(&mut self, ids: &[T]) -> Result<Nil> {
-        self.put("/me/episodes".to_owned(), body_list("ids", ids))
-            .await
+    #[cfg(feature = "shows")]
+    pub async fn save_episodes<T: AsId<EpisodeId>>(&self, ids: &[T]) -> Result<Nil> {
+        chunked(&as_ids(ids), 50, |ids| {
+            self.put("/me/episodes".to_owned(), body_list("ids", ids))
+        })
+        .await
+        .map(|_: Vec<Nil>| Nil)
     }
 
-    pub async fn remove_saved_episodes<T: AsRef<str>>(&mut self, ids: &[T]) -> Result<Nil> {
-        self.delete("/me/episodes".to_owned(), body_list("ids", ids))
-            .await
+    #[cfg(feature = "shows")]
+    pub async fn remove_saved_episodes<T: AsId<EpisodeId>>(&self, ids: &[T]) -> Result<Nil> {
+        chunked(&as_ids(ids), 50, |ids| {
+            self.delete("/me/episodes".to_owned(), body_list("ids", ids))
+        })
+        .await
+        .map(|_: Vec<Nil>| Nil)
     }
 
-    pub async fn check_saved_episodes<T: AsRef<str>>(&mut self, ids: &[T]) -> Result<Vec<bool>> {
-        self.get::<(), _>(
-            format!("/me/episodes/contains?ids={}", query_list(ids)),
-            None,
-        )
+    #[cfg(feature = "shows")]
+    pub async fn check_saved_episodes<T: AsId<EpisodeId>>(&self, ids: &[T]) -> Result<Vec<bool>> {
+        chunked(&as_ids(ids), 50, |ids| {
+            self.get::<(), _>(
+                format!("/me/episodes/contains?ids={}", query_list(ids)),
+                None,
+            )
+        })
         .await
+        .map(|saved: Vec<Vec<bool>>| saved.concat())
     }
 
-    pub fn current_user_playlists(&mut self) -> Builder<'_, F, V, CurrentUserPlaylistsEndpoint> {
-        self.builder(CurrentUserPlaylistsEndpoint::default())
+    #[cfg(feature = "playlists")]
+    pub fn current_user_playlists(&self) -> Builder<'_, F, CurrentUserPlaylistsEndpoint> {
+        self.builder(CurrentUserPlaylistsEndpoint {
+            limit: self.default_limit.map(Limit::new),
+            ..Default::default()
+        })
     }
 
-    pub fn saved_shows(&mut self) -> Builder<'_, F, V, SavedShowsEndpoint> {
-        self.builder(SavedShowsEndpoint::default())
+    #[cfg(feature = "shows")]
+    pub fn saved_shows(&self) -> Builder<'_, F, SavedShowsEndpoint> {
+        self.builder(SavedShowsEndpoint {
+            limit: self.default_limit.map(Limit::new),
+            ..Default::default()
+        })
     }
 
-    pub async fn save_shows<T: AsRef<str>>(&mut self, ids: &[T]) -> Result<Nil> {
-        self.put("/me/shows".to_owned(), body_list("ids", ids))
-            .await
+    #[cfg(feature = "shows")]
+    pub async fn save_shows<T: AsId<ShowId>>(&self, ids: &[T]) -> Result<Nil> {
+        chunked(&as_ids(ids), 50, |ids| {
+            self.put("/me/shows".to_owned(), body_list("ids", ids))
+        })
+        .await
+        .map(|_: Vec<Nil>| Nil)
     }
 
-    pub async fn remove_saved_shows<T: AsRef<str>>(&mut self, ids: &[T]) -> Result<Nil> {
-        self.delete("/me/shows".to_owned(), body_list("ids", ids))
-            .await
+    #[cfg(feature = "shows")]
+    pub async fn remove_saved_shows<T: AsId<ShowId>>(&self, ids: &[T]) -> Result<Nil> {
+        chunked(&as_ids(ids), 50, |ids| {
+            self.delete("/me/shows".to_owned(), body_list("ids", ids))
+        })
+        .await
+        .map(|_: Vec<Nil>| Nil)
     }
 
-    pub async fn check_saved_shows<T: AsRef<str>>(&mut self, ids: &[T]) -> Result<Vec<bool>> {
-        self.get("/me/shows/contains".to_owned(), [("ids", query_list(ids))])
-            .await
+    #[cfg(feature = "shows")]
+    pub async fn check_saved_shows<T: AsId<ShowId>>(&self, ids: &[T]) -> Result<Vec<bool>> {
+        chunked(&as_ids(ids), 50, |ids| {
+            self.get("/me/shows/contains".to_owned(), [("ids", query_list(ids))])
+        })
+        .await
+        .map(|saved: Vec<Vec<bool>>| saved.concat())
     }
 
-    pub fn saved_tracks(&mut self) -> Builder<'_, F, V, SavedTracksEndpoint> {
-        self.builder(SavedTracksEndpoint::default())
+    pub fn saved_tracks(&self) -> Builder<'_, F, SavedTracksEndpoint> {
+        self.builder(SavedTracksEndpoint {
+            market: self.default_market(),
+            limit: self.default_limit.map(Limit::new),
+            ..Default::default()
+        })
     }
 
-    pub async fn save_tracks<T: AsRef<str>>(&mut self, ids: &[T]) -> Result<Nil> {
-        self.put("/me/tracks".to_owned(), body_list("ids", ids))
-            .await
+    pub async fn save_tracks<T: AsId<TrackId>>(&self, ids: &[T]) -> Result<Nil> {
+        chunked(&as_ids(ids), 50, |ids| {
+            self.put("/me/tracks".to_owned(), body_list("ids", ids))
+        })
+        .await
+        .map(|_: Vec<Nil>| Nil)
     }
 
-    pub async fn remove_saved_tracks<T: AsRef<str>>(&mut self, ids: &[T]) -> Result<Nil> {
-        self.delete("/me/tracks".to_owned(), body_list("ids", ids))
-            .await
+    pub async fn remove_saved_tracks<T: AsId<TrackId>>(&self, ids: &[T]) -> Result<Nil> {
+        chunked(&as_ids(ids), 50, |ids| {
+            self.delete("/me/tracks".to_owned(), body_list("ids", ids))
+        })
+        .await
+        .map(|_: Vec<Nil>| Nil)
     }
 
-    pub async fn check_saved_tracks<T: AsRef<str>>(&mut self, ids: &[T]) -> Result<Vec<bool>> {
-        self.get("/me/tracks/contains".to_owned(), [("ids", query_list(ids))])
-            .await
+    pub async fn check_saved_tracks<T: AsId<TrackId>>(&self, ids: &[T]) -> Result<Vec<bool>> {
+        chunked(&as_ids(ids), 50, |ids| {
+            self.get("/me/tracks/contains".to_owned(), [("ids", query_list(ids))])
+        })
+        .await
+        .map(|saved: Vec<Vec<bool>>| saved.concat())
+    }
+
+    /// Check if an item is saved in the current user's library, given its Spotify URI,
+    /// e.g. `spotify:track:{id}`.
+    ///
+    /// Tracks, albums, episodes, shows and audiobooks can be checked.
+    pub async fn is_saved(&self, uri: impl AsRef<str>) -> Result<bool> {
+        let saved = self.are_saved(&[uri]).await?;
+        Ok(saved.first().copied().unwrap_or_default())
+    }
+
+    /// Check if items of any type are saved in the current user's library, given their
+    /// Spotify URIs. The results are in the same order as the URIs.
+    ///
+    /// The items are checked with one request per type (and per 20 to 50 items of that type,
+    /// depending on how many the type's endpoint accepts).
+    pub async fn are_saved<T: AsRef<str>>(&self, uris: &[T]) -> Result<Vec<bool>> {
+        // The indices and IDs of the items of each type, in the order they appear.
+        let mut groups: Vec<(LibraryItem, Vec<(usize, &str)>)> = Vec::new();
+
+        for (i, uri) in uris.iter().enumerate() {
+            let (item, id) = LibraryItem::from_uri(uri.as_ref())?;
+
+            match groups.iter_mut().find(|(g, _)| *g == item) {
+                Some((_, items)) => items.push((i, id)),
+                None => groups.push((item, vec![(i, id)])),
+            }
+        }
+
+        let mut saved = vec![false; uris.len()];
+
+        for (item, items) in groups {
+            let ids: Vec<_> = items.iter().map(|(_, id)| *id).collect();
+
+            let results = match item {
+                LibraryItem::Track => self.check_saved_tracks(&ids).await?,
+                LibraryItem::Album => self.check_saved_albums(&ids).await?,
+                #[cfg(feature = "shows")]
+                LibraryItem::Episode => self.check_saved_episodes(&ids).await?,
+                #[cfg(feature = "shows")]
+                LibraryItem::Show => self.check_saved_shows(&ids).await?,
+                #[cfg(feature = "audiobooks")]
+                LibraryItem::Audiobook => self.check_saved_audiobooks(&ids).await?,
+            };
+
+            for ((i, _), is_saved) in items.iter().zip(results) {
+                saved[*i] = is_saved;
+            }
+        }
+
+        Ok(saved)
     }
 
-    pub async fn get_current_user_profile(&mut self) -> Result<User> {
+    #[cfg(feature = "user")]
+    pub async fn get_current_user_profile(&self) -> Result<User> {
         self.get::<(), _>("/me".to_owned(), None).await
     }
 
+    /// Get the current user's top artists or tracks, as [`UserItem`](crate::model::user::UserItem)s.
+    ///
+    /// When the type is known up front, [`current_user_top_artists`](Self::current_user_top_artists)
+    /// and [`current_user_top_tracks`](Self::current_user_top_tracks) return the items as
+    /// artists or tracks instead.
+    #[cfg(feature = "user")]
     pub fn current_user_top_items(
-        &mut self,
+        &self,
         r#type: UserItemType,
-    ) -> Builder<'_, F, V, UserTopItemsEndpoint> {
-        self.builder(UserTopItemsEndpoint {
-            r#type,
-            ..Default::default()
-        })
+    ) -> Builder<'_, F, UserTopItemsEndpoint> {
+        self.builder(UserTopItemsEndpoint::new(r#type, self.default_limit))
+    }
+
+    /// Get the current user's top artists.
+    #[cfg(feature = "user")]
+    pub fn current_user_top_artists(&self) -> Builder<'_, F, UserTopItemsEndpoint<Artist>> {
+        self.builder(UserTopItemsEndpoint::new(
+            UserItemType::Artists,
+            self.default_limit,
+        ))
+    }
+
+    /// Get the current user's top tracks.
+    #[cfg(feature = "user")]
+    pub fn current_user_top_tracks(
+        &self,
+    ) -> Builder<'_, F, UserTopItemsEndpoint<crate::model::track::Track>> {
+        self.builder(UserTopItemsEndpoint::new(
+            UserItemType::Tracks,
+            self.default_limit,
+        ))
     }
 
+    #[cfg(all(feature = "playlists", feature = "user"))]
     pub fn follow_playlist(
-        &mut self,
-        id: impl Into<String>,
-    ) -> Builder<'_, F, V, FollowPlaylistBuilder> {
-        self.builder(FollowPlaylistBuilder {
-            id: id.into(),
+        &self,
+        id: impl AsId<PlaylistId>,
+    ) -> Builder<'_, F, FollowPlaylistEndpoint> {
+        self.builder(FollowPlaylistEndpoint {
+            id: id.as_id().to_owned(),
             public: None,
         })
     }
 
-    pub async fn unfollow_playlist(&mut self, id: impl Into<String>) -> Result<Nil> {
-        self.delete::<(), _>(format!("/playlists/{}/followers", id.into()), None)
+    #[cfg(feature = "playlists")]
+    pub async fn unfollow_playlist(&self, id: impl AsId<PlaylistId>) -> Result<Nil> {
+        self.delete::<(), _>(format!("/playlists/{}/followers", id.as_id()), None)
+            .await
+    }
+
+    /// Unfollow multiple playlists, one request at a time, as the API can only unfollow
+    /// one per request.
+    ///
+    /// If a request fails, the playlists after it aren't unfollowed.
+    #[cfg(feature = "playlists")]
+    pub async fn unfollow_playlists<T: AsId<PlaylistId>>(&self, ids: &[T]) -> Result<Nil> {
+        for id in ids {
+            self.unfollow_playlist(id.as_id()).await?;
+        }
+
+        Ok(Nil)
+    }
+
+    /// Check if the current user follows a playlist.
+    ///
+    /// The user's ID is requested first, as the API needs it to check.
+    #[cfg(all(feature = "playlists", feature = "user"))]
+    pub async fn check_if_current_user_follows_playlist(
+        &self,
+        id: impl AsId<PlaylistId>,
+    ) -> Result<bool> {
+        let user = self.get_current_user_profile().await?;
+
+        self.check_if_users_follow_playlist(id, &[user.id])
             .await
+            .map(|follows| follows.first().copied().unwrap_or(false))
     }
 
-    pub fn followed_artists(&mut self) -> Builder<'_, F, V, FollowedArtistsBuilder> {
+    #[cfg(feature = "user")]
+    pub fn followed_artists(&self) -> Builder<'_, F, FollowedArtistsBuilder> {
         // Currently only the "artist" type is supported, so it's hardcoded.
         self.builder(FollowedArtistsBuilder {
             r#type: "artist".to_owned(),
+            limit: self.default_limit.map(Limit::new),
             ..Default::default()
         })
     }
 
-    pub fn follow_artists<T: AsRef<str>>(
-        &mut self,
+    #[cfg(feature = "user")]
+    pub fn follow_artists<T: AsId<ArtistId>>(
+        &self,
         ids: &[T],
-    ) -> Builder<'_, F, V, FollowUserOrArtistEndpoint> {
+    ) -> Builder<'_, F, FollowUserOrArtistEndpoint> {
         self.builder(FollowUserOrArtistEndpoint {
             r#type: "artist".to_owned(),
-            ids: ids.iter().map(|i| i.as_ref().to_owned()).collect(),
+            ids: ids.iter().map(|i| i.as_id().to_owned()).collect(),
         })
     }
 
-    pub fn follow_users<T: AsRef<str>>(
-        &mut self,
+    #[cfg(feature = "user")]
+    pub fn follow_users<T: AsId<UserId>>(
+        &self,
         ids: &[T],
-    ) -> Builder<'_, F, V, FollowUserOrArtistEndpoint> {
+    ) -> Builder<'_, F, FollowUserOrArtistEndpoint> {
         self.builder(FollowUserOrArtistEndpoint {
             r#type: "user".to_owned(),
-            ids: ids.iter().map(|i| i.as_ref().to_owned()).collect(),
+            ids: ids.iter().map(|i| i.as_id().to_owned()).collect(),
         })
     }
 
-    pub async fn get_playback_state(&mut self, market: Option<&str>) -> Result<PlaybackState> {
-        let market = market.map(|m| [("market", m)]);
-        self.get::<[(&str, &str); 1], _>("/me/player".to_owned(), market)
-            .await
+    /// Unfollow the artists with the given IDs, as the current user.
+    #[cfg(feature = "user")]
+    pub async fn unfollow_artists<T: AsId<ArtistId>>(&self, ids: &[T]) -> Result<Nil> {
+        self.follow_artists(ids).unfollow().await
+    }
+
+    /// Unfollow the users with the given IDs, as the current user.
+    #[cfg(feature = "user")]
+    pub async fn unfollow_users<T: AsId<UserId>>(&self, ids: &[T]) -> Result<Nil> {
+        self.follow_users(ids).unfollow().await
+    }
+
+    /// Check if the current user follows the artists with the given IDs.
+    /// The results are in the same order as the IDs.
+    #[cfg(feature = "user")]
+    pub async fn check_following_artists<T: AsId<ArtistId>>(&self, ids: &[T]) -> Result<Vec<bool>> {
+        self.follow_artists(ids).check().await
+    }
+
+    /// Check if the current user follows the users with the given IDs.
+    /// The results are in the same order as the IDs.
+    #[cfg(feature = "user")]
+    pub async fn check_following_users<T: AsId<UserId>>(&self, ids: &[T]) -> Result<Vec<bool>> {
+        self.follow_users(ids).check().await
+    }
+
+    /// Get the user's playback state.
+    ///
+    /// This fails when nothing is playing, as Spotify responds without a body, so
+    /// [`current_playback`](Self::current_playback) is usually more convenient.
+    #[cfg(feature = "player")]
+    pub async fn get_playback_state(&self, market: Option<Market>) -> Result<PlaybackState> {
+        self.playback_state("/me/player", market).await
     }
 
+    /// Transfer playback to a device, given by its ID or as a [`Device`] returned by
+    /// [`get_available_devices`](Self::get_available_devices).
+    #[cfg(feature = "player")]
     pub fn transfer_playback(
-        &mut self,
-        device_id: impl Into<String>,
-    ) -> Builder<'_, F, V, TransferPlaybackEndpoint> {
+        &self,
+        device: impl AsDeviceId,
+    ) -> Builder<'_, F, TransferPlaybackEndpoint> {
         self.builder(TransferPlaybackEndpoint {
-            device_ids: vec![device_id.into()],
-            play: None,
+            device_ids: device
+                .device_id()
+                .map(ToOwned::to_owned)
+                .into_iter()
+                .collect(),
+            ..Default::default()
         })
     }
 
-    pub async fn get_available_devices(&mut self) -> Result<Vec<Device>> {
+    /// Transfer playback to a device and start playing on it, like
+    /// [`transfer_playback`](Self::transfer_playback) with [`play(true)`](Builder::play).
+    #[cfg(feature = "player")]
+    pub async fn transfer_playback_and_play(&self, device: impl AsDeviceId) -> Result<Nil> {
+        self.transfer_playback(device).play(true).send().await
+    }
+
+    #[cfg(feature = "player")]
+    pub async fn get_available_devices(&self) -> Result<Vec<Device>> {
         self.get::<(), _>("/me/player/devices".to_owned(), None)
             .await
             .map(|d: Devices| d.devices)
     }
 
+    /// Get the device that player commands are sent to when they don't specify one.
+    #[cfg(feature = "player")]
+    pub fn preferred_device(&self) -> Option<PreferredDevice> {
+        self.preferred_device
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Set the device that player commands are sent to when they don't specify one,
+    /// or `None` to send them to the user's active device.
+    #[cfg(feature = "player")]
+    pub fn set_preferred_device(&self, device: Option<PreferredDevice>) {
+        *self
+            .preferred_device
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = device;
+    }
+
+    /// Find the preferred device among the user's available devices, by its ID or,
+    /// failing that, by its name, in which case the preferred device's ID is updated.
+    ///
+    /// Returns `None` if there's no preferred device or it isn't available.
+    #[cfg(feature = "player")]
+    pub async fn find_preferred_device(&self) -> Result<Option<Device>> {
+        if self.preferred_device().is_none() {
+            return Ok(None);
+        }
+
+        let devices = self.get_available_devices().await?;
+        Ok(self.match_preferred_device(devices))
+    }
+
+    /// Transfer playback to the preferred device if no device is currently active,
+    /// so that player commands don't fail with "no active device".
+    ///
+    /// Returns whether playback was transferred.
+    #[cfg(feature = "player")]
+    pub async fn activate_preferred_device(&self) -> Result<bool> {
+        if self.preferred_device().is_none() {
+            return Ok(false);
+        }
+
+        let devices = self.get_available_devices().await?;
+
+        if devices.iter().any(|d| d.is_active) {
+            return Ok(false);
+        }
+
+        let Some(id) = self.match_preferred_device(devices).and_then(|d| d.id) else {
+            return Ok(false);
+        };
+
+        self.transfer_playback(id).send().await?;
+        Ok(true)
+    }
+
+    #[cfg(feature = "player")]
+    fn match_preferred_device(&self, devices: Vec<Device>) -> Option<Device> {
+        let mut preferred = self
+            .preferred_device
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        let preferred = preferred.as_mut()?;
+
+        let index = devices
+            .iter()
+            .position(|d| d.id.as_ref() == Some(&preferred.id))
+            .or_else(|| {
+                let name = preferred.name.as_ref()?;
+                devices.iter().position(|d| &d.name == name)
+            })?;
+
+        let device = devices.into_iter().nth(index)?;
+
+        if let Some(id) = &device.id {
+            preferred.id.clone_from(id);
+        }
+
+        Some(device)
+    }
+
+    #[cfg(feature = "player")]
+    fn preferred_device_id(&self) -> Option<String> {
+        self.preferred_device().map(|d| d.id)
+    }
+
+    #[cfg(feature = "player")]
+    fn device_id_or_preferred(&self, device_id: Option<&str>) -> Option<String> {
+        device_id
+            .map(ToOwned::to_owned)
+            .or_else(|| self.preferred_device_id())
+    }
+
+    #[cfg(feature = "player")]
     pub async fn get_currently_playing_track(
-        &mut self,
-        market: Option<&str>,
+        &self,
+        market: Option<Market>,
     ) -> Result<PlaybackState> {
-        let market = market.map(|m| [("market", m)]);
-        self.get::<Option<[(&str, &str); 1]>, _>("/me/player/currently-playing".to_owned(), market)
+        self.playback_state("/me/player/currently-playing", market)
             .await
     }
 
-    pub fn start_playback(&mut self) -> Builder<'_, F, V, StartPlaybackEndpoint> {
-        self.builder(StartPlaybackEndpoint::default())
+    // Gets the playback state from `path`, or from the cache if it was fetched recently.
+    #[cfg(feature = "player")]
+    async fn playback_state(
+        &self,
+        path: &'static str,
+        market: Option<Market>,
+    ) -> Result<PlaybackState> {
+        let key = (path, market.or(self.default_market()));
+
+        if let Some(state) = self.playback_cache().get(&key) {
+            return Ok(state);
+        }
+
+        let query = key.1.map(|m| [("market", m)]);
+        let state = self
+            .get::<[(&str, Market); 1], _>(path.to_owned(), query)
+            .await?;
+
+        self.playback_cache().insert(key, &state);
+
+        Ok(state)
+    }
+
+    /// Get the user's playback state, or `None` when nothing is playing, in the
+    /// [default market](crate::ClientBuilder::default_market) if there is one.
+    ///
+    /// Unlike [`get_playback_state`](Self::get_playback_state), the request is always sent,
+    /// rather than answered from the [playback state cache](crate::ClientBuilder::playback_cache).
+    #[cfg(feature = "player")]
+    pub async fn current_playback(&self) -> Result<Option<PlaybackState>> {
+        let endpoint = "/me/player".to_owned();
+        let query = self.default_market().map(|m| [("market", m)]);
+
+        let (_, body) = self
+            .request_bytes(
+                Method::GET,
+                endpoint.clone(),
+                query,
+                None,
+                &Overrides::default(),
+            )
+            .await?;
+
+        if body.iter().all(u8::is_ascii_whitespace) {
+            return Ok(None);
+        }
+
+        let state = deserialize_body(&endpoint, body)?;
+        self.playback_cache()
+            .insert(("/me/player", self.default_market()), &state);
+
+        Ok(Some(state))
+    }
+
+    #[cfg(feature = "player")]
+    pub fn start_playback(&self) -> Builder<'_, F, StartPlaybackEndpoint> {
+        self.builder(StartPlaybackEndpoint {
+            device_id: self.preferred_device_id(),
+            ..Default::default()
+        })
+    }
+
+    /// Play an album from the start on the given device, or the active (or preferred)
+    /// device if it's `None`. The album can be given by its ID, URI or URL.
+    ///
+    /// Use [`start_playback`](Self::start_playback) to start from another track, or at
+    /// another position.
+    ///
+    /// ```no_run
+    /// # async fn example(spotify: &spotify_rs::AuthCodeClient) -> spotify_rs::SpotifyResult<()> {
+    /// spotify.play_album("4aawyAB9vmqN3uQ7FjRGTy", None).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "player")]
+    pub async fn play_album(&self, id: impl AsId<AlbumId>, device_id: Option<&str>) -> Result<Nil> {
+        let uri = id.as_id().parse::<AlbumId>()?.uri();
+        self.play(device_id, Some(uri), None).await
+    }
+
+    /// Play a playlist from the start on the given device, or the active (or preferred)
+    /// device if it's `None`. The playlist can be given by its ID, URI or URL.
+    #[cfg(feature = "player")]
+    pub async fn play_playlist(
+        &self,
+        id: impl AsId<PlaylistId>,
+        device_id: Option<&str>,
+    ) -> Result<Nil> {
+        let uri = id.as_id().parse::<PlaylistId>()?.uri();
+        self.play(device_id, Some(uri), None).await
+    }
+
+    /// Play the given tracks, in order, on the given device, or the active (or preferred)
+    /// device if it's `None`. The tracks can be given by their IDs, URIs or URLs.
+    ///
+    /// Nothing is played (and no request sent) if there are no tracks.
+    #[cfg(feature = "player")]
+    pub async fn play_tracks<T: AsId<TrackId>>(
+        &self,
+        ids: &[T],
+        device_id: Option<&str>,
+    ) -> Result<Nil> {
+        if ids.is_empty() {
+            return Ok(Nil);
+        }
+
+        let uris = ids
+            .iter()
+            .map(|id| id.as_id().parse::<TrackId>().map(|id| id.uri()))
+            .collect::<Result<_>>()?;
+
+        self.play(device_id, None, Some(uris)).await
+    }
+
+    #[cfg(feature = "player")]
+    async fn play(
+        &self,
+        device_id: Option<&str>,
+        context_uri: Option<String>,
+        uris: Option<Vec<String>>,
+    ) -> Result<Nil> {
+        self.builder(StartPlaybackEndpoint {
+            device_id: self.device_id_or_preferred(device_id),
+            context_uri,
+            uris,
+            ..Default::default()
+        })
+        .send()
+        .await
     }
 
-    pub async fn pause_playback(&mut self, device_id: Option<&str>) -> Result<Nil> {
-        let device_id = device_id.map(|d| [("device_id", d)]);
+    #[cfg(feature = "player")]
+    pub async fn pause_playback(&self, device_id: Option<&str>) -> Result<Nil> {
+        let device_id = self.device_id_or_preferred(device_id);
+        let device_id = device_id.as_deref().map(|d| [("device_id", d)]);
         self.request(Method::PUT, "/me/player/pause".to_owned(), device_id, None)
             .await
     }
 
-    pub async fn skip_to_next(&mut self, device_id: Option<&str>) -> Result<Nil> {
-        let device_id = device_id.map(|d| [("device_id", d)]);
+    #[cfg(feature = "player")]
+    pub async fn skip_to_next(&self, device_id: Option<&str>) -> Result<Nil> {
+        let device_id = self.device_id_or_preferred(device_id);
+        let device_id = device_id.as_deref().map(|d| [("device_id", d)]);
         self.request(Method::POST, "/me/player/next".to_owned(), device_id, None)
             .await
     }
 
-    pub async fn skip_to_previous(&mut self, device_id: Option<&str>) -> Result<Nil> {
-        let device_id = device_id.map(|d| [("device_id", d)]);
+    #[cfg(feature = "player")]
+    pub async fn skip_to_previous(&self, device_id: Option<&str>) -> Result<Nil> {
+        let device_id = self.device_id_or_preferred(device_id);
+        let device_id = device_id.as_deref().map(|d| [("device_id", d)]);
         self.request(
             Method::POST,
             "/me/player/previous".to_owned(),
@@ -915,74 +2689,136 @@ impl<F: AuthFlow + Authorised, V: Verifier> Client<Token, F, V> {
         .await
     }
 
-    pub fn seek_to_position(&mut self, position: u32) -> Builder<'_, F, V, SeekToPositionEndpoint> {
+    #[cfg(feature = "player")]
+    pub fn seek_to_position(&self, position: u32) -> Builder<'_, F, SeekToPositionEndpoint> {
         self.builder(SeekToPositionEndpoint {
             position_ms: position,
-            device_id: None,
+            device_id: self.preferred_device_id(),
         })
     }
 
     /// *Note: This endpoint seems to be broken, returning 403 Forbidden "Player command failed: Restriction violated"*
+    #[cfg(feature = "player")]
     pub fn set_repeat_mode(
-        &mut self,
+        &self,
         repeat_mode: RepeatMode,
-    ) -> Builder<'_, F, V, SetRepeatModeEndpoint> {
+    ) -> Builder<'_, F, SetRepeatModeEndpoint> {
         self.builder(SetRepeatModeEndpoint {
             state: repeat_mode,
-            device_id: None,
+            device_id: self.preferred_device_id(),
         })
     }
 
-    pub fn set_playback_volume(
-        &mut self,
-        volume: u32,
-    ) -> Builder<'_, F, V, SetPlaybackVolumeEndpoint> {
+    #[cfg(feature = "player")]
+    pub fn set_playback_volume(&self, volume: u32) -> Builder<'_, F, SetPlaybackVolumeEndpoint> {
         self.builder(SetPlaybackVolumeEndpoint {
             volume_percent: volume,
-            device_id: None,
+            device_id: self.preferred_device_id(),
         })
     }
 
     /// *Note: This endpoint seems to be broken, returning 403 Forbidden "Player command failed: Restriction violated"*
-    pub fn toggle_playback_shuffle(
-        &mut self,
-        shuffle: bool,
-    ) -> Builder<'_, F, V, ToggleShuffleEndpoint> {
+    #[cfg(feature = "player")]
+    pub fn toggle_playback_shuffle(&self, shuffle: bool) -> Builder<'_, F, ToggleShuffleEndpoint> {
         self.builder(ToggleShuffleEndpoint {
             state: shuffle,
-            device_id: None,
+            device_id: self.preferred_device_id(),
         })
     }
 
-    pub fn recently_played_tracks(&mut self) -> Builder<'_, F, V, RecentlyPlayedTracksEndpoint> {
-        self.builder(RecentlyPlayedTracksEndpoint::default())
+    #[cfg(feature = "player")]
+    pub fn recently_played_tracks(&self) -> Builder<'_, F, RecentlyPlayedTracksEndpoint> {
+        self.builder(RecentlyPlayedTracksEndpoint {
+            limit: self.default_limit.map(|l| l.clamp(1, 50)),
+            after: None,
+            before: None,
+            marker: PhantomData,
+        })
     }
 
-    pub async fn get_user_queue(&mut self) -> Result<Queue> {
+    #[cfg(feature = "player")]
+    pub async fn get_user_queue(&self) -> Result<Queue> {
         self.get::<(), _>("/me/player/queue".to_owned(), None).await
     }
 
+    /// Check if the item with the given URI is in the user's queue or currently playing.
+    #[cfg(feature = "player")]
+    pub async fn is_queued(&self, uri: impl AsRef<str>) -> Result<bool> {
+        let queue = self.get_user_queue().await?;
+        Ok(queue.contains(uri.as_ref()))
+    }
+
+    #[cfg(feature = "player")]
     pub fn add_item_to_queue(
-        &mut self,
+        &self,
         uri: impl Into<String>,
-    ) -> Builder<'_, F, V, AddItemToQueueEndpoint> {
+    ) -> Builder<'_, F, AddItemToQueueEndpoint> {
         self.builder(AddItemToQueueEndpoint {
             uri: uri.into(),
-            device_id: None,
+            device_id: self.preferred_device_id(),
+        })
+    }
+
+    /// Add several items to the user's queue, in the given order.
+    ///
+    /// Spotify can only add one item at a time, so a request is sent for each.
+    #[cfg(feature = "player")]
+    pub fn add_items_to_queue<T: AsRef<str>>(
+        &self,
+        uris: &[T],
+    ) -> Builder<'_, F, AddItemsToQueueEndpoint> {
+        self.builder(AddItemsToQueueEndpoint {
+            uris: uris.iter().map(|u| u.as_ref().to_owned()).collect(),
+            device_id: self.preferred_device_id(),
         })
     }
 }
 
+impl<F: AuthFlow, V: Verifier> Client<UnAuthenticated, F, V> {
+    fn into_authenticated(self, token: Token) -> Client<Token, F> {
+        Client {
+            auto_refresh: self.auto_refresh,
+            refresh_on_unauthorised: self.refresh_on_unauthorised,
+            auth: RwLock::new(token),
+            oauth: self.oauth,
+            http: self.http,
+            api_url: self.api_url,
+            local: self.local,
+            default_market: self.default_market,
+            default_locale: self.default_locale,
+            utc_offset: self.utc_offset,
+            default_limit: self.default_limit,
+            retry_policy: self.retry_policy,
+            rate_limiter: self.rate_limiter,
+            token_store: self.token_store,
+            on_token_refresh: self.on_token_refresh,
+            refresh_lock: AsyncMutex::default(),
+            cache: self.cache,
+            middleware: self.middleware,
+            #[cfg(feature = "player")]
+            playback_cache: self.playback_cache,
+            #[cfg(feature = "player")]
+            preferred_device: self.preferred_device,
+            verifier: NoVerifier,
+            marker: PhantomData,
+        }
+    }
+}
+
 impl Client<UnAuthenticated, AuthCodeFlow, CsrfVerifier> {
     /// This will exchange the `auth_code` for a token which will allow the client
     /// to make requests.
     ///
     /// `csrf_state` is used for CSRF protection.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, err(Display))
+    )]
     pub async fn authenticate(
         self,
         auth_code: impl Into<String>,
         csrf_state: impl AsRef<str>,
-    ) -> Result<Client<Token, AuthCodeFlow, NoVerifier>> {
+    ) -> Result<AuthCodeClient> {
         let auth_code = auth_code.into().trim().to_owned();
         let csrf_state = csrf_state.as_ref().trim();
 
@@ -993,18 +2829,63 @@ impl Client<UnAuthenticated, AuthCodeFlow, CsrfVerifier> {
         let token = self
             .oauth
             .exchange_code(AuthorizationCode::new(auth_code))
-            .request_async(async_http_client)
+            .request_async(|req| oauth_request(&self.http, req))
             .await?
-            .set_timestamps();
+            .into_token();
 
-        Ok(Client {
-            auto_refresh: self.auto_refresh,
-            auth: token,
-            oauth: self.oauth,
-            http: self.http,
-            verifier: NoVerifier,
-            marker: PhantomData,
-        })
+        let client = self.into_authenticated(token);
+
+        client.save_token().await?;
+        Ok(client)
+    }
+}
+
+impl Client<UnAuthenticated, ImplicitGrantFlow, CsrfVerifier> {
+    /// Authenticate the client with the fragment of the URL the user was redirected to,
+    /// e.g. `access_token=...&token_type=Bearer&expires_in=3600&state=...`.
+    ///
+    /// The whole URL can be passed as well, in which case everything up to the `#` is
+    /// ignored. The `state` parameter in it is used for CSRF protection.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, err(Display))
+    )]
+    pub async fn authenticate(self, fragment: impl AsRef<str>) -> Result<ImplicitGrantClient> {
+        let fragment = fragment.as_ref().trim();
+        let fragment = fragment.rsplit_once('#').map_or(fragment, |(_, f)| f);
+
+        let params: HashMap<String, String> =
+            serde_urlencoded::from_str(fragment).map_err(|e| Error::Authentication {
+                kind: Kind::Parse,
+                description: format!("Failed to parse the URL fragment: {e}"),
+            })?;
+
+        if params.get("state").map(String::as_str) != Some(self.verifier.0.secret()) {
+            return Err(Error::InvalidStateParameter);
+        }
+
+        if let Some(error) = params.get("error") {
+            return Err(Error::Authentication {
+                kind: Kind::ServerResponse,
+                description: format!("The app wasn't authorised: {error}"),
+            });
+        }
+
+        let (Some(access_token), Some(expires_in)) = (
+            params.get("access_token"),
+            params.get("expires_in").and_then(|e| e.parse().ok()),
+        ) else {
+            return Err(Error::Authentication {
+                kind: Kind::Parse,
+                description: "The URL fragment doesn't contain an access token and its expiry."
+                    .to_owned(),
+            });
+        };
+
+        let client = self.into_authenticated(Token::new(access_token.clone(), expires_in, None));
+
+        client.save_token().await?;
+        Ok(client)
     }
 }
 
@@ -1013,11 +2894,15 @@ impl Client<UnAuthenticated, AuthCodePkceFlow, PkceVerifier> {
     /// to make requests.
     ///
     /// `csrf_state` is used for CSRF protection.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, err(Display))
+    )]
     pub async fn authenticate(
         self,
         auth_code: impl Into<String>,
         csrf_state: impl AsRef<str>,
-    ) -> Result<Client<Token, AuthCodePkceFlow, NoVerifier>> {
+    ) -> Result<AuthCodePkceClient> {
         let auth_code = auth_code.into().trim().to_owned();
         let csrf_state = csrf_state.as_ref().trim();
 
@@ -1028,19 +2913,17 @@ impl Client<UnAuthenticated, AuthCodePkceFlow, PkceVerifier> {
         let token = self
             .oauth
             .exchange_code(AuthorizationCode::new(auth_code))
-            .set_pkce_verifier(self.verifier.pkce_verifier)
-            .request_async(async_http_client)
+            .set_pkce_verifier(PkceCodeVerifier::new(
+                self.verifier.pkce_verifier.secret().to_owned(),
+            ))
+            .request_async(|req| oauth_request(&self.http, req))
             .await?
-            .set_timestamps();
+            .into_token();
 
-        Ok(Client {
-            auto_refresh: self.auto_refresh,
-            auth: token,
-            oauth: self.oauth,
-            http: self.http,
-            verifier: NoVerifier,
-            marker: PhantomData,
-        })
+        let client = self.into_authenticated(token);
+
+        client.save_token().await?;
+        Ok(client)
     }
 }
 
@@ -1050,32 +2933,134 @@ impl Client<UnAuthenticated, ClientCredsFlow, NoVerifier> {
     ///
     /// This authentication method doesn't allow for token refreshing or to access
     /// user resources.
-    pub async fn authenticate(
-        ClientCredsFlow {
-            client_id,
-            client_secret,
-        }: ClientCredsFlow,
-    ) -> Result<Client<Token, ClientCredsFlow, NoVerifier>> {
-        let oauth = OAuthClient::new(
-            ClientId::new(client_id),
-            Some(ClientSecret::new(client_secret)),
-            AuthUrl::new(AUTHORISATION_URL.to_owned()).unwrap(),
-            Some(TokenUrl::new(TOKEN_URL.to_owned()).unwrap()),
-        );
+    ///
+    /// Use a [`ClientBuilder`] to configure the client further.
+    pub async fn authenticate(auth_flow: ClientCredsFlow) -> Result<ClientCredsClient> {
+        ClientBuilder::new(auth_flow).authenticate().await
+    }
 
-        let token = oauth
-            .exchange_client_credentials()
-            .request_async(async_http_client)
-            .await?
-            .set_timestamps();
+    /// Exchange the client ID and secret of your app for an access token, the same as
+    /// [`authenticate`](Self::authenticate) with a [`ClientCredsFlow`].
+    ///
+    /// No redirect URL is needed, and as no user authorises the client, the endpoints
+    /// that access user resources aren't available on it.
+    ///
+    /// ```no_run
+    /// # async fn example() -> spotify_rs::SpotifyResult<()> {
+    /// use spotify_rs::ClientCredsClient;
+    ///
+    /// let spotify = ClientCredsClient::with_client_credentials("client_id", "client_secret").await?;
+    /// let album = spotify.album("album_id").get().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn with_client_credentials(
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+    ) -> Result<ClientCredsClient> {
+        Self::authenticate(ClientCredsFlow::new(client_id, client_secret)).await
+    }
+}
 
-        Ok(Client {
-            auto_refresh: false,
-            auth: token,
-            oauth,
-            http: reqwest::Client::new(),
-            verifier: NoVerifier,
-            marker: PhantomData,
-        })
+// The types of items that can be saved in a user's library.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum LibraryItem {
+    Track,
+    Album,
+    #[cfg(feature = "shows")]
+    Episode,
+    #[cfg(feature = "shows")]
+    Show,
+    #[cfg(feature = "audiobooks")]
+    Audiobook,
+}
+
+impl LibraryItem {
+    // Splits a URI such as `spotify:track:{id}` into the type of item and its ID.
+    fn from_uri(uri: &str) -> Result<(Self, &str)> {
+        let unsupported = || Error::Configuration(format!("can't check if {uri} is saved"));
+
+        let mut parts = uri.split(':');
+        let (Some("spotify"), Some(kind), Some(id), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(unsupported());
+        };
+
+        let item = match kind {
+            "track" => Self::Track,
+            "album" => Self::Album,
+            #[cfg(feature = "shows")]
+            "episode" => Self::Episode,
+            #[cfg(feature = "shows")]
+            "show" => Self::Show,
+            #[cfg(feature = "audiobooks")]
+            "audiobook" => Self::Audiobook,
+            _ => return Err(unsupported()),
+        };
+
+        Ok((item, id))
+    }
+}
+
+// Sends the requests of the auth flows (e.g. exchanging a code for a token) with the
+// client's own HTTP client, so that they use its configuration as well.
+pub(crate) async fn oauth_request(
+    http: &reqwest::Client,
+    request: HttpRequest,
+) -> std::result::Result<HttpResponse, oauth2::reqwest::Error<reqwest::Error>> {
+    let mut builder = http
+        .request(request.method, request.url.as_str())
+        .body(request.body);
+
+    for (name, value) in &request.headers {
+        builder = builder.header(name.as_str(), value.as_bytes());
+    }
+
+    let response = http
+        .execute(builder.build().map_err(oauth2::reqwest::Error::Reqwest)?)
+        .await
+        .map_err(oauth2::reqwest::Error::Reqwest)?;
+
+    let status_code = response.status();
+    let headers = response.headers().to_owned();
+    let body = response
+        .bytes()
+        .await
+        .map_err(oauth2::reqwest::Error::Reqwest)?;
+
+    Ok(HttpResponse {
+        status_code,
+        headers,
+        body: body.to_vec(),
+    })
+}
+
+fn response_body(status: StatusCode, body: Bytes) -> Result<Bytes> {
+    if status.is_success() {
+        Ok(body)
+    } else if status == StatusCode::TOO_MANY_REQUESTS {
+        Err(Error::RateLimited { retry_after: None })
+    } else {
+        Err(Error::response(status.as_u16(), &body))
+    }
+}
+
+#[cfg(not(feature = "simd-json"))]
+fn deserialize_body<T: DeserializeOwned>(endpoint: &str, body: Bytes) -> Result<T> {
+    serde_json::from_slice(&body).map_err(|e| Error::deserialization(endpoint, &body, e))
+}
+
+// simd-json parses in place, so the original bytes are kept around in order
+// to fall back to serde_json for bodies simd-json rejects (e.g. empty responses).
+#[cfg(feature = "simd-json")]
+fn deserialize_body<T: DeserializeOwned>(endpoint: &str, body: Bytes) -> Result<T> {
+    let mut buf = body.to_vec();
+
+    match simd_json::serde::from_slice(&mut buf) {
+        Ok(value) => Ok(value),
+        Err(_) => {
+            serde_json::from_slice(&body).map_err(|e| Error::deserialization(endpoint, &body, e))
+        }
     }
 }
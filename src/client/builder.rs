@@ -0,0 +1,659 @@
+#[cfg(feature = "player")]
+use std::sync::Mutex;
+use std::{marker::PhantomData, sync::RwLock, time::Duration};
+
+use chrono::FixedOffset;
+use futures_util::lock::Mutex as AsyncMutex;
+use oauth2::{AuthUrl, CsrfToken, PkceCodeChallenge, RedirectUrl, RefreshToken, TokenUrl};
+#[cfg(not(target_arch = "wasm32"))]
+use reqwest::Proxy;
+use reqwest::Url;
+
+use crate::{
+    auth::{
+        AuthCodeFlow, AuthCodePkceFlow, AuthFlow, AuthenticationState, ClientCredsFlow,
+        CsrfVerifier, ImplicitGrantFlow, NoVerifier, PkceVerifier, Secret, Token, UnAuthenticated,
+        Verifier,
+    },
+    cache::{CacheBackend, SharedCache},
+    error::{Error, Result},
+    locale::Locale,
+    market::Market,
+    middleware::{Middleware, MiddlewareChain},
+    token_store::{SharedTokenStore, TokenStore},
+};
+
+use super::{
+    oauth_request, AuthCodeClient, AuthCodePkceClient, Client, ClientCredsClient,
+    ImplicitGrantClient, Local, OAuthClient, RateLimiter, RetryPolicy, TokenRefreshHook,
+};
+#[cfg(feature = "player")]
+use super::{PlaybackCache, PreferredDevice};
+
+const API_URL: &str = "https://api.spotify.com/v1";
+const ACCOUNTS_URL: &str = "https://accounts.spotify.com";
+
+// Environment variables that override the default base URLs, so that the client can be
+// pointed at a mock server without changing the code that creates it.
+const API_URL_VAR: &str = "SPOTIFY_API_URL";
+const ACCOUNTS_URL_VAR: &str = "SPOTIFY_ACCOUNTS_URL";
+
+/// A builder used to configure a [`Client`] before creating it.
+///
+/// The finishing method depends on the auth flow: [`build`](Self::build) for the
+/// authorisation code flows, [`authenticate`](Self::authenticate) for the client
+/// credentials flow and [`from_refresh_token`](Self::from_refresh_token) for any of them.
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use spotify_rs::{AuthCodeFlow, ClientBuilder, RedirectUrl};
+/// # use std::error::Error;
+///
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let auth_flow = AuthCodeFlow::new("client_id", "client_secret", ["user-library-read"]);
+///
+/// let (client, url) = ClientBuilder::new(auth_flow)
+///     .redirect_uri(RedirectUrl::new("redirect_url".to_owned())?)
+///     .auto_refresh(true)
+///     .timeout(Duration::from_secs(10))
+///     .user_agent("my-app/1.0")
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ClientBuilder<F: AuthFlow> {
+    auth_flow: F,
+    redirect_uri: Option<RedirectUrl>,
+    auto_refresh: bool,
+    refresh_on_unauthorised: bool,
+    #[cfg(not(target_arch = "wasm32"))]
+    timeout: Option<Duration>,
+    #[cfg(not(target_arch = "wasm32"))]
+    connect_timeout: Option<Duration>,
+    user_agent: Option<String>,
+    #[cfg(not(target_arch = "wasm32"))]
+    proxy: Option<Proxy>,
+    http: Option<reqwest::Client>,
+    api_url: String,
+    accounts_url: String,
+    local: Local,
+    default_market: Option<Market>,
+    default_locale: Option<Locale>,
+    utc_offset: Option<FixedOffset>,
+    default_limit: Option<u32>,
+    retry_policy: Option<RetryPolicy>,
+    rate_limiter: Option<RateLimiter>,
+    token_store: Option<SharedTokenStore>,
+    on_token_refresh: Option<TokenRefreshHook>,
+    cache: Option<SharedCache>,
+    middleware: MiddlewareChain,
+    #[cfg(feature = "player")]
+    playback_cache: Option<Duration>,
+    #[cfg(feature = "player")]
+    preferred_device: Option<PreferredDevice>,
+}
+
+impl<F: AuthFlow> ClientBuilder<F> {
+    /// Create a new builder for the given auth flow.
+    pub fn new(auth_flow: F) -> Self {
+        Self {
+            auth_flow,
+            redirect_uri: None,
+            auto_refresh: false,
+            refresh_on_unauthorised: true,
+            #[cfg(not(target_arch = "wasm32"))]
+            timeout: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            connect_timeout: None,
+            user_agent: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            proxy: None,
+            http: None,
+            api_url: base_url(API_URL_VAR, API_URL),
+            accounts_url: base_url(ACCOUNTS_URL_VAR, ACCOUNTS_URL),
+            local: Local::default(),
+            default_market: None,
+            default_locale: None,
+            utc_offset: None,
+            default_limit: None,
+            retry_policy: None,
+            rate_limiter: None,
+            token_store: None,
+            on_token_refresh: None,
+            cache: None,
+            middleware: MiddlewareChain::default(),
+            #[cfg(feature = "player")]
+            playback_cache: None,
+            #[cfg(feature = "player")]
+            preferred_device: None,
+        }
+    }
+
+    /// The URI the user is redirected to after authorising the app.
+    ///
+    /// This is required by the authorisation code flows and should match the
+    /// redirect URI set in your app's settings.
+    pub fn redirect_uri(mut self, redirect_uri: RedirectUrl) -> Self {
+        self.redirect_uri = Some(redirect_uri);
+        self
+    }
+
+    /// Whether or not the client will request a new token when the current one
+    /// is about to expire. Defaults to `false`.
+    ///
+    /// This has no effect on the client credentials flow, which can't refresh tokens.
+    pub fn auto_refresh(mut self, auto_refresh: bool) -> Self {
+        self.auto_refresh = auto_refresh;
+        self
+    }
+
+    /// Whether or not the client will refresh the token and send a request again, once,
+    /// when Spotify rejects the token with `401 Unauthorized` before it was due to expire,
+    /// e.g. because it was revoked or the system clock is off. Defaults to `true`.
+    ///
+    /// This only applies when [`auto_refresh`](Self::auto_refresh) is on.
+    pub fn refresh_on_unauthorised(mut self, refresh_on_unauthorised: bool) -> Self {
+        self.refresh_on_unauthorised = refresh_on_unauthorised;
+        self
+    }
+
+    /// The timeout for every request, from connecting until the response body has been read.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// The timeout for only the connect phase of every request.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// The `User-Agent` header sent with every request.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Send every request through `proxy`.
+    ///
+    /// By default, the proxy set by the `HTTP_PROXY`/`HTTPS_PROXY` environment
+    /// variables is used, if any.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// The HTTP client used to send every request, including those of the auth flow.
+    ///
+    /// The client is used as-is, so the [`timeout`](Self::timeout),
+    /// [`connect_timeout`](Self::connect_timeout), [`user_agent`](Self::user_agent)
+    /// and [`proxy`](Self::proxy) set on this builder have no effect.
+    pub fn http_client(mut self, http: reqwest::Client) -> Self {
+        self.http = Some(http);
+        self
+    }
+
+    /// The base URL of the Web API. Defaults to the `SPOTIFY_API_URL` environment
+    /// variable if it's set to an HTTP(S) URL on the local machine, or
+    /// `https://api.spotify.com/v1` otherwise.
+    pub fn api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.api_url = api_url.into().trim_end_matches('/').to_owned();
+        self
+    }
+
+    /// The base URL of the accounts service used for authorisation. Defaults to the
+    /// `SPOTIFY_ACCOUNTS_URL` environment variable if it's set to an HTTP(S) URL on the
+    /// local machine, or `https://accounts.spotify.com` otherwise.
+    pub fn accounts_url(mut self, accounts_url: impl Into<String>) -> Self {
+        self.accounts_url = accounts_url.into().trim_end_matches('/').to_owned();
+        self
+    }
+
+    /// The market used by every request that supports one, unless the request sets its own
+    /// with `.market()`.
+    pub fn default_market(mut self, market: Market) -> Self {
+        self.default_market = Some(market);
+        self
+    }
+
+    /// The locale used by every request that supports one, e.g. browse categories and
+    /// featured playlists, unless the request sets its own with `.locale()`. Its country
+    /// is used the same way, unless the request sets its own with `.country()`.
+    pub fn default_locale(mut self, locale: Locale) -> Self {
+        self.default_locale = Some(locale);
+        self
+    }
+
+    /// The user's offset from UTC. If it's set, featured playlists are requested for the
+    /// user's local time (e.g. to get morning playlists in the morning), unless the request
+    /// sets its own with `.timestamp()`.
+    pub fn utc_offset(mut self, offset: FixedOffset) -> Self {
+        self.utc_offset = Some(offset);
+        self
+    }
+
+    /// The number of items requested per page by every paged endpoint, unless the request
+    /// sets its own with `.limit()`. Like `.limit()`, it's clamped to what each endpoint allows
+    /// (usually 1 to 50).
+    pub fn default_limit(mut self, limit: u32) -> Self {
+        self.default_limit = Some(limit);
+        self
+    }
+
+    /// Wait and retry requests that are rate limited, according to `policy`, instead of
+    /// failing them with [`Error::RateLimited`].
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
+    /// Send requests no faster than `limiter` allows, so that long jobs stay under
+    /// Spotify's rate limit instead of running into it.
+    pub fn rate_limit(mut self, limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// Save every new token the client gets, from authenticating or refreshing, to `store`.
+    ///
+    /// See [`token_store`](crate::token_store).
+    pub fn token_store(mut self, store: impl TokenStore + 'static) -> Self {
+        self.token_store = Some(SharedTokenStore::new(store));
+        self
+    }
+
+    /// Cache the responses to the client's reads in `cache`, honouring their `ETag` and
+    /// `Cache-Control` headers.
+    ///
+    /// See [`cache`](crate::cache).
+    pub fn cache(mut self, cache: impl CacheBackend + 'static) -> Self {
+        self.cache = Some(SharedCache::new(cache));
+        self
+    }
+
+    /// Send the client's requests to the API through `middleware`, which can inspect and
+    /// modify them and their responses. Middleware runs in the order it's added.
+    ///
+    /// See [`middleware`](crate::middleware).
+    pub fn with_middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middleware.push(middleware);
+        self
+    }
+
+    /// Call `on_token_refresh` with every new token the client gets by refreshing it, whether
+    /// automatically, with [`request_refresh_token`](Client::request_refresh_token) or in
+    /// [`from_refresh_token`](Self::from_refresh_token), e.g. to persist it or send it over
+    /// a channel to another part of the app.
+    ///
+    /// The callback runs inside the request that refreshed the token, so it shouldn't block.
+    ///
+    /// ```no_run
+    /// use std::sync::mpsc;
+    /// use spotify_rs::{AuthCodeFlow, ClientBuilder};
+    ///
+    /// # async fn example() -> spotify_rs::SpotifyResult<()> {
+    /// let (tx, rx) = mpsc::channel();
+    /// let auth_flow = AuthCodeFlow::new("client_id", "client_secret", ["user-library-read"]);
+    ///
+    /// let spotify = ClientBuilder::new(auth_flow)
+    ///     .auto_refresh(true)
+    ///     .on_token_refresh(move |token| {
+    ///         let _ = tx.send(token.clone());
+    ///     })
+    ///     .from_refresh_token("refresh_token")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_token_refresh(
+        mut self,
+        on_token_refresh: impl Fn(&Token) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_token_refresh = Some(TokenRefreshHook::new(on_token_refresh));
+        self
+    }
+
+    /// Reuse the responses of `get_playback_state` and `get_currently_playing_track` for
+    /// up to `max_age`, so that frequent reads (e.g. from a UI) don't all reach the API.
+    ///
+    /// The cache is cleared by every request that isn't a read, such as pausing playback,
+    /// so the next read reflects the change.
+    #[cfg(feature = "player")]
+    pub fn playback_cache(mut self, max_age: Duration) -> Self {
+        self.playback_cache = Some(max_age);
+        self
+    }
+
+    /// The device that player commands are sent to when they don't specify one,
+    /// instead of the user's active device.
+    #[cfg(feature = "player")]
+    pub fn preferred_device(mut self, device: PreferredDevice) -> Self {
+        self.preferred_device = Some(device);
+        self
+    }
+
+    /// Record the client's interactions with the API to, or replay them from, a
+    /// [`Cassette`](crate::vcr::Cassette).
+    #[cfg(feature = "vcr")]
+    pub fn cassette(mut self, cassette: crate::vcr::Cassette) -> Self {
+        self.local.cassette = Some(cassette);
+        self
+    }
+
+    /// Create a new authenticated and authorised client from a refresh token.
+    ///
+    /// This method will fail if the refresh token is invalid or a new one cannot be obtained.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, err(Display))
+    )]
+    pub async fn from_refresh_token(
+        self,
+        refresh_token: impl Into<String>,
+    ) -> Result<Client<Token, F>> {
+        let oauth = self.oauth_client()?;
+        let http = self.build_http_client()?;
+
+        let refresh_token = RefreshToken::new(refresh_token.into());
+        let mut req = oauth.exchange_refresh_token(&refresh_token);
+
+        if let Some(scopes) = self.auth_flow.scopes() {
+            req = req.add_scopes(scopes);
+        }
+
+        let mut token = req
+            .request_async(|req| oauth_request(&http, req))
+            .await?
+            .into_token();
+
+        // Spotify doesn't always send a new refresh token, in which case the old one
+        // remains valid.
+        if token.refresh_token.is_none() {
+            token.refresh_token = Some(Secret::new(refresh_token.secret().clone()));
+        }
+
+        let client = self.client(token, oauth, http, NoVerifier)?;
+
+        if let Some(on_token_refresh) = &client.on_token_refresh {
+            (on_token_refresh.0)(&client.token());
+        }
+
+        client.save_token().await?;
+        Ok(client)
+    }
+
+    /// Create a new authenticated client from a token that was obtained elsewhere, e.g. by
+    /// a web framework or another service that handled the authorisation, without going
+    /// through the auth flow again.
+    ///
+    /// `expires_in` is the number of seconds until the access token expires, as returned
+    /// with it. Without a `refresh_token`, the token can't be refreshed once it expires.
+    pub async fn from_access_token(
+        self,
+        access_token: impl Into<String>,
+        expires_in: u64,
+        refresh_token: Option<String>,
+    ) -> Result<Client<Token, F>> {
+        let oauth = self.oauth_client()?;
+        let http = self.build_http_client()?;
+
+        let token = Token::new(access_token.into(), expires_in, refresh_token);
+        let client = self.client(token, oauth, http, NoVerifier)?;
+
+        client.save_token().await?;
+        Ok(client)
+    }
+
+    /// Create a new client from the token saved in the builder's
+    /// [`token_store`](Self::token_store), which the client keeps saving new tokens to.
+    ///
+    /// Fails with [`Error::NotAuthenticated`] if there's no token store or no token
+    /// has been saved yet.
+    pub async fn from_token_store(self) -> Result<Client<Token, F>> {
+        let Some(store) = &self.token_store else {
+            return Err(Error::NotAuthenticated);
+        };
+
+        let Some(token) = store.load().await? else {
+            return Err(Error::NotAuthenticated);
+        };
+
+        let oauth = self.oauth_client()?;
+        let http = self.build_http_client()?;
+        self.client(token, oauth, http, NoVerifier)
+    }
+
+    fn oauth_client(&self) -> Result<OAuthClient> {
+        let auth_url = AuthUrl::new(format!("{}/authorize", self.accounts_url))
+            .map_err(|e| Error::Configuration(e.to_string()))?;
+        let token_url = TokenUrl::new(format!("{}/api/token", self.accounts_url))
+            .map_err(|e| Error::Configuration(e.to_string()))?;
+
+        let oauth = OAuthClient::new(
+            self.auth_flow.client_id(),
+            self.auth_flow.client_secret(),
+            auth_url,
+            Some(token_url),
+        );
+
+        Ok(match &self.redirect_uri {
+            Some(redirect_uri) => oauth.set_redirect_uri(redirect_uri.clone()),
+            None => oauth,
+        })
+    }
+
+    fn build_http_client(&self) -> Result<reqwest::Client> {
+        if let Some(http) = &self.http {
+            return Ok(http.clone());
+        }
+
+        let mut builder = reqwest::Client::builder();
+
+        // The browser's `fetch` has no timeouts or proxies to configure.
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            if let Some(timeout) = self.timeout {
+                builder = builder.timeout(timeout);
+            }
+
+            if let Some(connect_timeout) = self.connect_timeout {
+                builder = builder.connect_timeout(connect_timeout);
+            }
+
+            if let Some(proxy) = &self.proxy {
+                builder = builder.proxy(proxy.clone());
+            }
+        }
+
+        if let Some(user_agent) = &self.user_agent {
+            builder = builder.user_agent(user_agent);
+        }
+
+        builder
+            .build()
+            .map_err(|e| Error::Configuration(e.to_string()))
+    }
+
+    fn unauthenticated<V: Verifier>(
+        self,
+        oauth: OAuthClient,
+        verifier: V,
+    ) -> Result<Client<UnAuthenticated, F, V>> {
+        if self.redirect_uri.is_none() {
+            return Err(Error::Configuration(
+                "a redirect URI is required for this auth flow".to_owned(),
+            ));
+        }
+
+        let http = self.build_http_client()?;
+        self.client(UnAuthenticated, oauth, http, verifier)
+    }
+
+    fn client<A: AuthenticationState, V: Verifier>(
+        self,
+        auth: A,
+        oauth: OAuthClient,
+        http: reqwest::Client,
+        verifier: V,
+    ) -> Result<Client<A, F, V>> {
+        Ok(Client {
+            auto_refresh: self.auto_refresh,
+            refresh_on_unauthorised: self.refresh_on_unauthorised,
+            auth: RwLock::new(auth),
+            oauth,
+            http,
+            api_url: self.api_url,
+            local: self.local,
+            default_market: RwLock::new(self.default_market),
+            default_locale: RwLock::new(self.default_locale),
+            utc_offset: self.utc_offset,
+            default_limit: self.default_limit,
+            retry_policy: self.retry_policy,
+            rate_limiter: self.rate_limiter,
+            token_store: self.token_store,
+            on_token_refresh: self.on_token_refresh,
+            refresh_lock: AsyncMutex::default(),
+            cache: self.cache,
+            middleware: self.middleware,
+            #[cfg(feature = "player")]
+            playback_cache: Mutex::new(PlaybackCache {
+                max_age: self.playback_cache,
+                ..Default::default()
+            }),
+            #[cfg(feature = "player")]
+            preferred_device: RwLock::new(self.preferred_device),
+            verifier,
+            marker: PhantomData,
+        })
+    }
+}
+
+impl ClientBuilder<AuthCodeFlow> {
+    /// Create the client and generate an authorisation URL.
+    ///
+    /// You must redirect the user to the returned URL, which in turn redirects them to
+    /// the redirect URI you provided, along with a `code` and `state` parameter in the URL.
+    pub fn build(self) -> Result<(AuthCodeClient<UnAuthenticated, CsrfVerifier>, Url)> {
+        let oauth = self.oauth_client()?;
+
+        let (auth_url, csrf_token) = oauth
+            .authorize_url(CsrfToken::new_random)
+            .add_scopes(self.auth_flow.scopes.clone())
+            .url();
+
+        let client = self.unauthenticated(oauth, CsrfVerifier(csrf_token))?;
+        Ok((client, auth_url))
+    }
+}
+
+impl ClientBuilder<AuthCodePkceFlow> {
+    /// Create the client and generate an authorisation URL.
+    ///
+    /// You must redirect the user to the returned URL, which in turn redirects them to
+    /// the redirect URI you provided, along with a `code` and `state` parameter in the URL.
+    pub fn build(self) -> Result<(AuthCodePkceClient<UnAuthenticated, PkceVerifier>, Url)> {
+        let oauth = self.oauth_client()?;
+        let (pkce_challenge, pkce_verifier) = PkceCodeChallenge::new_random_sha256();
+
+        let (auth_url, csrf_token) = oauth
+            .authorize_url(CsrfToken::new_random)
+            .add_scopes(self.auth_flow.scopes.clone())
+            .set_pkce_challenge(pkce_challenge)
+            .url();
+
+        let verifier = PkceVerifier {
+            csrf_token,
+            pkce_verifier,
+        };
+
+        let client = self.unauthenticated(oauth, verifier)?;
+        Ok((client, auth_url))
+    }
+}
+
+impl ClientBuilder<ImplicitGrantFlow> {
+    /// Create the client and generate an authorisation URL.
+    ///
+    /// You must redirect the user to the returned URL, which in turn redirects them to
+    /// the redirect URI you provided, with the access token and a `state` parameter in
+    /// the URL's fragment.
+    pub fn build(mut self) -> Result<(ImplicitGrantClient<UnAuthenticated, CsrfVerifier>, Url)> {
+        let oauth = self.oauth_client()?;
+
+        let (auth_url, csrf_token) = oauth
+            .authorize_url(CsrfToken::new_random)
+            .add_scopes(self.auth_flow.scopes.clone())
+            .use_implicit_flow()
+            .url();
+
+        // The implicit grant flow doesn't give out refresh tokens.
+        self.auto_refresh = false;
+        let client = self.unauthenticated(oauth, CsrfVerifier(csrf_token))?;
+        Ok((client, auth_url))
+    }
+}
+
+impl ClientBuilder<ClientCredsFlow> {
+    /// Create the client and exchange the client credentials for an access token.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, err(Display))
+    )]
+    pub async fn authenticate(mut self) -> Result<ClientCredsClient> {
+        let oauth = self.oauth_client()?;
+        let http = self.build_http_client()?;
+
+        let token = oauth
+            .exchange_client_credentials()
+            .request_async(|req| oauth_request(&http, req))
+            .await?
+            .into_token();
+
+        self.auto_refresh = false;
+        let client = self.client(token, oauth, http, NoVerifier)?;
+
+        client.save_token().await?;
+        Ok(client)
+    }
+}
+
+// The base URL set in the environment variable, or the default if it isn't set or isn't a
+// valid HTTP(S) URL, so that a typo can't stop clients from being created.
+//
+// The variable is read by every client, so only URLs on the local machine (i.e. test
+// servers) are accepted, so that whoever can set it can't send the client secret and
+// tokens to a host of their choosing.
+fn base_url(var: &str, default: &str) -> String {
+    let Some(url) = std::env::var(var).ok().filter(|url| !url.is_empty()) else {
+        return default.to_owned();
+    };
+
+    match Url::parse(&url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") && is_loopback(&parsed) => {
+            url.trim_end_matches('/').to_owned()
+        }
+        _ => {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(var, url, "ignoring invalid base URL, using {default}");
+
+            default.to_owned()
+        }
+    }
+}
+
+fn is_loopback(url: &Url) -> bool {
+    match url.host_str() {
+        Some("localhost") => true,
+        Some(host) => host
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback()),
+        None => false,
+    }
+}
@@ -0,0 +1,91 @@
+#[cfg(feature = "image")]
+use std::borrow::Cow;
+
+use base64::{engine::general_purpose, Engine};
+
+use crate::error::{Error, Result};
+
+// Spotify's limit on the size of an uploaded playlist cover, which applies to the image
+// once it's Base64 encoded.
+pub(super) const MAX_SIZE: usize = 256 * 1024;
+
+// The quality covers are re-encoded with when they have to be converted or shrunk.
+#[cfg(feature = "image")]
+const QUALITY: u8 = 85;
+
+// Checks that an image can be uploaded as a playlist cover, converting it into a JPEG
+// that fits first with the `image` feature, and returns it Base64 encoded, as Spotify
+// expects. Spotify doesn't say why it rejects a cover, so it's better to fail here.
+pub(super) fn encode(image: &[u8]) -> Result<Vec<u8>> {
+    #[cfg(feature = "image")]
+    let image = &*fit(image)?;
+
+    if !is_jpeg(image) {
+        return Err(Error::UnsupportedImageFormat);
+    }
+
+    let encoded = general_purpose::STANDARD.encode(image).into_bytes();
+
+    if encoded.len() > MAX_SIZE {
+        return Err(Error::ImageTooLarge {
+            size: encoded.len(),
+            max: MAX_SIZE,
+        });
+    }
+
+    Ok(encoded)
+}
+
+fn is_jpeg(image: &[u8]) -> bool {
+    image.starts_with(&[0xFF, 0xD8, 0xFF])
+}
+
+#[cfg(feature = "image")]
+fn encoded_size(len: usize) -> usize {
+    len.div_ceil(3) * 4
+}
+
+// Converts a PNG into a JPEG, and downscales the image by a quarter at a time until its
+// JPEG fits. JPEGs that already fit are left as they are.
+#[cfg(feature = "image")]
+fn fit(image: &[u8]) -> Result<Cow<'_, [u8]>> {
+    use image::{codecs::jpeg::JpegEncoder, imageops::FilterType, DynamicImage, ImageFormat};
+
+    if is_jpeg(image) && encoded_size(image.len()) <= MAX_SIZE {
+        return Ok(Cow::Borrowed(image));
+    }
+
+    let format = match image::guess_format(image) {
+        Ok(format @ (ImageFormat::Jpeg | ImageFormat::Png)) => format,
+        _ => return Err(Error::UnsupportedImageFormat),
+    };
+
+    // JPEGs can't be transparent, so any alpha channel is dropped.
+    let mut decoded = image::load_from_memory_with_format(image, format)
+        .map(|i| DynamicImage::ImageRgb8(i.to_rgb8()))
+        .map_err(|_| Error::UnsupportedImageFormat)?;
+
+    loop {
+        let mut jpeg = Vec::new();
+        JpegEncoder::new_with_quality(&mut jpeg, QUALITY)
+            .encode_image(&decoded)
+            .map_err(|_| Error::UnsupportedImageFormat)?;
+
+        let size = encoded_size(jpeg.len());
+
+        if size <= MAX_SIZE {
+            return Ok(Cow::Owned(jpeg));
+        }
+
+        let (width, height) = (decoded.width() * 3 / 4, decoded.height() * 3 / 4);
+
+        if width == 0 || height == 0 {
+            return Err(Error::ImageTooLarge {
+                size,
+                max: MAX_SIZE,
+            });
+        }
+
+        decoded = decoded.resize(width, height, FilterType::Triangle);
+    }
+}
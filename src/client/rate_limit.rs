@@ -0,0 +1,125 @@
+use std::{
+    fmt,
+    future::Future,
+    sync::{Arc, Mutex, PoisonError},
+    time::Duration,
+};
+
+use web_time::Instant;
+
+use crate::endpoint::{BoxFuture, MaybeSend};
+
+type Sleep = Arc<dyn Fn(Duration) -> BoxFuture<'static, ()> + Send + Sync>;
+
+/// Limits how many requests the client sends, so that long jobs (such as exporting a
+/// library or mirroring playlists) stay under Spotify's rate limit, instead of running
+/// into it and waiting out `429 Too Many Requests` responses.
+///
+/// The limiter is a token bucket: it allows bursts of up to `requests` requests, and
+/// refills at a steady rate of `requests` every `window`. A request that finds the
+/// bucket empty waits until it has refilled enough. Every attempt at a request counts,
+/// including retries, but responses served from a [`cache`](crate::cache) don't.
+///
+/// Clones of a limiter share the same bucket, so one limiter can be given to several
+/// clients that share a rate limit, such as clients for different users of the same app.
+///
+/// Like [`RetryPolicy`](super::RetryPolicy), the limiter is given the function to wait
+/// with, such as `tokio::time::sleep`.
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use spotify_rs::{client::RateLimiter, ClientBuilder, ClientCredsFlow};
+/// # async fn sleep(_: Duration) {}
+///
+/// # async fn example() -> spotify_rs::SpotifyResult<()> {
+/// let auth_flow = ClientCredsFlow::new("client_id", "client_secret");
+/// let spotify = ClientBuilder::new(auth_flow)
+///     .rate_limit(RateLimiter::new(100, Duration::from_secs(30), sleep))
+///     .authenticate()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct RateLimiter {
+    capacity: f64,
+    // How many requests the bucket gains per second.
+    rate: f64,
+    bucket: Arc<Mutex<Bucket>>,
+    sleep: Sleep,
+}
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl RateLimiter {
+    /// Create a limiter that allows `requests` (at least 1) every `window`, and waits
+    /// using `sleep`.
+    pub fn new<S, Fut>(requests: u32, window: Duration, sleep: S) -> Self
+    where
+        S: Fn(Duration) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + MaybeSend + 'static,
+    {
+        let capacity = f64::from(requests.max(1));
+        // A window of zero would make the rate infinite, so it's at least a millisecond.
+        let window = window.as_secs_f64().max(0.001);
+
+        Self {
+            capacity,
+            rate: capacity / window,
+            bucket: Arc::new(Mutex::new(Bucket {
+                tokens: capacity,
+                updated: Instant::now(),
+            })),
+            sleep: Arc::new(move |duration| Box::pin(sleep(duration))),
+        }
+    }
+
+    // Wait until the bucket has a request to spare, and take it.
+    pub(crate) async fn acquire(&self) {
+        #[cfg(feature = "tracing")]
+        let mut waited = Duration::ZERO;
+
+        while let Some(wait) = self.try_acquire() {
+            (self.sleep)(wait).await;
+
+            #[cfg(feature = "tracing")]
+            {
+                waited += wait;
+            }
+        }
+
+        #[cfg(feature = "tracing")]
+        if !waited.is_zero() {
+            tracing::debug!(delay_ms = waited.as_millis() as u64, "rate_limited");
+        }
+    }
+
+    // Take a request from the bucket, or return how long to wait until it has one.
+    fn try_acquire(&self) -> Option<Duration> {
+        let mut bucket = self.bucket.lock().unwrap_or_else(PoisonError::into_inner);
+        let now = Instant::now();
+
+        let refilled = now.duration_since(bucket.updated).as_secs_f64() * self.rate;
+        bucket.tokens = (bucket.tokens + refilled).min(self.capacity);
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            None
+        } else {
+            Some(Duration::from_secs_f64((1.0 - bucket.tokens) / self.rate))
+        }
+    }
+}
+
+impl fmt::Debug for RateLimiter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RateLimiter")
+            .field("capacity", &self.capacity)
+            .field("rate", &self.rate)
+            .finish_non_exhaustive()
+    }
+}
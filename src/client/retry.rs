@@ -0,0 +1,154 @@
+use std::{
+    collections::hash_map::RandomState, fmt, future::Future, hash::BuildHasher, sync::Arc,
+    time::Duration,
+};
+
+use reqwest::{
+    header::{HeaderMap, RETRY_AFTER},
+    StatusCode,
+};
+
+use crate::endpoint::{BoxFuture, MaybeSend};
+
+/// How long to wait before retrying when Spotify doesn't say.
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(1);
+
+type Sleep = Arc<dyn Fn(Duration) -> BoxFuture<'static, ()> + Send + Sync>;
+
+/// How the client handles being rate limited (a `429 Too Many Requests` response), and
+/// optionally, Spotify's intermittent server errors.
+///
+/// Without a policy, a rate limited request fails with [`Error::RateLimited`](crate::Error::RateLimited).
+/// With one, the client waits for as long as the `Retry-After` header asks and sends the
+/// request again, up to [`max_retries`](Self::max_retries) times.
+///
+/// A policy can be set for the whole client with [`ClientBuilder::retry_policy`](crate::ClientBuilder::retry_policy),
+/// or for a single request with [`Builder::retry_policy`](crate::endpoint::Builder::retry_policy).
+///
+/// The client isn't tied to an async runtime, so the policy is given the function to
+/// wait with, such as `tokio::time::sleep`.
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use spotify_rs::{client::RetryPolicy, ClientBuilder, ClientCredsFlow};
+/// # async fn sleep(_: Duration) {}
+///
+/// # async fn example() -> spotify_rs::SpotifyResult<()> {
+/// let auth_flow = ClientCredsFlow::new("client_id", "client_secret");
+/// let spotify = ClientBuilder::new(auth_flow)
+///     .retry_policy(RetryPolicy::new(sleep).max_retries(5))
+///     .authenticate()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct RetryPolicy {
+    pub(crate) max_retries: u32,
+    pub(crate) max_wait: Option<Duration>,
+    server_error_delay: Option<Duration>,
+    sleep: Sleep,
+}
+
+impl RetryPolicy {
+    /// Create a policy that waits using `sleep`.
+    ///
+    /// By default, a request is retried up to 3 times, however long Spotify asks to wait.
+    pub fn new<S, Fut>(sleep: S) -> Self
+    where
+        S: Fn(Duration) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + MaybeSend + 'static,
+    {
+        Self {
+            max_retries: 3,
+            max_wait: None,
+            server_error_delay: None,
+            sleep: Arc::new(move |duration| Box::pin(sleep(duration))),
+        }
+    }
+
+    /// The maximum number of times a request is retried before giving up.
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Give up instead of waiting if Spotify asks to wait longer than `max_wait`.
+    pub fn max_wait(mut self, max_wait: Duration) -> Self {
+        self.max_wait = Some(max_wait);
+        self
+    }
+
+    /// Also retry `GET` and `PUT` requests that fail with a `500`, `502`, `503` or `504`
+    /// server error, which Spotify returns every now and then.
+    ///
+    /// The first retry waits up to `delay`, and each one after it up to twice as long as
+    /// the one before, but never longer than [`max_wait`](Self::max_wait) if it's set.
+    /// The waits are randomised, so that clients that failed at the same time don't all
+    /// retry at once.
+    /// Other requests aren't retried, as sending them twice may apply them twice.
+    pub fn retry_server_errors(mut self, delay: Duration) -> Self {
+        self.server_error_delay = Some(delay);
+        self
+    }
+
+    // Returns how long to wait before sending the request again, or `None` to give up.
+    pub(crate) fn wait(&self, retries: u32, retry_after: Option<Duration>) -> Option<Duration> {
+        let wait = retry_after.unwrap_or(DEFAULT_RETRY_AFTER);
+
+        (retries < self.max_retries && self.max_wait.is_none_or(|max| wait <= max)).then_some(wait)
+    }
+
+    // Like `wait`, but for a server error, after which the wait backs off exponentially.
+    pub(crate) fn server_error_wait(&self, retries: u32) -> Option<Duration> {
+        let delay = self.server_error_delay?;
+        let backoff = delay.saturating_mul(2u32.saturating_pow(retries));
+        let wait = jitter(backoff);
+
+        (retries < self.max_retries).then(|| self.max_wait.map_or(wait, |max| wait.min(max)))
+    }
+
+    pub(crate) async fn sleep(&self, duration: Duration) {
+        (self.sleep)(duration).await;
+    }
+}
+
+impl fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_retries", &self.max_retries)
+            .field("max_wait", &self.max_wait)
+            .field("server_error_delay", &self.server_error_delay)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The delay in a `Retry-After` header, which Spotify always sends as a number of seconds.
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    headers
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+// The server errors Spotify recovers from on its own.
+pub(crate) fn is_server_error(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+// A random duration between half and all of `duration`. `RandomState` is seeded randomly
+// each time, which is random enough for spreading out retries.
+fn jitter(duration: Duration) -> Duration {
+    let random = RandomState::new().hash_one(()) % 1000;
+    duration.mul_f64(0.5 + random as f64 / 2000.0)
+}
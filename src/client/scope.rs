@@ -0,0 +1,39 @@
+use reqwest::Method;
+
+const PLAYLIST_MODIFY: &[&str] = &["playlist-modify-public", "playlist-modify-private"];
+
+// The scopes a request needs, as groups that each need one of their scopes granted, or none
+// if it doesn't need any (or only needs one for some of what it returns, such as private
+// playlists).
+pub(super) fn required(method: &Method, endpoint: &str) -> &'static [&'static [&'static str]] {
+    let path = endpoint.split('?').next().unwrap_or(endpoint);
+    let segments: Vec<&str> = path.trim_start_matches('/').split('/').collect();
+    let read = method == Method::GET;
+
+    match segments.as_slice() {
+        ["me", "tracks" | "albums" | "episodes" | "shows" | "audiobooks", ..] if read => {
+            &[&["user-library-read"]]
+        }
+        ["me", "tracks" | "albums" | "episodes" | "shows" | "audiobooks", ..] => {
+            &[&["user-library-modify"]]
+        }
+        ["me", "top", ..] => &[&["user-top-read"]],
+        ["me", "following", ..] if read => &[&["user-follow-read"]],
+        ["me", "following", ..] => &[&["user-follow-modify"]],
+        ["me", "player", "currently-playing"] => &[&["user-read-currently-playing"]],
+        ["me", "player", "recently-played"] => &[&["user-read-recently-played"]],
+        ["me", "player", "queue"] if read => {
+            &[&["user-read-currently-playing", "user-read-playback-state"]]
+        }
+        ["me", "player", ..] if read => &[&["user-read-playback-state"]],
+        ["me", "player", ..] => &[&["user-modify-playback-state"]],
+        // Uploading a cover also needs permission to modify the playlist.
+        ["playlists", _, "images"] if !read => &[&["ugc-image-upload"], PLAYLIST_MODIFY],
+        ["playlists", _] | ["playlists", _, "tracks" | "followers"] | ["users", _, "playlists"]
+            if !read =>
+        {
+            &[PLAYLIST_MODIFY]
+        }
+        _ => &[],
+    }
+}
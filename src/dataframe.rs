@@ -0,0 +1,119 @@
+//! Converting models into [polars](https://docs.rs/polars) `DataFrame`s, for data analysis.
+//!
+//! [`ToDataFrame`] is implemented for slices (and therefore `Vec`s) of tracks, audio
+//! features and (with the `player` feature) play history, as well as for pages of them. Every field that maps to a single value becomes
+//! a column; artists are joined into one comma-separated string per track.
+//!
+//! ```no_run
+//! use spotify_rs::{dataframe::ToDataFrame, AuthCodeClient};
+//! # use std::error::Error;
+//!
+//! # async fn example(spotify: &AuthCodeClient) -> Result<(), Box<dyn Error>> {
+//! let tracks = spotify.tracks(&["track_1", "track_2"]).get().await?;
+//! let features = spotify.get_tracks_audio_features(&["track_1", "track_2"]).await?;
+//! let features: Vec<_> = features.into_iter().flatten().collect();
+//!
+//! let tracks = tracks.to_dataframe()?;
+//! let features = features.to_dataframe()?;
+//!
+//! let danceability = features.column("danceability")?;
+//! # Ok(())
+//! # }
+//! ```
+
+use polars::prelude::*;
+
+#[cfg(feature = "player")]
+use crate::model::player::PlayHistory;
+use crate::model::{audio::AudioFeatures, track::Track, CursorPage, Page};
+
+/// Conversion of a collection of models into a `DataFrame`, with one row per item.
+pub trait ToDataFrame {
+    /// Build a `DataFrame` from the items.
+    fn to_dataframe(&self) -> PolarsResult<DataFrame>;
+}
+
+impl ToDataFrame for [Track] {
+    fn to_dataframe(&self) -> PolarsResult<DataFrame> {
+        tracks(self.iter())
+    }
+}
+
+impl ToDataFrame for [AudioFeatures] {
+    fn to_dataframe(&self) -> PolarsResult<DataFrame> {
+        df!(
+            "id" => self.iter().map(|f| f.id.as_str()).collect::<Vec<_>>(),
+            "acousticness" => self.iter().map(|f| f.acousticness).collect::<Vec<_>>(),
+            "danceability" => self.iter().map(|f| f.danceability).collect::<Vec<_>>(),
+            "duration_ms" => self.iter().map(|f| f.duration_ms).collect::<Vec<_>>(),
+            "energy" => self.iter().map(|f| f.energy).collect::<Vec<_>>(),
+            "instrumentalness" => self.iter().map(|f| f.instrumentalness).collect::<Vec<_>>(),
+            "key" => self.iter().map(|f| f.key).collect::<Vec<_>>(),
+            "liveness" => self.iter().map(|f| f.liveness).collect::<Vec<_>>(),
+            "loudness" => self.iter().map(|f| f.loudness).collect::<Vec<_>>(),
+            "mode" => self.iter().map(|f| i32::from(f.mode)).collect::<Vec<_>>(),
+            "speechiness" => self.iter().map(|f| f.speechiness).collect::<Vec<_>>(),
+            "tempo" => self.iter().map(|f| f.tempo).collect::<Vec<_>>(),
+            "time_signature" => self.iter().map(|f| f.time_signature).collect::<Vec<_>>(),
+            "valence" => self.iter().map(|f| f.valence).collect::<Vec<_>>(),
+        )
+    }
+}
+
+/// Has the same columns as the tracks, preceded by `played_at` and followed by `context_uri`.
+#[cfg(feature = "player")]
+impl ToDataFrame for [PlayHistory] {
+    fn to_dataframe(&self) -> PolarsResult<DataFrame> {
+        let played_at: Vec<_> = self.iter().map(|h| h.played_at.naive_utc()).collect();
+        let context_uri: Vec<_> = self
+            .iter()
+            .map(|h| h.context.as_ref().map(|c| c.uri.as_str()))
+            .collect();
+
+        let mut df = tracks(self.iter().map(|h| &h.track))?;
+        df.insert_column(0, Column::new("played_at".into(), played_at))?;
+        df.with_column(Column::new("context_uri".into(), context_uri))?;
+
+        Ok(df)
+    }
+}
+
+impl<T> ToDataFrame for Page<T>
+where
+    [T]: ToDataFrame,
+{
+    fn to_dataframe(&self) -> PolarsResult<DataFrame> {
+        self.items.to_dataframe()
+    }
+}
+
+impl<T> ToDataFrame for CursorPage<T>
+where
+    [T]: ToDataFrame,
+{
+    fn to_dataframe(&self) -> PolarsResult<DataFrame> {
+        self.items.to_dataframe()
+    }
+}
+
+fn tracks<'a>(tracks: impl Iterator<Item = &'a Track>) -> PolarsResult<DataFrame> {
+    let tracks: Vec<_> = tracks.collect();
+
+    df!(
+        "id" => tracks.iter().map(|t| t.id.as_str()).collect::<Vec<_>>(),
+        "name" => tracks.iter().map(|t| t.name.as_str()).collect::<Vec<_>>(),
+        "artists" => tracks
+            .iter()
+            .map(|t| t.artists.iter().map(|a| a.name.as_str()).collect::<Vec<_>>().join(", "))
+            .collect::<Vec<_>>(),
+        "album" => tracks.iter().map(|t| t.album.name.as_str()).collect::<Vec<_>>(),
+        "album_id" => tracks.iter().map(|t| t.album.id.as_str()).collect::<Vec<_>>(),
+        "disc_number" => tracks.iter().map(|t| t.disc_number).collect::<Vec<_>>(),
+        "track_number" => tracks.iter().map(|t| t.track_number).collect::<Vec<_>>(),
+        "duration_ms" => tracks.iter().map(|t| t.duration_ms).collect::<Vec<_>>(),
+        "explicit" => tracks.iter().map(|t| t.explicit).collect::<Vec<_>>(),
+        "popularity" => tracks.iter().map(|t| t.popularity).collect::<Vec<_>>(),
+        "isrc" => tracks.iter().map(|t| t.external_ids.isrc.as_deref()).collect::<Vec<_>>(),
+        "uri" => tracks.iter().map(|t| t.uri.as_str()).collect::<Vec<_>>(),
+    )
+}
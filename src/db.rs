@@ -0,0 +1,85 @@
+//! Database support for the typed IDs and markets, which are stored as text: IDs as the
+//! plain ID, and markets as their country code.
+
+use crate::{id::*, market::Market};
+
+macro_rules! text_types {
+    ($($ty:ty => $as_str:ident),+ $(,)?) => {
+        $(
+            #[cfg(feature = "sqlx")]
+            impl<DB: sqlx::Database> sqlx::Type<DB> for $ty
+            where
+                String: sqlx::Type<DB>,
+            {
+                fn type_info() -> DB::TypeInfo {
+                    <String as sqlx::Type<DB>>::type_info()
+                }
+
+                fn compatible(ty: &DB::TypeInfo) -> bool {
+                    <String as sqlx::Type<DB>>::compatible(ty)
+                }
+            }
+
+            #[cfg(feature = "sqlx")]
+            impl<'q, DB: sqlx::Database> sqlx::Encode<'q, DB> for $ty
+            where
+                String: sqlx::Encode<'q, DB>,
+            {
+                fn encode_by_ref(
+                    &self,
+                    buf: &mut DB::ArgumentBuffer<'q>,
+                ) -> Result<sqlx::encode::IsNull, sqlx::error::BoxDynError> {
+                    self.$as_str().to_owned().encode_by_ref(buf)
+                }
+            }
+
+            #[cfg(feature = "sqlx")]
+            impl<'r, DB: sqlx::Database> sqlx::Decode<'r, DB> for $ty
+            where
+                String: sqlx::Decode<'r, DB>,
+            {
+                fn decode(value: DB::ValueRef<'r>) -> Result<Self, sqlx::error::BoxDynError> {
+                    Ok(String::decode(value)?.parse()?)
+                }
+            }
+
+            #[cfg(feature = "diesel")]
+            impl<DB: diesel::backend::Backend> diesel::serialize::ToSql<diesel::sql_types::Text, DB>
+                for $ty
+            where
+                str: diesel::serialize::ToSql<diesel::sql_types::Text, DB>,
+            {
+                fn to_sql<'b>(
+                    &'b self,
+                    out: &mut diesel::serialize::Output<'b, '_, DB>,
+                ) -> diesel::serialize::Result {
+                    self.$as_str().to_sql(out)
+                }
+            }
+
+            #[cfg(feature = "diesel")]
+            impl<DB: diesel::backend::Backend>
+                diesel::deserialize::FromSql<diesel::sql_types::Text, DB> for $ty
+            where
+                String: diesel::deserialize::FromSql<diesel::sql_types::Text, DB>,
+            {
+                fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+                    Ok(String::from_sql(bytes)?.parse()?)
+                }
+            }
+        )+
+    };
+}
+
+text_types! {
+    AlbumId => id,
+    ArtistId => id,
+    AudiobookId => id,
+    ChapterId => id,
+    EpisodeId => id,
+    PlaylistId => id,
+    ShowId => id,
+    TrackId => id,
+    UserId => id,
+    Market => as_str,
+}
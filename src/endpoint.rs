@@ -1,23 +1,115 @@
-use serde::Serialize;
+use std::{
+    future::{Future, IntoFuture},
+    ops::{Bound, Deref, RangeBounds},
+    pin::Pin,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use chrono::{DateTime, Utc};
+
+use reqwest::Method;
+use serde::{de::DeserializeOwned, Serialize};
 
 use crate::{
-    auth::{AuthFlow, Token, Verifier},
-    client::Client,
+    auth::{AuthFlow, Secret, Token},
+    chunked,
+    client::{Body, Client, Overrides, RawResponse, ResponseMeta, RetryPolicy},
+    error::Result as SpotifyResult,
+    model::{audio::AudioAnalysis, user::User, Page, SavedItem},
+    query_list,
 };
 
+/// The boxed future returned when awaiting a [`Builder`] directly.
+#[cfg(not(target_arch = "wasm32"))]
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// The boxed future returned when awaiting a [`Builder`] directly.
+///
+/// It isn't `Send` on WebAssembly, where the browser's `fetch` futures aren't either.
+#[cfg(target_arch = "wasm32")]
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+/// `Send` on every target except WebAssembly, where futures that make requests (or wait)
+/// can't be sent between threads, so nothing has to be.
+#[cfg(not(target_arch = "wasm32"))]
+pub trait MaybeSend: Send {}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<T: Send + ?Sized> MaybeSend for T {}
+
+/// `Send` on every target except WebAssembly, where futures that make requests (or wait)
+/// can't be sent between threads, so nothing has to be.
+#[cfg(target_arch = "wasm32")]
+pub trait MaybeSend {}
+
+#[cfg(target_arch = "wasm32")]
+impl<T: ?Sized> MaybeSend for T {}
+
+// Implements `IntoFuture` for builders by forwarding to their `get`/`send` method,
+// so `client.album("id").market(Market::GB).await` works the same as calling `.get()`.
+macro_rules! impl_into_future {
+    ([$($generics:tt)*] $endpoint:ty => $output:ty, $method:ident; $($rest:tt)*) => {
+        impl<'s, $($generics)* F: AuthFlow + Send + Sync> std::future::IntoFuture
+            for Builder<'s, F, $endpoint>
+        {
+            type Output = Result<$output>;
+            type IntoFuture = super::BoxFuture<'s, Self::Output>;
+
+            fn into_future(self) -> Self::IntoFuture {
+                Box::pin(self.$method())
+            }
+        }
+
+        impl_into_future!($($rest)*);
+    };
+    ($endpoint:ty => $output:ty, $method:ident; $($rest:tt)*) => {
+        impl_into_future!([] $endpoint => $output, $method; $($rest)*);
+    };
+    () => {};
+}
+
 pub mod album;
 pub mod artist;
+#[cfg(feature = "audiobooks")]
+#[cfg_attr(docsrs, doc(cfg(feature = "audiobooks")))]
 pub mod audiobook;
+#[cfg(feature = "browse")]
+#[cfg_attr(docsrs, doc(cfg(feature = "browse")))]
 pub mod category;
+#[cfg(feature = "player")]
+#[cfg_attr(docsrs, doc(cfg(feature = "player")))]
 pub mod player;
+#[cfg(feature = "playlists")]
+#[cfg_attr(docsrs, doc(cfg(feature = "playlists")))]
 pub mod playlist;
+#[cfg(feature = "search")]
+#[cfg_attr(docsrs, doc(cfg(feature = "search")))]
 pub mod search;
+#[cfg(feature = "shows")]
+#[cfg_attr(docsrs, doc(cfg(feature = "shows")))]
 pub mod show;
 pub mod track;
+#[cfg(feature = "user")]
+#[cfg_attr(docsrs, doc(cfg(feature = "user")))]
 pub mod user;
 
-pub trait Endpoint: Serialize {}
+pub trait Endpoint: Serialize {
+    /// The path of the endpoint, relative to the API's base URL.
+    fn path(&self) -> String;
+
+    /// The query parameters that are sent with the request.
+    ///
+    /// By default, these are the endpoint's fields. Endpoints that send their
+    /// fields in the request body instead override this.
+    fn query(&self) -> Vec<(String, String)> {
+        serde_urlencoded::to_string(self)
+            .ok()
+            .and_then(|q| serde_urlencoded::from_str(&q).ok())
+            .unwrap_or_default()
+    }
+}
 
+#[cfg_attr(not(feature = "playlists"), allow(dead_code))]
 pub(crate) trait PrivateEndpoint: Serialize {
     fn json(self) -> crate::client::Body<Self>
     where
@@ -30,11 +122,307 @@ pub(crate) trait PrivateEndpoint: Serialize {
 impl<T: Endpoint> PrivateEndpoint for T {}
 
 /// Builder for methods that get information from the API.
-pub struct Builder<'s, F: AuthFlow, V: Verifier, E: Endpoint> {
-    pub(crate) spotify: &'s mut Client<Token, F, V>,
+pub struct Builder<'s, F: AuthFlow, E: Endpoint> {
+    pub(crate) spotify: ClientRef<'s, F>,
     pub(crate) endpoint: E,
 }
 
+// The client a builder sends its request with, along with what's set on the builder to be
+// used instead of the client's own settings, such as the token set by `Builder::with_token`.
+pub(crate) struct ClientRef<'s, F: AuthFlow> {
+    client: &'s Client<Token, F>,
+    pub(crate) overrides: Overrides,
+}
+
+impl<'s, F: AuthFlow> ClientRef<'s, F> {
+    pub(crate) fn new(client: &'s Client<Token, F>) -> Self {
+        Self {
+            client,
+            overrides: Overrides::default(),
+        }
+    }
+
+    pub(crate) async fn request<P: Serialize, T: DeserializeOwned>(
+        &self,
+        method: Method,
+        endpoint: String,
+        query: Option<P>,
+        body: Option<Body<P>>,
+    ) -> SpotifyResult<T> {
+        self.client
+            .request_as(method, endpoint, query, body, &self.overrides)
+            .await
+    }
+
+    pub(crate) async fn get<P: Serialize, T: DeserializeOwned>(
+        &self,
+        endpoint: String,
+        query: impl Into<Option<P>>,
+    ) -> SpotifyResult<T> {
+        self.request(Method::GET, endpoint, query.into(), None)
+            .await
+    }
+
+    #[cfg_attr(not(feature = "playlists"), allow(dead_code))]
+    pub(crate) async fn post<P: Serialize, T: DeserializeOwned>(
+        &self,
+        endpoint: String,
+        body: impl Into<Option<Body<P>>>,
+    ) -> SpotifyResult<T> {
+        self.request(Method::POST, endpoint, None, body.into())
+            .await
+    }
+
+    #[cfg_attr(
+        not(any(feature = "player", feature = "playlists", feature = "user")),
+        allow(dead_code)
+    )]
+    pub(crate) async fn put<P: Serialize, T: DeserializeOwned>(
+        &self,
+        endpoint: String,
+        body: impl Into<Option<Body<P>>>,
+    ) -> SpotifyResult<T> {
+        self.request(Method::PUT, endpoint, None, body.into()).await
+    }
+
+    #[cfg_attr(not(any(feature = "playlists", feature = "user")), allow(dead_code))]
+    pub(crate) async fn delete<P: Serialize, T: DeserializeOwned>(
+        &self,
+        endpoint: String,
+        body: impl Into<Option<Body<P>>>,
+    ) -> SpotifyResult<T> {
+        self.request(Method::DELETE, endpoint, None, body.into())
+            .await
+    }
+}
+
+impl<F: AuthFlow> Deref for ClientRef<'_, F> {
+    type Target = Client<Token, F>;
+
+    fn deref(&self) -> &Self::Target {
+        self.client
+    }
+}
+
+impl<F: AuthFlow, E: Endpoint> Builder<'_, F, E> {
+    /// The path that will be requested, relative to the API's base URL,
+    /// e.g. `/albums/{id}`.
+    pub fn endpoint_path(&self) -> String {
+        self.endpoint.path()
+    }
+
+    /// The query parameters that will be sent with the request, in the order
+    /// they appear in the URL.
+    pub fn query_pairs(&self) -> Vec<(String, String)> {
+        self.endpoint.query()
+    }
+
+    /// Send the request with the given access token instead of the client's own, e.g. to
+    /// make requests on behalf of different users with a single client.
+    ///
+    /// The client's token isn't refreshed (or used) for this request, and the given token
+    /// only applies to this request. Refreshing it, if needed, is up to the caller.
+    ///
+    /// ```no_run
+    /// # async fn example(spotify: &spotify_rs::AuthCodeClient) -> spotify_rs::SpotifyResult<()> {
+    /// let saved_tracks = spotify
+    ///     .saved_tracks()
+    ///     .with_token("another_users_access_token")
+    ///     .get()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_token(mut self, access_token: impl Into<String>) -> Self {
+        self.spotify.overrides.token = Some(Secret::new(access_token.into()));
+        self
+    }
+
+    /// Send the request with the given retry policy instead of the client's own, e.g. to
+    /// retry an important request more times, or not at all with
+    /// [`max_retries(0)`](RetryPolicy::max_retries).
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.spotify.overrides.retry_policy = Some(policy);
+        self
+    }
+
+    /// Get the endpoint's response as raw JSON, along with its headers, e.g. to read
+    /// fields the models don't include yet.
+    ///
+    /// This always sends a single `GET` request, so it's only meant for builders that get
+    /// information, rather than ones that change it.
+    ///
+    /// ```no_run
+    /// # async fn example(spotify: &spotify_rs::ClientCredsClient) -> spotify_rs::SpotifyResult<()> {
+    /// let response = spotify.album("4aawyAB9vmqN3uQ7FjRGTy").get_raw().await?;
+    /// let popularity = response.body["popularity"].as_u64();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_raw(self) -> SpotifyResult<RawResponse> {
+        let query = self.endpoint.query();
+
+        self.spotify
+            .client
+            .request_raw(
+                Method::GET,
+                self.endpoint.path(),
+                Some(query),
+                None,
+                &self.spotify.overrides,
+            )
+            .await
+    }
+}
+
+impl<'s, F: AuthFlow + Send + Sync, E: Endpoint> Builder<'s, F, E> {
+    /// Send the request like awaiting the builder does, and also return the status and
+    /// headers of the response, e.g. to log Spotify's request ID or monitor rate limiting.
+    ///
+    /// For builders that send several requests, these are of the last one. They're `None`
+    /// if no request had to be sent, e.g. when getting tracks (or albums, artists, etc.)
+    /// by an empty list of IDs.
+    ///
+    /// ```no_run
+    /// # async fn example(spotify: &spotify_rs::ClientCredsClient) -> spotify_rs::SpotifyResult<()> {
+    /// let (album, meta) = spotify.album("4aawyAB9vmqN3uQ7FjRGTy").get_with_meta().await?;
+    ///
+    /// if let Some(request_id) = meta.as_ref().and_then(|m| m.request_id()) {
+    ///     println!("got {} (request {request_id})", album.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_with_meta<T>(mut self) -> SpotifyResult<(T, Option<ResponseMeta>)>
+    where
+        Self: IntoFuture<Output = SpotifyResult<T>>,
+    {
+        let meta = Arc::new(Mutex::new(None));
+        self.spotify.overrides.meta = Some(meta.clone());
+
+        let output = self.await?;
+        let meta = meta.lock().unwrap_or_else(PoisonError::into_inner).take();
+
+        Ok((output, meta))
+    }
+}
+
+impl<F: AuthFlow, E: Endpoint + Clone> Builder<'_, F, E> {
+    // Gets an endpoint that takes a comma-separated list of IDs, accessed through `ids`,
+    // with one request per chunk of at most `size` of them (see `chunked`).
+    pub(crate) async fn get_chunked<T: DeserializeOwned>(
+        self,
+        size: usize,
+        ids: impl Fn(&mut E) -> &mut String,
+    ) -> SpotifyResult<Vec<T>> {
+        let Self {
+            spotify,
+            mut endpoint,
+        } = self;
+
+        let all_ids = std::mem::take(ids(&mut endpoint));
+
+        // Spotify rejects an empty list, and there's nothing to get anyway.
+        if all_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let all_ids: Vec<_> = all_ids.split(',').collect();
+
+        chunked(&all_ids, size, |chunk| {
+            let mut endpoint = endpoint.clone();
+            *ids(&mut endpoint) = query_list(chunk);
+            spotify.get(endpoint.path(), endpoint)
+        })
+        .await
+    }
+
+    // Gets every page of the user's saved items, whose `limit` and `offset` are accessed
+    // through `page`, keeping the items added within `added`. The items are listed newest
+    // first, so this stops at the first one added before the range.
+    pub(crate) async fn get_all_saved<T: DeserializeOwned + SavedItem>(
+        self,
+        added: impl RangeBounds<DateTime<Utc>>,
+        page: impl Fn(&mut E) -> (&mut Option<Limit>, &mut Option<u32>),
+    ) -> SpotifyResult<Vec<T>> {
+        let Self {
+            spotify,
+            mut endpoint,
+        } = self;
+
+        let (limit, offset) = page(&mut endpoint);
+        *limit = Some(Limit::new(50));
+        let mut offset = offset.unwrap_or(0);
+
+        let mut items = Vec::new();
+
+        loop {
+            *page(&mut endpoint).1 = Some(offset);
+            let page: Page<T> = spotify.get(endpoint.path(), endpoint.clone()).await?;
+
+            let done = page.next.is_none() || page.items.is_empty();
+            offset += page.items.len() as u32;
+
+            for item in page.items {
+                let added_at = item.added_at();
+
+                let too_old = match added.start_bound() {
+                    Bound::Included(start) => added_at < *start,
+                    Bound::Excluded(start) => added_at <= *start,
+                    Bound::Unbounded => false,
+                };
+
+                if too_old {
+                    return Ok(items);
+                }
+
+                if added.contains(&added_at) {
+                    items.push(item);
+                }
+            }
+
+            if done {
+                return Ok(items);
+            }
+        }
+    }
+}
+
+/// An object that can only be requested by its ID one at a time,
+/// which can be fetched in bulk using [`Client::fetch_many`].
+pub trait FetchById: DeserializeOwned + private::Sealed {
+    #[doc(hidden)]
+    fn path(id: &str) -> String;
+}
+
+#[cfg(feature = "playlists")]
+impl FetchById for crate::model::playlist::Playlist {
+    fn path(id: &str) -> String {
+        format!("/playlists/{id}")
+    }
+}
+
+impl FetchById for User {
+    fn path(id: &str) -> String {
+        format!("/users/{id}")
+    }
+}
+
+impl FetchById for AudioAnalysis {
+    fn path(id: &str) -> String {
+        format!("/audio-analysis/{id}")
+    }
+}
+
+mod private {
+    pub trait Sealed {}
+
+    #[cfg(feature = "playlists")]
+    impl Sealed for crate::model::playlist::Playlist {}
+    impl Sealed for super::User {}
+    impl Sealed for super::AudioAnalysis {}
+}
+
 #[derive(Clone, Debug)]
 pub(crate) struct Limit<const MIN: u32 = 1, const MAX: u32 = 50>(u32);
 
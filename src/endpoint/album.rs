@@ -1,65 +1,106 @@
+use std::ops::RangeBounds;
+
+use chrono::{DateTime, Utc};
 use serde::Serialize;
 
 use crate::{
-    auth::{AuthFlow, Verifier},
+    auth::AuthFlow,
     error::Result,
+    market::Market,
     model::{
-        album::{Album, Albums, PagedAlbums, SavedAlbum, SimplifiedAlbum},
+        album::{Album, Albums, SavedAlbum},
         track::SimplifiedTrack,
         Page,
     },
 };
 
+#[cfg(feature = "browse")]
+use crate::model::album::{PagedAlbums, SimplifiedAlbum};
+
 use super::{Builder, Endpoint, Limit};
 
-impl Endpoint for AlbumEndpoint {}
-impl Endpoint for AlbumsEndpoint {}
-impl Endpoint for AlbumTracksEndpoint {}
-impl Endpoint for SavedAlbumsEndpoint {}
-impl Endpoint for NewReleasesEndpoint {}
+impl Endpoint for AlbumEndpoint {
+    fn path(&self) -> String {
+        format!("/albums/{}", self.id)
+    }
+}
+
+impl Endpoint for AlbumsEndpoint {
+    fn path(&self) -> String {
+        "/albums".to_owned()
+    }
+}
+
+impl Endpoint for AlbumTracksEndpoint {
+    fn path(&self) -> String {
+        format!("/albums/{}/tracks", self.id)
+    }
+}
+
+impl Endpoint for SavedAlbumsEndpoint {
+    fn path(&self) -> String {
+        "/me/albums".to_owned()
+    }
+}
+
+#[cfg(feature = "browse")]
+impl Endpoint for NewReleasesEndpoint {
+    fn path(&self) -> String {
+        "/browse/new-releases".to_owned()
+    }
+}
+
+impl_into_future! {
+    AlbumEndpoint => Album, get;
+    AlbumsEndpoint => Vec<Album>, get;
+    AlbumTracksEndpoint => Page<SimplifiedTrack>, get;
+    SavedAlbumsEndpoint => Page<SavedAlbum>, get;
+}
+
+#[cfg(feature = "browse")]
+impl_into_future! {
+    NewReleasesEndpoint => Page<SimplifiedAlbum>, get;
+}
 
 /// Endpoint for getting a single album.
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct AlbumEndpoint {
     #[serde(skip)]
     pub(crate) id: String,
-    pub(crate) market: Option<String>,
+    pub(crate) market: Option<Market>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, AlbumEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, AlbumEndpoint> {
     #[doc = include_str!("../docs/market.md")]
-    pub fn market(mut self, market: impl Into<String>) -> Self {
-        self.endpoint.market = Some(market.into());
+    pub fn market(mut self, market: Market) -> Self {
+        self.endpoint.market = Some(market);
         self
     }
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<Album> {
-        self.spotify
-            .get(format!("/albums/{}", self.endpoint.id), self.endpoint)
-            .await
+        self.spotify.get(self.endpoint.path(), self.endpoint).await
     }
 }
 
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct AlbumsEndpoint {
     pub(crate) ids: String,
-    pub(crate) market: Option<String>,
+    pub(crate) market: Option<Market>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, AlbumsEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, AlbumsEndpoint> {
     #[doc = include_str!("../docs/market.md")]
-    pub fn market(mut self, market: impl Into<String>) -> Self {
-        self.endpoint.market = Some(market.into());
+    pub fn market(mut self, market: Market) -> Self {
+        self.endpoint.market = Some(market);
         self
     }
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<Vec<Album>> {
-        self.spotify
-            .get("/albums".to_owned(), self.endpoint)
+        self.get_chunked(20, |e| &mut e.ids)
             .await
-            .map(|a: Albums| a.albums)
+            .map(|a: Vec<Albums>| a.into_iter().flat_map(|a| a.albums).collect())
     }
 }
 
@@ -67,15 +108,15 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, AlbumsEndpoint> {
 pub struct AlbumTracksEndpoint {
     #[serde(skip)]
     pub(crate) id: String,
-    pub(crate) market: Option<String>,
+    pub(crate) market: Option<Market>,
     pub(crate) limit: Option<Limit>,
     pub(crate) offset: Option<u32>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, AlbumTracksEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, AlbumTracksEndpoint> {
     #[doc = include_str!("../docs/market.md")]
-    pub fn market(mut self, market: impl Into<String>) -> Self {
-        self.endpoint.market = Some(market.into());
+    pub fn market(mut self, market: Market) -> Self {
+        self.endpoint.market = Some(market);
         self
     }
 
@@ -93,26 +134,21 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, AlbumTracksEndpoint> {
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<Page<SimplifiedTrack>> {
-        self.spotify
-            .get(
-                format!("/albums/{}/tracks", self.endpoint.id),
-                self.endpoint,
-            )
-            .await
+        self.spotify.get(self.endpoint.path(), self.endpoint).await
     }
 }
 
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct SavedAlbumsEndpoint {
-    pub(crate) market: Option<String>,
+    pub(crate) market: Option<Market>,
     pub(crate) limit: Option<Limit>,
     pub(crate) offset: Option<u32>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, SavedAlbumsEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, SavedAlbumsEndpoint> {
     #[doc = include_str!("../docs/market.md")]
-    pub fn market(mut self, market: impl Into<String>) -> Self {
-        self.endpoint.market = Some(market.into());
+    pub fn market(mut self, market: Market) -> Self {
+        self.endpoint.market = Some(market);
         self
     }
 
@@ -130,12 +166,29 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, SavedAlbumsEndpoint> {
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<Page<SavedAlbum>> {
-        self.spotify
-            .get("/me/albums".to_owned(), self.endpoint)
+        self.spotify.get(self.endpoint.path(), self.endpoint).await
+    }
+
+    /// Get all of the user's saved albums, going through all of the pages.
+    pub async fn get_all(self) -> Result<Vec<SavedAlbum>> {
+        self.get_all_added(..).await
+    }
+
+    /// Get all of the user's saved albums that were added within `range`, e.g. `since..`
+    /// for the ones added since a given time.
+    ///
+    /// Saved albums are listed newest first, so pages are only requested until the
+    /// first one added before the range.
+    pub async fn get_all_added(
+        self,
+        range: impl RangeBounds<DateTime<Utc>>,
+    ) -> Result<Vec<SavedAlbum>> {
+        self.get_all_saved(range, |e| (&mut e.limit, &mut e.offset))
             .await
     }
 }
 
+#[cfg(feature = "browse")]
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct NewReleasesEndpoint {
     pub(crate) country: Option<String>,
@@ -143,7 +196,8 @@ pub struct NewReleasesEndpoint {
     pub(crate) offset: Option<u32>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, NewReleasesEndpoint> {
+#[cfg(feature = "browse")]
+impl<F: AuthFlow> Builder<'_, F, NewReleasesEndpoint> {
     #[doc = include_str!("../docs/country.md")]
     pub fn country(mut self, country: impl Into<String>) -> Self {
         self.endpoint.country = Some(country.into());
@@ -165,7 +219,7 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, NewReleasesEndpoint> {
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<Page<SimplifiedAlbum>> {
         self.spotify
-            .get("/browse/new-releases".to_owned(), self.endpoint)
+            .get(self.endpoint.path(), self.endpoint)
             .await
             .map(|p: PagedAlbums| p.albums)
     }
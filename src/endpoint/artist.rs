@@ -1,8 +1,9 @@
 use serde::Serialize;
 
 use crate::{
-    auth::{AuthFlow, Verifier},
+    auth::AuthFlow,
     error::Result,
+    market::Market,
     model::{
         album::{AlbumGroup, SimplifiedAlbum},
         artist::{Artist, Artists},
@@ -14,52 +15,104 @@ use crate::{
 
 use super::{Builder, Endpoint, Limit};
 
-impl Endpoint for ArtistAlbumsEndpoint {}
-impl Endpoint for ArtistTopTracksEndpoint {}
-impl Endpoint for ArtistEndpoint {}
+impl Endpoint for ArtistsEndpoint {
+    fn path(&self) -> String {
+        "/artists".to_owned()
+    }
+}
+
+impl Endpoint for ArtistAlbumsEndpoint {
+    fn path(&self) -> String {
+        format!("/artists/{}/albums", self.id)
+    }
+}
+
+impl Endpoint for ArtistTopTracksEndpoint {
+    fn path(&self) -> String {
+        format!("/artists/{}/top-tracks", self.id)
+    }
+}
+
+impl Endpoint for ArtistRelatedArtistsEndpoint {
+    fn path(&self) -> String {
+        format!("/artists/{}/related-artists", self.id)
+    }
+}
+
+impl Endpoint for ArtistEndpoint {
+    fn path(&self) -> String {
+        format!("/artists/{}", self.id)
+    }
+}
+
+impl_into_future! {
+    ArtistEndpoint => Artist, get;
+    ArtistsEndpoint => Vec<Artist>, get;
+    ArtistAlbumsEndpoint => Page<SimplifiedAlbum>, get;
+    ArtistTopTracksEndpoint => Vec<Track>, get;
+    ArtistRelatedArtistsEndpoint => Vec<Artist>, get;
+}
 
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct ArtistEndpoint {
+    #[serde(skip)]
     pub(crate) id: String,
 }
 
-impl<'a, F: AuthFlow, V: Verifier> Builder<'a, F, V, ArtistEndpoint> {
-    pub fn albums(self) -> Builder<'a, F, V, ArtistAlbumsEndpoint> {
+impl<'a, F: AuthFlow> Builder<'a, F, ArtistEndpoint> {
+    pub fn albums(self) -> Builder<'a, F, ArtistAlbumsEndpoint> {
         Builder {
-            spotify: self.spotify,
             endpoint: ArtistAlbumsEndpoint {
                 id: self.endpoint.id,
+                market: self.spotify.default_market(),
+                limit: self.spotify.default_limit.map(Limit::new),
                 ..Default::default()
             },
+            spotify: self.spotify,
         }
     }
 
-    pub fn top_tracks(self) -> Builder<'a, F, V, ArtistTopTracksEndpoint> {
+    pub fn top_tracks(self) -> Builder<'a, F, ArtistTopTracksEndpoint> {
         Builder {
-            spotify: self.spotify,
             endpoint: ArtistTopTracksEndpoint {
                 id: self.endpoint.id,
-                market: None,
+                market: self.spotify.default_market(),
             },
+            spotify: self.spotify,
         }
     }
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<Artist> {
-        self.spotify
-            .get::<(), _>(format!("/artists/{}", self.endpoint.id), None)
-            .await
+        self.spotify.get::<(), _>(self.endpoint.path(), None).await
+    }
+
+    pub fn related_artists(self) -> Builder<'a, F, ArtistRelatedArtistsEndpoint> {
+        Builder {
+            endpoint: ArtistRelatedArtistsEndpoint {
+                id: self.endpoint.id,
+            },
+            spotify: self.spotify,
+        }
     }
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn get_related_artists(self) -> Result<Vec<Artist>> {
-        self.spotify
-            .get::<(), _>(
-                format!("/artists/{}/related-artists", self.endpoint.id),
-                None,
-            )
+        self.related_artists().get().await
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ArtistsEndpoint {
+    pub(crate) ids: String,
+}
+
+impl<F: AuthFlow> Builder<'_, F, ArtistsEndpoint> {
+    #[doc = include_str!("../docs/send.md")]
+    pub async fn get(self) -> Result<Vec<Artist>> {
+        self.get_chunked(50, |e| &mut e.ids)
             .await
-            .map(|a: Artists| a.artists)
+            .map(|a: Vec<Artists>| a.into_iter().flat_map(|a| a.artists).collect())
     }
 }
 
@@ -68,12 +121,12 @@ pub struct ArtistAlbumsEndpoint {
     #[serde(skip)]
     pub(crate) id: String,
     pub(crate) include_groups: Option<String>,
-    pub(crate) market: Option<String>,
+    pub(crate) market: Option<Market>,
     pub(crate) limit: Option<Limit>,
     pub(crate) offset: Option<u32>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, ArtistAlbumsEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, ArtistAlbumsEndpoint> {
     /// Sets the album types to be returned. If not supplied all album types will be returned.
     pub fn include_groups(mut self, include_groups: &[AlbumGroup]) -> Self {
         self.endpoint.include_groups = Some(query_list(include_groups));
@@ -81,8 +134,8 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, ArtistAlbumsEndpoint> {
     }
 
     #[doc = include_str!("../docs/market.md")]
-    pub fn market(mut self, market: impl Into<String>) -> Self {
-        self.endpoint.market = Some(market.into());
+    pub fn market(mut self, market: Market) -> Self {
+        self.endpoint.market = Some(market);
         self
     }
 
@@ -100,12 +153,7 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, ArtistAlbumsEndpoint> {
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<Page<SimplifiedAlbum>> {
-        self.spotify
-            .get(
-                format!("/artists/{}/albums", self.endpoint.id),
-                self.endpoint,
-            )
-            .await
+        self.spotify.get(self.endpoint.path(), self.endpoint).await
     }
 }
 
@@ -113,24 +161,37 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, ArtistAlbumsEndpoint> {
 pub struct ArtistTopTracksEndpoint {
     #[serde(skip)]
     pub(crate) id: String,
-    pub(crate) market: Option<String>,
+    pub(crate) market: Option<Market>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, ArtistTopTracksEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, ArtistTopTracksEndpoint> {
     #[doc = include_str!("../docs/market.md")]
-    pub fn market(mut self, market: impl Into<String>) -> Self {
-        self.endpoint.market = Some(market.into());
+    pub fn market(mut self, market: Market) -> Self {
+        self.endpoint.market = Some(market);
         self
     }
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<Vec<Track>> {
         self.spotify
-            .get(
-                format!("/artists/{}/top-tracks", self.endpoint.id),
-                self.endpoint,
-            )
+            .get(self.endpoint.path(), self.endpoint)
             .await
             .map(|t: Tracks| t.tracks)
     }
 }
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ArtistRelatedArtistsEndpoint {
+    #[serde(skip)]
+    pub(crate) id: String,
+}
+
+impl<F: AuthFlow> Builder<'_, F, ArtistRelatedArtistsEndpoint> {
+    #[doc = include_str!("../docs/send.md")]
+    pub async fn get(self) -> Result<Vec<Artist>> {
+        self.spotify
+            .get::<(), _>(self.endpoint.path(), None)
+            .await
+            .map(|a: Artists| a.artists)
+    }
+}
@@ -1,8 +1,9 @@
 use serde::Serialize;
 
 use crate::{
-    auth::{AuthFlow, Verifier},
+    auth::AuthFlow,
     error::Result,
+    market::Market,
     model::{
         audiobook::{
             Audiobook, Audiobooks, Chapter, Chapters, SimplifiedAudiobook, SimplifiedChapter,
@@ -13,54 +14,89 @@ use crate::{
 
 use super::{Builder, Endpoint, Limit};
 
-impl Endpoint for AudiobookEndpoint {}
-impl Endpoint for AudiobooksEndpoint {}
-impl Endpoint for AudiobookChaptersEndpoint {}
-impl Endpoint for SavedAudiobooksEndpoint {}
-impl Endpoint for ChapterEndpoint {}
-impl Endpoint for ChaptersEndpoint {}
+impl Endpoint for AudiobookEndpoint {
+    fn path(&self) -> String {
+        format!("/audiobooks/{}", self.id)
+    }
+}
+
+impl Endpoint for AudiobooksEndpoint {
+    fn path(&self) -> String {
+        "/audiobooks".to_owned()
+    }
+}
+
+impl Endpoint for AudiobookChaptersEndpoint {
+    fn path(&self) -> String {
+        format!("/audiobooks/{}/chapters", self.id)
+    }
+}
+
+impl Endpoint for SavedAudiobooksEndpoint {
+    fn path(&self) -> String {
+        "/me/audiobooks".to_owned()
+    }
+}
+
+impl Endpoint for ChapterEndpoint {
+    fn path(&self) -> String {
+        format!("/chapters/{}", self.id)
+    }
+}
+
+impl Endpoint for ChaptersEndpoint {
+    fn path(&self) -> String {
+        "/chapters/".to_owned()
+    }
+}
+
+impl_into_future! {
+    AudiobookEndpoint => Audiobook, get;
+    AudiobooksEndpoint => Vec<Audiobook>, get;
+    AudiobookChaptersEndpoint => Page<SimplifiedChapter>, get;
+    SavedAudiobooksEndpoint => Page<SimplifiedAudiobook>, get;
+    ChapterEndpoint => Chapter, get;
+    ChaptersEndpoint => Vec<Chapter>, get;
+}
 
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct AudiobookEndpoint {
     #[serde(skip)]
     pub(crate) id: String,
-    pub(crate) market: Option<String>,
+    pub(crate) market: Option<Market>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, AudiobookEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, AudiobookEndpoint> {
     #[doc = include_str!("../docs/market.md")]
-    pub fn market(mut self, market: impl Into<String>) -> Self {
-        self.endpoint.market = Some(market.into());
+    pub fn market(mut self, market: Market) -> Self {
+        self.endpoint.market = Some(market);
         self
     }
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<Audiobook> {
-        self.spotify
-            .get(format!("/audiobooks/{}", self.endpoint.id), self.endpoint)
-            .await
+        self.spotify.get(self.endpoint.path(), self.endpoint).await
     }
 }
 
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct AudiobooksEndpoint {
     pub(crate) ids: String,
-    pub(crate) market: Option<String>,
+    pub(crate) market: Option<Market>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, AudiobooksEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, AudiobooksEndpoint> {
     #[doc = include_str!("../docs/market.md")]
-    pub fn market(mut self, market: impl Into<String>) -> Self {
-        self.endpoint.market = Some(market.into());
+    pub fn market(mut self, market: Market) -> Self {
+        self.endpoint.market = Some(market);
         self
     }
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<Vec<Audiobook>> {
-        self.spotify
-            .get("/audiobooks".to_owned(), self.endpoint)
+        self.get_chunked(50, |e| &mut e.ids)
             .await
-            .map(|a: Audiobooks| a.audiobooks)
+            .map(|a: Vec<Audiobooks>| a.into_iter().flat_map(|a| a.audiobooks).collect())
     }
 }
 
@@ -68,15 +104,15 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, AudiobooksEndpoint> {
 pub struct AudiobookChaptersEndpoint {
     #[serde(skip)]
     pub(crate) id: String,
-    pub(crate) market: Option<String>,
+    pub(crate) market: Option<Market>,
     pub(crate) limit: Option<Limit>,
     pub(crate) offset: Option<u32>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, AudiobookChaptersEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, AudiobookChaptersEndpoint> {
     #[doc = include_str!("../docs/market.md")]
-    pub fn market(mut self, market: impl Into<String>) -> Self {
-        self.endpoint.market = Some(market.into());
+    pub fn market(mut self, market: Market) -> Self {
+        self.endpoint.market = Some(market);
         self
     }
 
@@ -94,12 +130,7 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, AudiobookChaptersEndpoint> {
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<Page<SimplifiedChapter>> {
-        self.spotify
-            .get(
-                format!("/audiobooks/{}/chapters", self.endpoint.id),
-                self.endpoint,
-            )
-            .await
+        self.spotify.get(self.endpoint.path(), self.endpoint).await
     }
 }
 
@@ -109,7 +140,7 @@ pub struct SavedAudiobooksEndpoint {
     pub(crate) offset: Option<u32>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, SavedAudiobooksEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, SavedAudiobooksEndpoint> {
     #[doc = include_str!("../docs/limit.md")]
     pub fn limit(mut self, limit: u32) -> Self {
         self.endpoint.limit = Some(Limit::new(limit));
@@ -127,7 +158,7 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, SavedAudiobooksEndpoint> {
         // The map is required because the page's items might contain null (for some reason),
         // so this filters out the nulls.
         self.spotify
-            .get("/me/audiobooks".to_owned(), self.endpoint)
+            .get(self.endpoint.path(), self.endpoint)
             .await
             .map(|p: Page<Option<SimplifiedAudiobook>>| Page {
                 href: p.href,
@@ -145,42 +176,39 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, SavedAudiobooksEndpoint> {
 pub struct ChapterEndpoint {
     #[serde(skip)]
     pub(crate) id: String,
-    pub(crate) market: Option<String>,
+    pub(crate) market: Option<Market>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, ChapterEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, ChapterEndpoint> {
     #[doc = include_str!("../docs/market.md")]
-    pub fn market(mut self, market: impl Into<String>) -> Self {
-        self.endpoint.market = Some(market.into());
+    pub fn market(mut self, market: Market) -> Self {
+        self.endpoint.market = Some(market);
         self
     }
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<Chapter> {
-        self.spotify
-            .get(format!("/chapters/{}", self.endpoint.id), self.endpoint)
-            .await
+        self.spotify.get(self.endpoint.path(), self.endpoint).await
     }
 }
 
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct ChaptersEndpoint {
     pub(crate) ids: String,
-    pub(crate) market: Option<String>,
+    pub(crate) market: Option<Market>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, ChaptersEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, ChaptersEndpoint> {
     #[doc = include_str!("../docs/market.md")]
-    pub fn market(mut self, market: impl Into<String>) -> Self {
-        self.endpoint.market = Some(market.into());
+    pub fn market(mut self, market: Market) -> Self {
+        self.endpoint.market = Some(market);
         self
     }
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<Vec<Chapter>> {
-        self.spotify
-            .get("/chapters/".to_owned(), self.endpoint)
+        self.get_chunked(50, |e| &mut e.ids)
             .await
-            .map(|c: Chapters| c.chapters)
+            .map(|c: Vec<Chapters>| c.into_iter().flat_map(|c| c.chapters).collect())
     }
 }
@@ -1,7 +1,7 @@
 use serde::Serialize;
 
 use crate::{
-    auth::{AuthFlow, Verifier},
+    auth::AuthFlow,
     error::Result,
     model::{
         category::{Categories, Category},
@@ -11,8 +11,22 @@ use crate::{
 
 use super::{Builder, Endpoint, Limit};
 
-impl Endpoint for BrowseCategoryEndpoint {}
-impl Endpoint for BrowseCategoriesEndpoint {}
+impl Endpoint for BrowseCategoryEndpoint {
+    fn path(&self) -> String {
+        format!("/browse/categories/{}", self.id)
+    }
+}
+
+impl Endpoint for BrowseCategoriesEndpoint {
+    fn path(&self) -> String {
+        "/browse/categories".to_owned()
+    }
+}
+
+impl_into_future! {
+    BrowseCategoryEndpoint => Category, get;
+    BrowseCategoriesEndpoint => Page<Category>, get;
+}
 
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct BrowseCategoryEndpoint {
@@ -22,7 +36,7 @@ pub struct BrowseCategoryEndpoint {
     pub(crate) locale: Option<String>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, BrowseCategoryEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, BrowseCategoryEndpoint> {
     #[doc = include_str!("../docs/country.md")]
     pub fn country(mut self, country: impl Into<String>) -> Self {
         self.endpoint.country = Some(country.into());
@@ -37,12 +51,7 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, BrowseCategoryEndpoint> {
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<Category> {
-        self.spotify
-            .get(
-                format!("/browse/categories/{}", self.endpoint.id),
-                self.endpoint,
-            )
-            .await
+        self.spotify.get(self.endpoint.path(), self.endpoint).await
     }
 }
 
@@ -54,7 +63,7 @@ pub struct BrowseCategoriesEndpoint {
     pub(crate) offset: Option<u32>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, BrowseCategoriesEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, BrowseCategoriesEndpoint> {
     #[doc = include_str!("../docs/country.md")]
     pub fn country(mut self, country: impl Into<String>) -> Self {
         self.endpoint.country = Some(country.into());
@@ -82,7 +91,7 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, BrowseCategoriesEndpoint> {
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<Page<Category>> {
         self.spotify
-            .get("/browse/categories".to_owned(), self.endpoint)
+            .get(self.endpoint.path(), self.endpoint)
             .await
             .map(|c: Categories| c.categories)
     }
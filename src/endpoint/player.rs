@@ -1,27 +1,101 @@
-use std::marker::PhantomData;
+use std::{future::Future, marker::PhantomData, time::Duration};
 
+use chrono::{DateTime, Utc};
+use futures_util::{stream, Stream};
 use reqwest::Method;
 use serde::Serialize;
 use serde_json::{json, Value};
 
 use crate::{
-    auth::{AuthFlow, Verifier},
+    auth::AuthFlow,
     client::Body,
-    error::Result,
-    model::{player::PlayHistory, CursorPage},
+    error::{Error, Result},
+    id::{AlbumId, ArtistId, PlaylistId, TrackId},
+    model::{
+        player::{Device, Devices, PlayHistory, Queue},
+        CursorPage,
+    },
     Nil,
 };
 
 use super::{Builder, Endpoint};
 
-impl Endpoint for TransferPlaybackEndpoint {}
-impl Endpoint for StartPlaybackEndpoint {}
-impl Endpoint for SeekToPositionEndpoint {}
-impl Endpoint for SetRepeatModeEndpoint {}
-impl Endpoint for SetPlaybackVolumeEndpoint {}
-impl Endpoint for ToggleShuffleEndpoint {}
-impl<T: TimestampMarker> Endpoint for RecentlyPlayedTracksEndpoint<T> {}
-impl Endpoint for AddItemToQueueEndpoint {}
+impl Endpoint for TransferPlaybackEndpoint {
+    fn path(&self) -> String {
+        "/me/player".to_owned()
+    }
+
+    fn query(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
+}
+
+impl Endpoint for StartPlaybackEndpoint {
+    fn path(&self) -> String {
+        "/me/player/play".to_owned()
+    }
+
+    fn query(&self) -> Vec<(String, String)> {
+        self.device_id
+            .iter()
+            .map(|id| ("device_id".to_owned(), id.clone()))
+            .collect()
+    }
+}
+
+impl Endpoint for SeekToPositionEndpoint {
+    fn path(&self) -> String {
+        "/me/player/seek".to_owned()
+    }
+}
+
+impl Endpoint for SetRepeatModeEndpoint {
+    fn path(&self) -> String {
+        "/me/player/repeat".to_owned()
+    }
+}
+
+impl Endpoint for SetPlaybackVolumeEndpoint {
+    fn path(&self) -> String {
+        "/me/player/volume".to_owned()
+    }
+}
+
+impl Endpoint for ToggleShuffleEndpoint {
+    fn path(&self) -> String {
+        "/me/player/shuffle".to_owned()
+    }
+}
+
+impl<T: TimestampMarker> Endpoint for RecentlyPlayedTracksEndpoint<T> {
+    fn path(&self) -> String {
+        "/me/player/recently-played".to_owned()
+    }
+}
+
+impl Endpoint for AddItemToQueueEndpoint {
+    fn path(&self) -> String {
+        "/me/player/queue".to_owned()
+    }
+}
+
+impl Endpoint for AddItemsToQueueEndpoint {
+    fn path(&self) -> String {
+        "/me/player/queue".to_owned()
+    }
+}
+
+impl_into_future! {
+    TransferPlaybackEndpoint => Nil, send;
+    StartPlaybackEndpoint => Nil, send;
+    SeekToPositionEndpoint => Nil, send;
+    SetRepeatModeEndpoint => Nil, send;
+    SetPlaybackVolumeEndpoint => Nil, send;
+    ToggleShuffleEndpoint => Nil, send;
+    [T: TimestampMarker + Send + 's,] RecentlyPlayedTracksEndpoint<T> => CursorPage<PlayHistory>, get;
+    AddItemToQueueEndpoint => Nil, send;
+    AddItemsToQueueEndpoint => Nil, send;
+}
 
 #[derive(Clone, Copy, Debug, Default, Serialize)]
 #[serde(rename_all = "snake_case")]
@@ -56,13 +130,40 @@ pub struct Before;
 #[derive(Clone, Copy, Debug, Default)]
 pub struct Unspecified;
 
+/// A device that playback can be transferred to: its ID, or a [`Device`] returned by
+/// the API.
+pub trait AsDeviceId {
+    /// The device's ID, if it has one.
+    fn device_id(&self) -> Option<&str>;
+}
+
+impl<S: AsRef<str> + ?Sized> AsDeviceId for S {
+    fn device_id(&self) -> Option<&str> {
+        Some(self.as_ref())
+    }
+}
+
+impl AsDeviceId for Device {
+    fn device_id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+}
+
+impl AsDeviceId for &Device {
+    fn device_id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+}
+
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct TransferPlaybackEndpoint {
     pub(crate) device_ids: Vec<String>,
     pub(crate) play: Option<bool>,
+    #[serde(skip)]
+    pub(crate) check_available: bool,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, TransferPlaybackEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, TransferPlaybackEndpoint> {
     /// If `true`, ensure playback happens on the new device.
     /// Otherwise, keep the current playback state.
     pub fn play(mut self, play: bool) -> Self {
@@ -70,14 +171,83 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, TransferPlaybackEndpoint> {
         self
     }
 
+    /// Check that the device is one of the user's available devices before transferring
+    /// playback to it, which takes an extra request.
+    ///
+    /// Otherwise, transferring playback to an unavailable device fails with a `404` from
+    /// Spotify.
+    pub fn check_available(mut self) -> Self {
+        self.endpoint.check_available = true;
+        self
+    }
+
     #[doc = include_str!("../docs/send.md")]
     pub async fn send(self) -> Result<Nil> {
+        let Some(device_id) = self.endpoint.device_ids.first() else {
+            return Err(Error::DeviceUnavailable("it has no ID".to_owned()));
+        };
+
+        if self.endpoint.check_available {
+            let devices: Devices = self
+                .spotify
+                .get::<(), _>("/me/player/devices".to_owned(), None)
+                .await?;
+
+            if !devices
+                .devices
+                .iter()
+                .any(|d| d.id.as_ref() == Some(device_id))
+            {
+                return Err(Error::DeviceUnavailable(format!(
+                    "`{device_id}` isn't one of the user's available devices"
+                )));
+            }
+        }
+
         self.spotify
-            .put("/me/player".to_owned(), Body::Json(self.endpoint))
+            .put(self.endpoint.path(), Body::Json(self.endpoint))
             .await
     }
 }
 
+/// A context whose items can be played, for [`context`](Builder::context).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PlayContext {
+    Album(AlbumId),
+    Playlist(PlaylistId),
+    /// An artist, whose top tracks are played.
+    Artist(ArtistId),
+}
+
+impl PlayContext {
+    /// The context's URI, e.g. `spotify:album:{id}`.
+    pub fn uri(&self) -> String {
+        match self {
+            Self::Album(id) => id.uri(),
+            Self::Playlist(id) => id.uri(),
+            Self::Artist(id) => id.uri(),
+        }
+    }
+}
+
+impl From<AlbumId> for PlayContext {
+    fn from(id: AlbumId) -> Self {
+        Self::Album(id)
+    }
+}
+
+impl From<PlaylistId> for PlayContext {
+    fn from(id: PlaylistId) -> Self {
+        Self::Playlist(id)
+    }
+}
+
+impl From<ArtistId> for PlayContext {
+    fn from(id: ArtistId) -> Self {
+        Self::Artist(id)
+    }
+}
+
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct StartPlaybackEndpoint {
     #[serde(skip)]
@@ -88,10 +258,10 @@ pub struct StartPlaybackEndpoint {
     pub(crate) position_ms: Option<u32>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, StartPlaybackEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, StartPlaybackEndpoint> {
     #[doc = include_str!("../docs/device_id.md")]
     pub fn device_id(mut self, device_id: impl Into<String>) -> Self {
-        self.endpoint.device_id = Some(format!("?device_id={}", device_id.into()));
+        self.endpoint.device_id = Some(device_id.into());
         self
     }
 
@@ -101,6 +271,27 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, StartPlaybackEndpoint> {
         self
     }
 
+    /// The context to play, e.g. an album.
+    ///
+    /// ```no_run
+    /// use spotify_rs::{endpoint::player::PlayContext, id::{AlbumId, TrackId}};
+    ///
+    /// # async fn example(spotify: &spotify_rs::AuthCodeClient) -> spotify_rs::SpotifyResult<()> {
+    /// spotify
+    ///     .start_playback()
+    ///     .context(PlayContext::Album(AlbumId::from_id("4aawyAB9vmqN3uQ7FjRGTy")?))
+    ///     .offset_uri(&TrackId::from_id("6rqhFgbbKwnb9MLmUQDhG6")?)
+    ///     .position_ms(30_000)
+    ///     .send()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn context(mut self, context: impl Into<PlayContext>) -> Self {
+        self.endpoint.context_uri = Some(context.into().uri());
+        self
+    }
+
     /// The *URI*s of the tracks to play.
     pub fn uris(mut self, uris: &[&str]) -> Self {
         self.endpoint.uris = Some(uris.iter().map(ToString::to_string).collect());
@@ -113,6 +304,17 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, StartPlaybackEndpoint> {
         self
     }
 
+    /// Start playing the context from the item at this position, counting from 0.
+    pub fn offset_position(self, position: u32) -> Self {
+        self.offset(position)
+    }
+
+    /// Start playing the context from this track.
+    pub fn offset_uri(mut self, track: &TrackId) -> Self {
+        self.endpoint.offset = Some(json!({ "uri": track.uri() }));
+        self
+    }
+
     /// The position at which to start/resume the playback.
     pub fn position_ms(mut self, position_ms: u32) -> Self {
         self.endpoint.position_ms = Some(position_ms);
@@ -121,14 +323,12 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, StartPlaybackEndpoint> {
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn send(self) -> Result<Nil> {
-        let device_id = self.endpoint.device_id.as_deref().unwrap_or("");
+        let path = match &self.endpoint.device_id {
+            Some(device_id) => format!("{}?device_id={device_id}", self.endpoint.path()),
+            None => self.endpoint.path(),
+        };
 
-        self.spotify
-            .put(
-                format!("/me/player/play{}", device_id),
-                Body::Json(self.endpoint),
-            )
-            .await
+        self.spotify.put(path, Body::Json(self.endpoint)).await
     }
 }
 
@@ -138,7 +338,7 @@ pub struct SeekToPositionEndpoint {
     pub(crate) device_id: Option<String>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, SeekToPositionEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, SeekToPositionEndpoint> {
     #[doc = include_str!("../docs/device_id.md")]
     pub fn device_id(mut self, device_id: impl Into<String>) -> Self {
         self.endpoint.device_id = Some(device_id.into());
@@ -150,7 +350,7 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, SeekToPositionEndpoint> {
         self.spotify
             .request(
                 Method::PUT,
-                "/me/player/seek".to_owned(),
+                self.endpoint.path(),
                 self.endpoint.into(),
                 None,
             )
@@ -164,7 +364,7 @@ pub struct SetRepeatModeEndpoint {
     pub(crate) device_id: Option<String>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, SetRepeatModeEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, SetRepeatModeEndpoint> {
     #[doc = include_str!("../docs/device_id.md")]
     pub fn device_id(mut self, device_id: impl Into<String>) -> Self {
         self.endpoint.device_id = Some(device_id.into());
@@ -176,7 +376,7 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, SetRepeatModeEndpoint> {
         self.spotify
             .request(
                 Method::PUT,
-                "/me/player/repeat".to_owned(),
+                self.endpoint.path(),
                 self.endpoint.into(),
                 None,
             )
@@ -190,7 +390,7 @@ pub struct SetPlaybackVolumeEndpoint {
     pub(crate) device_id: Option<String>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, SetPlaybackVolumeEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, SetPlaybackVolumeEndpoint> {
     #[doc = include_str!("../docs/device_id.md")]
     pub fn device_id(mut self, device_id: impl Into<String>) -> Self {
         self.endpoint.device_id = Some(device_id.into());
@@ -202,7 +402,7 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, SetPlaybackVolumeEndpoint> {
         self.spotify
             .request(
                 Method::PUT,
-                "/me/player/volume".to_owned(),
+                self.endpoint.path(),
                 self.endpoint.into(),
                 None,
             )
@@ -216,7 +416,7 @@ pub struct ToggleShuffleEndpoint {
     pub(crate) device_id: Option<String>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, ToggleShuffleEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, ToggleShuffleEndpoint> {
     #[doc = include_str!("../docs/device_id.md")]
     pub fn device_id(mut self, device_id: impl Into<String>) -> Self {
         self.endpoint.device_id = Some(device_id.into());
@@ -228,7 +428,7 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, ToggleShuffleEndpoint> {
         self.spotify
             .request(
                 Method::PUT,
-                "/me/player/shuffle".to_owned(),
+                self.endpoint.path(),
                 self.endpoint.into(),
                 None,
             )
@@ -241,12 +441,12 @@ pub struct RecentlyPlayedTracksEndpoint<T: TimestampMarker = Unspecified> {
     pub(crate) limit: Option<u32>,
     pub(crate) after: Option<u64>,
     pub(crate) before: Option<u64>,
-    marker: PhantomData<T>,
+    pub(crate) marker: PhantomData<T>,
 }
 
-impl<'a, F: AuthFlow, V: Verifier> Builder<'a, F, V, RecentlyPlayedTracksEndpoint<Unspecified>> {
+impl<'a, F: AuthFlow> Builder<'a, F, RecentlyPlayedTracksEndpoint<Unspecified>> {
     /// A Unix timestamp in miliseconds. Returns all items after (but not including) this cursor position.
-    pub fn after(self, after: u64) -> Builder<'a, F, V, RecentlyPlayedTracksEndpoint<After>> {
+    pub fn after(self, after: u64) -> Builder<'a, F, RecentlyPlayedTracksEndpoint<After>> {
         Builder {
             spotify: self.spotify,
             endpoint: RecentlyPlayedTracksEndpoint {
@@ -259,7 +459,7 @@ impl<'a, F: AuthFlow, V: Verifier> Builder<'a, F, V, RecentlyPlayedTracksEndpoin
     }
 
     /// A Unix timestamp in miliseconds. Returns all items before (but not including) this cursor position.
-    pub fn before(self, before: u64) -> Builder<'a, F, V, RecentlyPlayedTracksEndpoint<Before>> {
+    pub fn before(self, before: u64) -> Builder<'a, F, RecentlyPlayedTracksEndpoint<Before>> {
         Builder {
             spotify: self.spotify,
             endpoint: RecentlyPlayedTracksEndpoint {
@@ -270,11 +470,30 @@ impl<'a, F: AuthFlow, V: Verifier> Builder<'a, F, V, RecentlyPlayedTracksEndpoin
             },
         }
     }
+
+    /// Returns all items played after (but not including) the given time.
+    pub fn played_after(
+        self,
+        after: DateTime<Utc>,
+    ) -> Builder<'a, F, RecentlyPlayedTracksEndpoint<After>> {
+        self.after(timestamp_ms(after))
+    }
+
+    /// Returns all items played before (but not including) the given time.
+    pub fn played_before(
+        self,
+        before: DateTime<Utc>,
+    ) -> Builder<'a, F, RecentlyPlayedTracksEndpoint<Before>> {
+        self.before(timestamp_ms(before))
+    }
+}
+
+// The cursors are Unix timestamps in milliseconds, which times before 1970 can't be.
+fn timestamp_ms(time: DateTime<Utc>) -> u64 {
+    time.timestamp_millis().max(0) as u64
 }
 
-impl<F: AuthFlow, V: Verifier, T: TimestampMarker>
-    Builder<'_, F, V, RecentlyPlayedTracksEndpoint<T>>
-{
+impl<'a, F: AuthFlow, T: TimestampMarker> Builder<'a, F, RecentlyPlayedTracksEndpoint<T>> {
     #[doc = include_str!("../docs/limit.md")]
     pub fn limit(mut self, limit: u32) -> Self {
         self.endpoint.limit = Some(limit);
@@ -283,9 +502,72 @@ impl<F: AuthFlow, V: Verifier, T: TimestampMarker>
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<CursorPage<PlayHistory>> {
-        self.spotify
-            .get("/me/player/recently-played".to_owned(), self.endpoint)
-            .await
+        self.spotify.get(self.endpoint.path(), self.endpoint).await
+    }
+
+    /// Get every page of the user's history, one request at a time, by following the
+    /// cursors of each page.
+    ///
+    /// Without a cursor, or with [`before`](Builder::before), the pages go back in time
+    /// from the most recent (or given) time. With [`after`](Builder::after), they go
+    /// forward from the given time. The stream ends after the last page, or after an error.
+    ///
+    /// ```no_run
+    /// use futures_util::StreamExt;
+    ///
+    /// # async fn example(spotify: &spotify_rs::AuthCodeClient) -> spotify_rs::SpotifyResult<()> {
+    /// let mut pages = Box::pin(spotify.recently_played_tracks().limit(50).stream());
+    ///
+    /// while let Some(page) = pages.next().await {
+    ///     for item in page?.items {
+    ///         println!("{}: {}", item.played_at, item.track.name);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stream(self) -> impl Stream<Item = Result<CursorPage<PlayHistory>>> + 'a
+    where
+        T: 'a,
+    {
+        let forward = self.endpoint.after.is_some();
+
+        stream::unfold(
+            (self.spotify, Some(self.endpoint)),
+            move |(spotify, endpoint)| async move {
+                let endpoint = endpoint?;
+                let result: Result<CursorPage<PlayHistory>> =
+                    spotify.get(endpoint.path(), &endpoint).await;
+
+                let page = match result {
+                    Ok(page) => page,
+                    Err(err) => return Some((Err(err), (spotify, None))),
+                };
+
+                let cursor = if forward {
+                    page.cursors.after.as_deref()
+                } else {
+                    page.cursors.before.as_deref()
+                };
+
+                let next = match cursor.and_then(|c| c.parse().ok()) {
+                    Some(cursor) if page.next.is_some() && !page.items.is_empty() => {
+                        let mut next = endpoint;
+
+                        if forward {
+                            next.after = Some(cursor);
+                        } else {
+                            next.before = Some(cursor);
+                        }
+
+                        Some(next)
+                    }
+                    _ => None,
+                };
+
+                Some((Ok(page), (spotify, next)))
+            },
+        )
     }
 }
 
@@ -295,7 +577,16 @@ pub struct AddItemToQueueEndpoint {
     pub(crate) device_id: Option<String>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, AddItemToQueueEndpoint> {
+/// Whether [`send_unless_queued`](Builder::send_unless_queued) added the item to the queue.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QueueOutcome {
+    /// The item was added to the queue.
+    Queued,
+    /// The item was already in the queue or currently playing, so it wasn't added again.
+    AlreadyQueued,
+}
+
+impl<F: AuthFlow> Builder<'_, F, AddItemToQueueEndpoint> {
     #[doc = include_str!("../docs/device_id.md")]
     pub fn device_id(mut self, device_id: impl Into<String>) -> Self {
         self.endpoint.device_id = Some(device_id.into());
@@ -307,10 +598,102 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, AddItemToQueueEndpoint> {
         self.spotify
             .request(
                 Method::POST,
-                "/me/player/queue".to_owned(),
+                self.endpoint.path(),
                 self.endpoint.into(),
                 None,
             )
             .await
     }
+
+    /// Like [`send`](Self::send), but check the user's queue first, and don't add the
+    /// item if it's already queued or currently playing.
+    ///
+    /// Spotify's queue also lists the upcoming items of the current context (e.g. the
+    /// rest of the playlist that's playing), without saying which items were queued by
+    /// the user, so an item that's merely coming up next in the context isn't added either.
+    ///
+    /// This takes an extra request, and can't prevent duplicates added by
+    /// other clients at the same time.
+    pub async fn send_unless_queued(self) -> Result<QueueOutcome> {
+        // The queue is checked with the same token as the one it's added with.
+        let queue: Queue = self
+            .spotify
+            .get::<(), _>("/me/player/queue".to_owned(), None)
+            .await?;
+
+        if queue.contains(&self.endpoint.uri) {
+            return Ok(QueueOutcome::AlreadyQueued);
+        }
+
+        self.send().await.map(|_| QueueOutcome::Queued)
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct AddItemsToQueueEndpoint {
+    #[serde(skip)]
+    pub(crate) uris: Vec<String>,
+    pub(crate) device_id: Option<String>,
+}
+
+impl<F: AuthFlow> Builder<'_, F, AddItemsToQueueEndpoint> {
+    #[doc = include_str!("../docs/device_id.md")]
+    pub fn device_id(mut self, device_id: impl Into<String>) -> Self {
+        self.endpoint.device_id = Some(device_id.into());
+        self
+    }
+
+    /// Add the items to the queue one after the other, in order.
+    ///
+    /// If adding an item fails, the ones after it aren't added, and
+    /// [`Error::PartiallyQueued`] says how many were.
+    pub async fn send(self) -> Result<Nil> {
+        self.send_with_delay(Duration::ZERO, |_| std::future::ready(()))
+            .await
+    }
+
+    /// Like [`send`](Self::send), but waits `delay` between the items, using `sleep`
+    /// (e.g. `tokio::time::sleep`), so that Spotify keeps them in order, and to stay
+    /// clear of the rate limit when queueing many items.
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// # async fn sleep(_: Duration) {}
+    ///
+    /// # async fn example(spotify: &spotify_rs::AuthCodeClient) -> spotify_rs::SpotifyResult<()> {
+    /// let tracks = ["spotify:track:6rqhFgbbKwnb9MLmUQDhG6", "spotify:track:4iV5W9uYEdYUVa79Axb7Rh"];
+    ///
+    /// spotify
+    ///     .add_items_to_queue(&tracks)
+    ///     .send_with_delay(Duration::from_millis(250), sleep)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn send_with_delay<S, Fut>(self, delay: Duration, sleep: S) -> Result<Nil>
+    where
+        S: Fn(Duration) -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        for (queued, uri) in self.endpoint.uris.iter().enumerate() {
+            if queued > 0 && !delay.is_zero() {
+                sleep(delay).await;
+            }
+
+            let item = AddItemToQueueEndpoint {
+                uri: uri.clone(),
+                device_id: self.endpoint.device_id.clone(),
+            };
+
+            self.spotify
+                .request::<_, Nil>(Method::POST, self.endpoint.path(), Some(item), None)
+                .await
+                .map_err(|e| Error::PartiallyQueued {
+                    queued,
+                    source: Box::new(e),
+                })?;
+        }
+
+        Ok(Nil)
+    }
 }
@@ -1,13 +1,17 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, TimeZone};
 use serde::Serialize;
-use serde_json::Value;
+use serde_json::{json, Value};
 
 use crate::{
-    auth::{AuthFlow, Verifier},
+    auth::{AuthFlow, Token},
+    body_list,
+    client::Client,
     error::Result,
+    market::Market,
     model::{
         playlist::{
             FeaturedPlaylists, Playlist, PlaylistTrack, Playlists, SimplifiedPlaylist, SnapshotId,
+            SnapshotResponse,
         },
         Page,
     },
@@ -16,37 +20,134 @@ use crate::{
 
 use super::{Builder, Endpoint, Limit, PrivateEndpoint};
 
-impl Endpoint for PlaylistEndpoint {}
-impl Endpoint for ChangePlaylistDetailsEndpoint {}
-impl Endpoint for PlaylistItemsEndpoint {}
-impl Endpoint for UpdatePlaylistItemsEndpoint {}
-impl Endpoint for AddPlaylistItemsEndpoint {}
-impl Endpoint for RemovePlaylistItemsEndpoint {}
-impl Endpoint for CurrentUserPlaylistsEndpoint {}
-impl Endpoint for UserPlaylistsEndpoint {}
-impl Endpoint for CreatePlaylistEndpoint<'_> {}
-impl Endpoint for FeaturedPlaylistsEndpoint {}
-impl Endpoint for CategoryPlaylistsEndpoint {}
+impl Endpoint for PlaylistEndpoint {
+    fn path(&self) -> String {
+        format!("/playlists/{}", self.id)
+    }
+}
+
+impl Endpoint for ChangePlaylistDetailsEndpoint {
+    fn path(&self) -> String {
+        format!("/playlists/{}", self.id)
+    }
+
+    fn query(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
+}
+
+impl Endpoint for PlaylistItemsEndpoint {
+    fn path(&self) -> String {
+        format!("/playlists/{}/tracks", self.id)
+    }
+}
+
+impl Endpoint for UpdatePlaylistItemsEndpoint {
+    fn path(&self) -> String {
+        format!("/playlists/{}/tracks", self.id)
+    }
+
+    fn query(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
+}
+
+impl Endpoint for ReplacePlaylistItemsEndpoint {
+    fn path(&self) -> String {
+        format!("/playlists/{}/tracks", self.id)
+    }
+
+    fn query(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
+}
+
+impl Endpoint for AddPlaylistItemsEndpoint {
+    fn path(&self) -> String {
+        format!("/playlists/{}/tracks", self.id)
+    }
+
+    fn query(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
+}
+
+impl Endpoint for RemovePlaylistItemsEndpoint {
+    fn path(&self) -> String {
+        format!("/playlists/{}/tracks", self.id)
+    }
+
+    fn query(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
+}
+
+impl Endpoint for CurrentUserPlaylistsEndpoint {
+    fn path(&self) -> String {
+        "/me/playlists".to_owned()
+    }
+}
+
+impl Endpoint for UserPlaylistsEndpoint {
+    fn path(&self) -> String {
+        format!("/users/{}/playlists", self.id)
+    }
+}
+
+impl Endpoint for CreatePlaylistEndpoint<'_> {
+    fn path(&self) -> String {
+        format!("/users/{}/playlists", self.user_id)
+    }
+
+    fn query(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
+}
+
+impl Endpoint for FeaturedPlaylistsEndpoint {
+    fn path(&self) -> String {
+        "/browse/featured-playlists".to_owned()
+    }
+}
+
+impl Endpoint for CategoryPlaylistsEndpoint {
+    fn path(&self) -> String {
+        format!("/browse/categories/{}/playlists", self.id)
+    }
+}
+
+impl_into_future! {
+    PlaylistEndpoint => Playlist, get;
+    ChangePlaylistDetailsEndpoint => Nil, send;
+    PlaylistItemsEndpoint => Page<PlaylistTrack>, get;
+    UpdatePlaylistItemsEndpoint => SnapshotId, send;
+    ReplacePlaylistItemsEndpoint => SnapshotId, send;
+    AddPlaylistItemsEndpoint => SnapshotId, send;
+    RemovePlaylistItemsEndpoint => SnapshotId, send;
+    CurrentUserPlaylistsEndpoint => Page<SimplifiedPlaylist>, get;
+    UserPlaylistsEndpoint => Page<SimplifiedPlaylist>, get;
+    ['a: 's,] CreatePlaylistEndpoint<'a> => Playlist, send;
+    FeaturedPlaylistsEndpoint => FeaturedPlaylists, get;
+    CategoryPlaylistsEndpoint => Page<SimplifiedPlaylist>, get;
+}
 
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct PlaylistEndpoint {
     #[serde(skip)]
     pub(crate) id: String,
-    pub(crate) market: Option<String>,
+    pub(crate) market: Option<Market>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, PlaylistEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, PlaylistEndpoint> {
     #[doc = include_str!("../docs/market.md")]
-    pub fn market(mut self, market: impl Into<String>) -> Self {
-        self.endpoint.market = Some(market.into());
+    pub fn market(mut self, market: Market) -> Self {
+        self.endpoint.market = Some(market);
         self
     }
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<Playlist> {
-        self.spotify
-            .get(format!("/playlists/{}", self.endpoint.id), self.endpoint)
-            .await
+        self.spotify.get(self.endpoint.path(), self.endpoint).await
     }
 }
 
@@ -64,7 +165,7 @@ pub struct ChangePlaylistDetailsEndpoint {
     pub(crate) description: Option<String>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, ChangePlaylistDetailsEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, ChangePlaylistDetailsEndpoint> {
     /// The new name for the playlist.
     pub fn name(mut self, name: impl Into<String>) -> Self {
         self.endpoint.name = Some(name.into());
@@ -94,10 +195,7 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, ChangePlaylistDetailsEndpoint>
     #[doc = include_str!("../docs/send.md")]
     pub async fn send(self) -> Result<Nil> {
         self.spotify
-            .put(
-                format!("/playlists/{}", self.endpoint.id),
-                self.endpoint.json(),
-            )
+            .put(self.endpoint.path(), self.endpoint.json())
             .await
     }
 }
@@ -106,15 +204,15 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, ChangePlaylistDetailsEndpoint>
 pub struct PlaylistItemsEndpoint {
     #[serde(skip)]
     pub(crate) id: String,
-    pub(crate) market: Option<String>,
+    pub(crate) market: Option<Market>,
     pub(crate) limit: Option<Limit>,
     pub(crate) offset: Option<u32>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, PlaylistItemsEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, PlaylistItemsEndpoint> {
     #[doc = include_str!("../docs/market.md")]
-    pub fn market(mut self, market: impl Into<String>) -> Self {
-        self.endpoint.market = Some(market.into());
+    pub fn market(mut self, market: Market) -> Self {
+        self.endpoint.market = Some(market);
         self
     }
 
@@ -132,12 +230,7 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, PlaylistItemsEndpoint> {
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<Page<PlaylistTrack>> {
-        self.spotify
-            .get(
-                format!("/playlists/{}/tracks", self.endpoint.id),
-                self.endpoint,
-            )
-            .await
+        self.spotify.get(self.endpoint.path(), self.endpoint).await
     }
 }
 
@@ -152,10 +245,10 @@ pub struct UpdatePlaylistItemsEndpoint {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) range_length: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub(crate) snapshot_id: Option<String>,
+    pub(crate) snapshot_id: Option<SnapshotId>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, UpdatePlaylistItemsEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, UpdatePlaylistItemsEndpoint> {
     /// The Spotify *URIs* of the items to add (an item can be a track or episode).
     pub fn uris<T: ToString>(mut self, uris: &[T]) -> Self {
         self.endpoint.uris = Some(uris.iter().map(ToString::to_string).collect());
@@ -175,20 +268,54 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, UpdatePlaylistItemsEndpoint> {
     }
 
     /// The playlist's snapshot ID against which to make changes.
-    pub fn snapshot_id(mut self, snapshot_id: impl Into<String>) -> Self {
+    pub fn snapshot_id(mut self, snapshot_id: impl Into<SnapshotId>) -> Self {
         self.endpoint.snapshot_id = Some(snapshot_id.into());
         self
     }
 
     #[doc = include_str!("../docs/send.md")]
-    pub async fn send(self) -> Result<String> {
+    pub async fn send(self) -> Result<SnapshotId> {
         self.spotify
-            .put(
-                format!("/playlists/{}/tracks", self.endpoint.id),
-                self.endpoint.json(),
-            )
+            .put(self.endpoint.path(), self.endpoint.json())
             .await
-            .map(|i: SnapshotId| i.snapshot_id)
+            .map(|i: SnapshotResponse| i.snapshot_id)
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ReplacePlaylistItemsEndpoint {
+    #[serde(skip)]
+    pub(crate) id: String,
+    pub(crate) uris: Vec<String>,
+}
+
+impl<F: AuthFlow> Builder<'_, F, ReplacePlaylistItemsEndpoint> {
+    /// Send the request, returning the playlist's new snapshot ID.
+    ///
+    /// Spotify only accepts 100 URIs per request, so the first 100 replace the
+    /// playlist's items, and the rest are added after them, 100 at a time. If one of
+    /// the later requests fails, the playlist is left with only the items before it.
+    pub async fn send(self) -> Result<SnapshotId> {
+        let path = self.endpoint.path();
+        let mut chunks = self.endpoint.uris.chunks(100);
+
+        // An empty list still has to be sent, to clear the playlist.
+        let first = chunks.next().unwrap_or_default();
+        let mut snapshot_id = self
+            .spotify
+            .put(path.clone(), body_list("uris", first))
+            .await
+            .map(|i: SnapshotResponse| i.snapshot_id)?;
+
+        for chunk in chunks {
+            snapshot_id = self
+                .spotify
+                .post(path.clone(), body_list("uris", chunk))
+                .await
+                .map(|i: SnapshotResponse| i.snapshot_id)?;
+        }
+
+        Ok(snapshot_id)
     }
 }
 
@@ -201,22 +328,51 @@ pub struct AddPlaylistItemsEndpoint {
     pub(crate) position: Option<u32>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, AddPlaylistItemsEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, AddPlaylistItemsEndpoint> {
     /// The position to insert the items at, zero-based. If omitted, items will be appended to the playlist.
     pub fn position(mut self, position: u32) -> Self {
         self.endpoint.position = Some(position);
         self
     }
 
-    #[doc = include_str!("../docs/send.md")]
-    pub async fn send(self) -> Result<String> {
+    /// Send the request, returning the playlist's new snapshot ID.
+    ///
+    /// Spotify only accepts 100 URIs per request, so longer lists are added 100 at a
+    /// time, in order. If a position is set, each chunk is inserted right after the one
+    /// before it. If one of the later requests fails, only the items before it are added.
+    pub async fn send(self) -> Result<SnapshotId> {
+        let path = self.endpoint.path();
+        let mut position = self.endpoint.position;
+        let mut chunks = self.endpoint.uris.chunks(100);
+
+        // An empty list is still sent, for Spotify to reject it as it always has.
+        let first = chunks.next().unwrap_or_default();
+        let mut snapshot_id = self.add_chunk(path.clone(), first, position).await?;
+
+        for chunk in chunks {
+            position = position.map(|p| p + 100);
+            snapshot_id = self.add_chunk(path.clone(), chunk, position).await?;
+        }
+
+        Ok(snapshot_id)
+    }
+
+    async fn add_chunk(
+        &self,
+        path: String,
+        uris: &[String],
+        position: Option<u32>,
+    ) -> Result<SnapshotId> {
+        let chunk = AddPlaylistItemsEndpoint {
+            id: String::new(),
+            uris: uris.to_vec(),
+            position,
+        };
+
         self.spotify
-            .post(
-                format!("/playlists/{}/tracks", self.endpoint.id),
-                self.endpoint.json(),
-            )
+            .post(path, chunk.json())
             .await
-            .map(|i: SnapshotId| i.snapshot_id)
+            .map(|i: SnapshotResponse| i.snapshot_id)
     }
 }
 
@@ -226,26 +382,81 @@ pub struct RemovePlaylistItemsEndpoint {
     pub(crate) id: String,
     pub(crate) tracks: Vec<Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub(crate) snapshot_id: Option<String>,
+    pub(crate) snapshot_id: Option<SnapshotId>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, RemovePlaylistItemsEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, RemovePlaylistItemsEndpoint> {
     /// The playlist's snapshot ID against which to make changes.
-    pub fn snapshot_id(mut self, snapshot_id: impl Into<String>) -> Self {
+    pub fn snapshot_id(mut self, snapshot_id: impl Into<SnapshotId>) -> Self {
         self.endpoint.snapshot_id = Some(snapshot_id.into());
         self
     }
 
-    #[doc = include_str!("../docs/send.md")]
+    /// Also remove the item with the given URI, but only at the given zero-based
+    /// positions, rather than every occurrence of it.
+    ///
+    /// The positions are those in the playlist's [snapshot](Self::snapshot_id), so one
+    /// should be set when removing by position.
+    pub fn item_at_positions(mut self, uri: impl AsRef<str>, positions: &[u32]) -> Self {
+        self.endpoint
+            .tracks
+            .push(json!({ "uri": uri.as_ref(), "positions": positions }));
+        self
+    }
+
+    /// Send the request, returning the playlist's new snapshot ID.
+    ///
+    /// Spotify only accepts 100 items per request, so longer lists are removed 100 at a
+    /// time, each request being made against the snapshot returned by the one before.
+    /// When removing by position, every request is instead made against the snapshot
+    /// that was set, as that's the one the positions are in. If one of the later
+    /// requests fails, only the items before it are removed.
+    pub async fn send(self) -> Result<SnapshotId> {
+        let path = self.endpoint.path();
+        let by_position = self
+            .endpoint
+            .tracks
+            .iter()
+            .any(|t| t.get("positions").is_some());
+
+        let mut snapshot_id = self.endpoint.snapshot_id.clone();
+        let mut chunks = self.endpoint.tracks.chunks(100);
+
+        // An empty list is still sent, for Spotify to reject it as it always has.
+        let first = chunks.next().unwrap_or_default();
+        let mut new_snapshot_id = self
+            .remove_chunk(path.clone(), first, snapshot_id.clone())
+            .await?;
+
+        for chunk in chunks {
+            if !by_position {
+                snapshot_id = Some(new_snapshot_id);
+            }
+
+            new_snapshot_id = self
+                .remove_chunk(path.clone(), chunk, snapshot_id.clone())
+                .await?;
+        }
+
+        Ok(new_snapshot_id)
+    }
+
+    async fn remove_chunk(
+        &self,
+        path: String,
+        tracks: &[Value],
+        snapshot_id: Option<SnapshotId>,
+    ) -> Result<SnapshotId> {
+        let chunk = RemovePlaylistItemsEndpoint {
+            id: String::new(),
+            tracks: tracks.to_vec(),
+            snapshot_id,
+        };
 
-    pub async fn send(self) -> Result<String> {
         self.spotify
-            .delete(
-                format!("/playlists/{}/tracks", self.endpoint.id),
-                self.endpoint.json(),
-            )
+            .delete(path, chunk.json())
             .await
-            .map(|i: SnapshotId| i.snapshot_id)
+            .map(|i: SnapshotResponse| i.snapshot_id)
     }
 }
 
@@ -255,7 +466,7 @@ pub struct CurrentUserPlaylistsEndpoint {
     pub(crate) offset: Option<u32>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, CurrentUserPlaylistsEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, CurrentUserPlaylistsEndpoint> {
     #[doc = include_str!("../docs/limit.md")]
     pub fn limit(mut self, limit: u32) -> Self {
         self.endpoint.limit = Some(Limit::new(limit));
@@ -270,9 +481,7 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, CurrentUserPlaylistsEndpoint> {
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<Page<SimplifiedPlaylist>> {
-        self.spotify
-            .get("/me/playlists".to_owned(), self.endpoint)
-            .await
+        self.spotify.get(self.endpoint.path(), self.endpoint).await
     }
 }
 
@@ -284,7 +493,7 @@ pub struct UserPlaylistsEndpoint {
     pub(crate) offset: Option<u32>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, UserPlaylistsEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, UserPlaylistsEndpoint> {
     #[doc = include_str!("../docs/limit.md")]
     pub fn limit(mut self, limit: u32) -> Self {
         self.endpoint.limit = Some(Limit::new(limit));
@@ -299,12 +508,7 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, UserPlaylistsEndpoint> {
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<Page<SimplifiedPlaylist>> {
-        self.spotify
-            .get(
-                format!("/users/{}/playlists", self.endpoint.id),
-                self.endpoint,
-            )
-            .await
+        self.spotify.get(self.endpoint.path(), self.endpoint).await
     }
 }
 
@@ -320,7 +524,7 @@ pub struct CreatePlaylistEndpoint<'a> {
     pub(crate) description: Option<String>,
 }
 
-impl<'a, F: AuthFlow, V: Verifier> Builder<'_, F, V, CreatePlaylistEndpoint<'a>> {
+impl<'a, F: AuthFlow> Builder<'_, F, CreatePlaylistEndpoint<'a>> {
     /// Whether or not to make the playlist public. Defaults to `true`.
     pub fn public(mut self, public: bool) -> Self {
         self.endpoint.public = Some(public);
@@ -353,20 +557,18 @@ impl<'a, F: AuthFlow, V: Verifier> Builder<'_, F, V, CreatePlaylistEndpoint<'a>>
 
         let mut playlist: Playlist = self
             .spotify
-            .post(
-                format!("/users/{}/playlists", self.endpoint.user_id),
-                self.endpoint.json(),
-            )
+            .post(self.endpoint.path(), self.endpoint.json())
             .await?;
 
         if let Some(tracks) = tracks {
-            self.spotify
-                .add_items_to_playlist(&playlist.id, tracks)
-                .send()
-                .await?;
-
-            let tracks = self.spotify.playlist_items(&playlist.id).get().await?;
-            playlist.tracks = tracks;
+            // The follow-up requests have to use the same token (and settings) as the first one.
+            let mut add = self.spotify.add_items_to_playlist(&playlist.id, tracks);
+            add.spotify.overrides.clone_from(&self.spotify.overrides);
+            add.send().await?;
+
+            let mut items = self.spotify.playlist_items(&playlist.id);
+            items.spotify.overrides.clone_from(&self.spotify.overrides);
+            playlist.tracks = items.get().await?;
         }
 
         Ok(playlist)
@@ -382,7 +584,7 @@ pub struct FeaturedPlaylistsEndpoint {
     pub(crate) offset: Option<u32>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, FeaturedPlaylistsEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, FeaturedPlaylistsEndpoint> {
     #[doc = include_str!("../docs/country.md")]
     pub fn country(mut self, country: impl Into<String>) -> Self {
         self.endpoint.country = Some(country.into());
@@ -395,9 +597,13 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, FeaturedPlaylistsEndpoint> {
         self
     }
 
-    /// An [ISO 8601](https://en.wikipedia.org/wiki/ISO_8601) timestamp (`yyyy-MM-ddTHH:mm:ss`)
-    pub fn timestamp(mut self, timestamp: DateTime<Utc>) -> Self {
-        self.endpoint.timestamp = Some(timestamp.to_rfc3339());
+    /// The user's local time, which Spotify uses to pick playlists for the time of day.
+    ///
+    /// It's sent as an [ISO 8601](https://en.wikipedia.org/wiki/ISO_8601) timestamp
+    /// (`yyyy-MM-ddTHH:mm:ss`) in the given time zone, so pass the time in the user's time
+    /// zone rather than in UTC.
+    pub fn timestamp<Tz: TimeZone>(mut self, timestamp: DateTime<Tz>) -> Self {
+        self.endpoint.timestamp = Some(local_timestamp(timestamp));
         self
     }
 
@@ -415,12 +621,15 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, FeaturedPlaylistsEndpoint> {
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<FeaturedPlaylists> {
-        self.spotify
-            .get("/browse/featured-playlists".to_owned(), self.endpoint)
-            .await
+        self.spotify.get(self.endpoint.path(), self.endpoint).await
     }
 }
 
+// Spotify expects the user's local time, without an offset.
+pub(crate) fn local_timestamp<Tz: TimeZone>(time: DateTime<Tz>) -> String {
+    time.naive_local().format("%Y-%m-%dT%H:%M:%S").to_string()
+}
+
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct CategoryPlaylistsEndpoint {
     #[serde(skip)]
@@ -430,7 +639,7 @@ pub struct CategoryPlaylistsEndpoint {
     pub(crate) offset: Option<u32>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, CategoryPlaylistsEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, CategoryPlaylistsEndpoint> {
     #[doc = include_str!("../docs/country.md")]
     pub fn country(mut self, country: impl Into<String>) -> Self {
         self.endpoint.country = Some(country.into());
@@ -452,11 +661,109 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, CategoryPlaylistsEndpoint> {
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<Page<SimplifiedPlaylist>> {
         self.spotify
-            .get(
-                format!("/browse/categories/{}/playlists", self.endpoint.id),
-                self.endpoint,
-            )
+            .get(self.endpoint.path(), self.endpoint)
             .await
             .map(|p: Playlists| p.playlists)
     }
 }
+
+/// Modifies a playlist's items with a chain of requests, each of which is made against
+/// the snapshot of the playlist returned by the one before.
+///
+/// Created with [`Client::edit_playlist`]. Each request is sent as soon as its method is
+/// called, and returns the editor, so that the next one can be chained onto it.
+///
+/// ```no_run
+/// # async fn example(spotify: &spotify_rs::AuthCodeClient) -> spotify_rs::SpotifyResult<()> {
+/// let mut editor = spotify.edit_playlist("3cEYpjA9oz9GiPac4AsH4n");
+///
+/// editor
+///     .remove(&["spotify:track:4iV5W9uYEdYUVa79Axb7Rh"])
+///     .await?
+///     .add(&["spotify:track:1301WleyT98MSxVHPZCA6M"], Some(0))
+///     .await?
+///     .reorder(5, 0, 2)
+///     .await?;
+///
+/// let snapshot_id = editor.snapshot_id();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct PlaylistEditor<'s, F: AuthFlow> {
+    pub(crate) spotify: &'s Client<Token, F>,
+    pub(crate) id: String,
+    pub(crate) snapshot_id: Option<SnapshotId>,
+}
+
+impl<F: AuthFlow> PlaylistEditor<'_, F> {
+    /// Make the first request against the given snapshot of the playlist, instead of
+    /// its latest one.
+    pub fn from_snapshot(mut self, snapshot_id: impl Into<SnapshotId>) -> Self {
+        self.snapshot_id = Some(snapshot_id.into());
+        self
+    }
+
+    /// The snapshot ID returned by the last request, or the one the editor started from.
+    pub fn snapshot_id(&self) -> Option<&SnapshotId> {
+        self.snapshot_id.as_ref()
+    }
+
+    /// Add the items with the given URIs at `position`, or at the end of the playlist.
+    pub async fn add<T: ToString>(
+        &mut self,
+        item_uris: &[T],
+        position: Option<u32>,
+    ) -> Result<&mut Self> {
+        let mut request = self.spotify.add_items_to_playlist(&self.id, item_uris);
+
+        if let Some(position) = position {
+            request = request.position(position);
+        }
+
+        // Adding items can't be made against a snapshot, as Spotify doesn't accept one.
+        self.snapshot_id = Some(request.send().await?);
+        Ok(self)
+    }
+
+    /// Remove every occurrence of the items with the given URIs.
+    pub async fn remove<T: AsRef<str>>(&mut self, item_uris: &[T]) -> Result<&mut Self> {
+        let mut request = self.spotify.remove_playlist_items(&self.id, item_uris);
+
+        if let Some(snapshot_id) = &self.snapshot_id {
+            request = request.snapshot_id(snapshot_id);
+        }
+
+        self.snapshot_id = Some(request.send().await?);
+        Ok(self)
+    }
+
+    /// Move the `range_length` items starting at `range_start` so that they're right
+    /// before the item that's currently at `insert_before`.
+    pub async fn reorder(
+        &mut self,
+        range_start: u32,
+        insert_before: u32,
+        range_length: u32,
+    ) -> Result<&mut Self> {
+        let mut request = self
+            .spotify
+            .update_playlist_items(&self.id, range_start, insert_before)
+            .range_length(range_length);
+
+        if let Some(snapshot_id) = &self.snapshot_id {
+            request = request.snapshot_id(snapshot_id);
+        }
+
+        self.snapshot_id = Some(request.send().await?);
+        Ok(self)
+    }
+
+    /// Replace all of the playlist's items with the items with the given URIs.
+    pub async fn replace<T: ToString>(&mut self, item_uris: &[T]) -> Result<&mut Self> {
+        let request = self.spotify.replace_playlist_items(&self.id, item_uris);
+
+        self.snapshot_id = Some(request.send().await?);
+        Ok(self)
+    }
+}
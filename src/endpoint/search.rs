@@ -1,14 +1,20 @@
+use std::{fmt, ops::RangeInclusive};
+
 use serde::Serialize;
 
-use crate::{
-    auth::{AuthFlow, Verifier},
-    error::Result,
-    model::search::SearchResults,
-};
+use crate::{auth::AuthFlow, error::Result, market::Market, model::search::SearchResults};
 
 use super::{Builder, Endpoint, Limit};
 
-impl Endpoint for SearchEndpoint {}
+impl Endpoint for SearchEndpoint {
+    fn path(&self) -> String {
+        "/search".to_owned()
+    }
+}
+
+impl_into_future! {
+    SearchEndpoint => SearchResults, get;
+}
 
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct SearchEndpoint {
@@ -16,23 +22,27 @@ pub struct SearchEndpoint {
     pub(crate) query: String,
     pub(crate) r#type: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub(crate) market: Option<String>,
+    pub(crate) market: Option<Market>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) limit: Option<Limit>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) offset: Option<u32>,
+    // Spotify's only value for this is `audio`.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub(crate) include_external: Option<bool>,
+    pub(crate) include_external: Option<&'static str>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, SearchEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, SearchEndpoint> {
     #[doc = include_str!("../docs/market.md")]
-    pub fn market(mut self, market: impl Into<String>) -> Self {
-        self.endpoint.market = Some(market.into());
+    pub fn market(mut self, market: Market) -> Self {
+        self.endpoint.market = Some(market);
         self
     }
 
     #[doc = include_str!("../docs/limit.md")]
+    ///
+    /// The limit applies to each item type separately, e.g. a limit of 10 when searching for
+    /// tracks and albums returns up to 10 of each.
     pub fn limit(mut self, limit: u32) -> Self {
         self.endpoint.limit = Some(Limit::new(limit));
         self
@@ -44,17 +54,152 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, SearchEndpoint> {
         self
     }
 
-    /// If specified, it signals that the client can play externally hosted audio content,
-    /// and marks the content as playable in the response.
+    /// If `true`, it signals that the client can play externally hosted audio content,
+    /// and marks the content as playable in the response (sent as `include_external=audio`).
     ///
     /// By default externally hosted audio content is marked as unplayable in the response.
     pub fn include_external(mut self, include_external: bool) -> Self {
-        self.endpoint.include_external = Some(include_external);
+        self.endpoint.include_external = include_external.then_some("audio");
         self
     }
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<SearchResults> {
-        self.spotify.get("/search".to_owned(), self.endpoint).await
+        self.spotify.get(self.endpoint.path(), self.endpoint).await
+    }
+}
+
+/// A search query with [field filters](https://developer.spotify.com/documentation/web-api/reference/search),
+/// which can be passed to [`search`](crate::client::Client::search) instead of a string.
+///
+/// Not every filter applies to every item type: `artist` and `year` only narrow down
+/// albums, artists and tracks, `album` only albums and tracks, `genre` only artists and
+/// tracks, `track` and `isrc` only tracks, and `upc` and the tags only albums.
+///
+/// ```
+/// use spotify_rs::endpoint::search::SearchQuery;
+///
+/// let query = SearchQuery::new("bohemian")
+///     .artist("queen")
+///     .album("A Night at the Opera")
+///     .year(1975..=1980);
+///
+/// assert_eq!(
+///     query.to_string(),
+///     r#"bohemian artist:queen album:"A Night at the Opera" year:1975-1980"#
+/// );
+/// assert_eq!(SearchQuery::new("").tag_new().to_string(), "tag:new");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SearchQuery {
+    text: String,
+    filters: Vec<(&'static str, String)>,
+}
+
+impl SearchQuery {
+    /// Create a query that matches `text`, which can be empty to only use filters.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            filters: Vec::new(),
+        }
+    }
+
+    /// Only match items by this artist.
+    pub fn artist(self, artist: impl Into<String>) -> Self {
+        self.filter("artist", artist.into())
+    }
+
+    /// Only match items from this album.
+    pub fn album(self, album: impl Into<String>) -> Self {
+        self.filter("album", album.into())
+    }
+
+    /// Only match tracks with this name.
+    pub fn track(self, track: impl Into<String>) -> Self {
+        self.filter("track", track.into())
+    }
+
+    /// Only match items in this genre.
+    pub fn genre(self, genre: impl Into<String>) -> Self {
+        self.filter("genre", genre.into())
+    }
+
+    /// Only match items released in this year, or range of years, e.g. `1975..=1980`.
+    pub fn year(self, years: impl Years) -> Self {
+        self.filter("year", years.to_filter())
+    }
+
+    /// Only match the track with this [ISRC](https://en.wikipedia.org/wiki/International_Standard_Recording_Code).
+    pub fn isrc(self, isrc: impl Into<String>) -> Self {
+        self.filter("isrc", isrc.into())
+    }
+
+    /// Only match the album with this [UPC](https://en.wikipedia.org/wiki/Universal_Product_Code).
+    pub fn upc(self, upc: impl Into<String>) -> Self {
+        self.filter("upc", upc.into())
+    }
+
+    /// Only match albums released in the past two weeks.
+    pub fn tag_new(self) -> Self {
+        self.filter("tag", "new".to_owned())
+    }
+
+    /// Only match albums with the lowest 10% popularity.
+    pub fn tag_hipster(self) -> Self {
+        self.filter("tag", "hipster".to_owned())
+    }
+
+    fn filter(mut self, name: &'static str, value: String) -> Self {
+        self.filters.push((name, value));
+        self
+    }
+}
+
+/// A year or an inclusive range of years, for [`SearchQuery::year`].
+pub trait Years {
+    /// The years as written in a search query, e.g. `1975` or `1975-1980`.
+    fn to_filter(&self) -> String;
+}
+
+impl Years for u32 {
+    fn to_filter(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl Years for RangeInclusive<u32> {
+    fn to_filter(&self) -> String {
+        format!("{}-{}", self.start(), self.end())
+    }
+}
+
+impl fmt::Display for SearchQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut separator = "";
+
+        if !self.text.is_empty() {
+            f.write_str(&self.text)?;
+            separator = " ";
+        }
+
+        for (name, value) in &self.filters {
+            // Values with spaces are quoted, so the filter applies to all of their words.
+            if value.contains(char::is_whitespace) {
+                write!(f, "{separator}{name}:\"{value}\"")?;
+            } else {
+                write!(f, "{separator}{name}:{value}")?;
+            }
+
+            separator = " ";
+        }
+
+        Ok(())
+    }
+}
+
+impl From<SearchQuery> for String {
+    fn from(query: SearchQuery) -> Self {
+        query.to_string()
     }
 }
@@ -1,8 +1,12 @@
+use std::ops::RangeBounds;
+
+use chrono::{DateTime, Utc};
 use serde::Serialize;
 
 use crate::{
-    auth::{AuthFlow, Verifier},
+    auth::AuthFlow,
     error::Result,
+    market::Market,
     model::{
         show::{
             Episode, Episodes, SavedEpisode, SavedShow, Show, Shows, SimplifiedEpisode,
@@ -14,46 +18,88 @@ use crate::{
 
 use super::{Builder, Endpoint, Limit};
 
-impl Endpoint for ShowEndpoint {}
-impl Endpoint for ShowsEndpoint {}
-impl Endpoint for ShowEpisodesEndpoint {}
-impl Endpoint for SavedShowsEndpoint {}
-impl Endpoint for EpisodeEndpoint {}
-impl Endpoint for EpisodesEndpoint {}
-impl Endpoint for SavedEpisodesEndpoint {}
+impl Endpoint for ShowEndpoint {
+    fn path(&self) -> String {
+        format!("/shows/{}", self.id)
+    }
+}
+
+impl Endpoint for ShowsEndpoint {
+    fn path(&self) -> String {
+        "/shows/".to_owned()
+    }
+}
+
+impl Endpoint for ShowEpisodesEndpoint {
+    fn path(&self) -> String {
+        format!("/shows/{}/episodes", self.show_id)
+    }
+}
+
+impl Endpoint for SavedShowsEndpoint {
+    fn path(&self) -> String {
+        "/me/shows".to_owned()
+    }
+}
+
+impl Endpoint for EpisodeEndpoint {
+    fn path(&self) -> String {
+        format!("/episodes/{}", self.id)
+    }
+}
+
+impl Endpoint for EpisodesEndpoint {
+    fn path(&self) -> String {
+        "/episodes/".to_owned()
+    }
+}
+
+impl Endpoint for SavedEpisodesEndpoint {
+    fn path(&self) -> String {
+        "/me/episodes".to_owned()
+    }
+}
+
+impl_into_future! {
+    ShowEndpoint => Show, get;
+    ShowsEndpoint => Vec<Option<SimplifiedShow>>, get;
+    ShowEpisodesEndpoint => Page<SimplifiedEpisode>, get;
+    SavedShowsEndpoint => Page<SavedShow>, get;
+    EpisodeEndpoint => Episode, get;
+    EpisodesEndpoint => Vec<Episode>, get;
+    SavedEpisodesEndpoint => Page<SavedEpisode>, get;
+}
 
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct ShowEndpoint {
     #[serde(skip)]
     pub(crate) id: String,
-    pub(crate) market: Option<String>,
+    pub(crate) market: Option<Market>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, ShowEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, ShowEndpoint> {
     #[doc = include_str!("../docs/market.md")]
-    pub fn market(mut self, market: impl Into<String>) -> Self {
-        self.endpoint.market = Some(market.into());
+    pub fn market(mut self, market: Market) -> Self {
+        self.endpoint.market = Some(market);
         self
     }
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<Show> {
-        self.spotify
-            .get(format!("/shows/{}", self.endpoint.id), self.endpoint)
-            .await
+        self.spotify.get(self.endpoint.path(), self.endpoint).await
     }
 }
 
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct ShowsEndpoint {
     pub(crate) ids: String,
-    pub(crate) market: Option<String>,
+    pub(crate) market: Option<Market>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, ShowsEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, ShowsEndpoint> {
     #[doc = include_str!("../docs/market.md")]
-    pub fn market(mut self, market: impl Into<String>) -> Self {
-        self.endpoint.market = Some(market.into());
+    pub fn market(mut self, market: Market) -> Self {
+        self.endpoint.market = Some(market);
         self
     }
 
@@ -61,10 +107,9 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, ShowsEndpoint> {
     // know that some of the shows they want return null.
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<Vec<Option<SimplifiedShow>>> {
-        self.spotify
-            .get("/shows/".to_owned(), self.endpoint)
+        self.get_chunked(50, |e| &mut e.ids)
             .await
-            .map(|s: Shows| s.shows)
+            .map(|s: Vec<Shows>| s.into_iter().flat_map(|s| s.shows).collect())
     }
 }
 
@@ -72,15 +117,15 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, ShowsEndpoint> {
 pub struct ShowEpisodesEndpoint {
     #[serde(skip)]
     pub(crate) show_id: String,
-    pub(crate) market: Option<String>,
+    pub(crate) market: Option<Market>,
     pub(crate) limit: Option<Limit>,
     pub(crate) offset: Option<u32>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, ShowEpisodesEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, ShowEpisodesEndpoint> {
     #[doc = include_str!("../docs/market.md")]
-    pub fn market(mut self, market: impl Into<String>) -> Self {
-        self.endpoint.market = Some(market.into());
+    pub fn market(mut self, market: Market) -> Self {
+        self.endpoint.market = Some(market);
         self
     }
 
@@ -98,12 +143,7 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, ShowEpisodesEndpoint> {
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<Page<SimplifiedEpisode>> {
-        self.spotify
-            .get(
-                format!("/shows/{}/episodes", self.endpoint.show_id),
-                self.endpoint,
-            )
-            .await
+        self.spotify.get(self.endpoint.path(), self.endpoint).await
     }
 }
 
@@ -113,7 +153,7 @@ pub struct SavedShowsEndpoint {
     pub(crate) offset: Option<u32>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, SavedShowsEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, SavedShowsEndpoint> {
     #[doc = include_str!("../docs/limit.md")]
     pub fn limit(mut self, limit: u32) -> Self {
         self.endpoint.limit = Some(Limit::new(limit));
@@ -128,8 +168,24 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, SavedShowsEndpoint> {
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<Page<SavedShow>> {
-        self.spotify
-            .get("/me/shows".to_owned(), self.endpoint)
+        self.spotify.get(self.endpoint.path(), self.endpoint).await
+    }
+
+    /// Get all of the user's saved shows, going through all of the pages.
+    pub async fn get_all(self) -> Result<Vec<SavedShow>> {
+        self.get_all_added(..).await
+    }
+
+    /// Get all of the user's saved shows that were added within `range`, e.g. `since..`
+    /// for the ones added since a given time.
+    ///
+    /// Saved shows are listed newest first, so pages are only requested until the
+    /// first one added before the range.
+    pub async fn get_all_added(
+        self,
+        range: impl RangeBounds<DateTime<Utc>>,
+    ) -> Result<Vec<SavedShow>> {
+        self.get_all_saved(range, |e| (&mut e.limit, &mut e.offset))
             .await
     }
 }
@@ -138,57 +194,54 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, SavedShowsEndpoint> {
 pub struct EpisodeEndpoint {
     #[serde(skip)]
     pub(crate) id: String,
-    pub(crate) market: Option<String>,
+    pub(crate) market: Option<Market>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, EpisodeEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, EpisodeEndpoint> {
     #[doc = include_str!("../docs/market.md")]
-    pub fn market(mut self, market: impl Into<String>) -> Self {
-        self.endpoint.market = Some(market.into());
+    pub fn market(mut self, market: Market) -> Self {
+        self.endpoint.market = Some(market);
         self
     }
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<Episode> {
-        self.spotify
-            .get(format!("/episodes/{}", self.endpoint.id), self.endpoint)
-            .await
+        self.spotify.get(self.endpoint.path(), self.endpoint).await
     }
 }
 
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct EpisodesEndpoint {
     pub(crate) ids: String,
-    pub(crate) market: Option<String>,
+    pub(crate) market: Option<Market>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, EpisodesEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, EpisodesEndpoint> {
     #[doc = include_str!("../docs/market.md")]
-    pub fn market(mut self, market: impl Into<String>) -> Self {
-        self.endpoint.market = Some(market.into());
+    pub fn market(mut self, market: Market) -> Self {
+        self.endpoint.market = Some(market);
         self
     }
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<Vec<Episode>> {
-        self.spotify
-            .get("/episodes/".to_owned(), self.endpoint)
+        self.get_chunked(50, |e| &mut e.ids)
             .await
-            .map(|e: Episodes| e.episodes)
+            .map(|e: Vec<Episodes>| e.into_iter().flat_map(|e| e.episodes).collect())
     }
 }
 
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct SavedEpisodesEndpoint {
-    pub(crate) market: Option<String>,
+    pub(crate) market: Option<Market>,
     pub(crate) limit: Option<Limit>,
     pub(crate) offset: Option<u32>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, SavedEpisodesEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, SavedEpisodesEndpoint> {
     #[doc = include_str!("../docs/market.md")]
-    pub fn market(mut self, market: impl Into<String>) -> Self {
-        self.endpoint.market = Some(market.into());
+    pub fn market(mut self, market: Market) -> Self {
+        self.endpoint.market = Some(market);
         self
     }
 
@@ -206,8 +259,24 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, SavedEpisodesEndpoint> {
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<Page<SavedEpisode>> {
-        self.spotify
-            .get("/me/episodes".to_owned(), self.endpoint)
+        self.spotify.get(self.endpoint.path(), self.endpoint).await
+    }
+
+    /// Get all of the user's saved episodes, going through all of the pages.
+    pub async fn get_all(self) -> Result<Vec<SavedEpisode>> {
+        self.get_all_added(..).await
+    }
+
+    /// Get all of the user's saved episodes that were added within `range`, e.g. `since..`
+    /// for the ones added since a given time.
+    ///
+    /// Saved episodes are listed newest first, so pages are only requested until the
+    /// first one added before the range.
+    pub async fn get_all_added(
+        self,
+        range: impl RangeBounds<DateTime<Utc>>,
+    ) -> Result<Vec<SavedEpisode>> {
+        self.get_all_saved(range, |e| (&mut e.limit, &mut e.offset))
             .await
     }
 }
@@ -1,11 +1,15 @@
-use std::{collections::HashMap, marker::PhantomData};
+use std::{collections::HashMap, marker::PhantomData, ops::RangeBounds};
+
+use chrono::{DateTime, Utc};
 
 use serde::Serialize;
 use strum::IntoStaticStr;
 
 use crate::{
-    auth::{AuthFlow, Verifier},
-    error::Result,
+    auth::AuthFlow,
+    error::{Error, Result},
+    id::{as_ids, ArtistId, AsId, TrackId},
+    market::Market,
     model::{
         recommendation::Recommendations,
         track::{SavedTrack, Track, Tracks},
@@ -16,15 +20,42 @@ use crate::{
 
 use super::{Builder, Endpoint, Limit};
 
-impl Endpoint for TrackEndpoint {}
-impl Endpoint for TracksEndpoint {}
-impl Endpoint for SavedTracksEndpoint {}
-impl<S: SeedType> Endpoint for RecommendationsEndpoint<S> {}
+impl Endpoint for TrackEndpoint {
+    fn path(&self) -> String {
+        format!("/tracks/{}", self.id)
+    }
+}
+
+impl Endpoint for TracksEndpoint {
+    fn path(&self) -> String {
+        "/tracks".to_owned()
+    }
+}
+
+impl Endpoint for SavedTracksEndpoint {
+    fn path(&self) -> String {
+        "/me/tracks".to_owned()
+    }
+}
+
+impl<S: SeedType> Endpoint for RecommendationsEndpoint<S> {
+    fn path(&self) -> String {
+        "/recommendations".to_owned()
+    }
+}
+
+impl_into_future! {
+    TrackEndpoint => Track, get;
+    TracksEndpoint => Vec<Track>, get;
+    SavedTracksEndpoint => Page<SavedTrack>, get;
+    [S: SeedType + Send + 's,] RecommendationsEndpoint<S> => Recommendations, get;
+}
 
 pub trait SeedType {}
 impl SeedType for SeedArtists {}
 impl SeedType for SeedGenres {}
 impl SeedType for SeedTracks {}
+impl SeedType for Seeds {}
 
 pub enum SeedArtists {}
 pub enum SeedGenres {}
@@ -55,6 +86,110 @@ impl<'a, T: AsRef<str> + Clone> Seed<'a, T, SeedTracks> {
     }
 }
 
+/// Seeds of any kind for recommendations, combined with [`Seeds::builder`] and used with
+/// [`Client::recommendations_with_seeds`](crate::client::Client::recommendations_with_seeds).
+///
+/// Spotify accepts up to 5 seeds in total, across artists, genres and tracks.
+///
+/// ```
+/// use spotify_rs::endpoint::track::Seeds;
+///
+/// let seeds = Seeds::builder()
+///     .artist("4NHQUGzhtTLFvgF5SZesLK")
+///     .track("0c6xIDDpzE81m2q797ordA")
+///     .genre("rock")
+///     .build()?;
+///
+/// assert_eq!(seeds.len(), 3);
+/// # Ok::<(), spotify_rs::Error>(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Seeds {
+    artists: Vec<String>,
+    genres: Vec<String>,
+    tracks: Vec<String>,
+}
+
+impl Seeds {
+    /// The most seeds Spotify accepts in total.
+    pub const MAX: usize = 5;
+
+    /// Start combining seeds.
+    pub fn builder() -> SeedsBuilder {
+        SeedsBuilder::default()
+    }
+
+    /// The number of seeds, of all kinds.
+    pub fn len(&self) -> usize {
+        self.artists.len() + self.genres.len() + self.tracks.len()
+    }
+
+    /// Returns `true` if there are no seeds, which [`SeedsBuilder::build`] doesn't allow.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Builder for [`Seeds`].
+#[derive(Clone, Debug, Default)]
+pub struct SeedsBuilder {
+    seeds: Seeds,
+}
+
+impl SeedsBuilder {
+    /// Add an artist, by its ID, as a seed.
+    pub fn artist(mut self, id: impl AsId<ArtistId>) -> Self {
+        self.seeds.artists.push(id.as_id().to_owned());
+        self
+    }
+
+    /// Add a genre as a seed, which has to be one of the
+    /// [available genre seeds](crate::client::Client::get_genre_seeds).
+    pub fn genre(mut self, genre: impl Into<String>) -> Self {
+        self.seeds.genres.push(genre.into());
+        self
+    }
+
+    /// Add a track, by its ID, as a seed.
+    pub fn track(mut self, id: impl AsId<TrackId>) -> Self {
+        self.seeds.tracks.push(id.as_id().to_owned());
+        self
+    }
+
+    /// Get the seeds, failing with [`Error::InvalidSeeds`] if there are none, or more than
+    /// [`Seeds::MAX`].
+    pub fn build(self) -> Result<Seeds> {
+        match self.seeds.len() {
+            0 => Err(Error::InvalidSeeds(
+                "At least one seed is required.".to_owned(),
+            )),
+            n if n > Seeds::MAX => Err(Error::InvalidSeeds(format!(
+                "{n} seeds were given, but Spotify accepts at most {}.",
+                Seeds::MAX
+            ))),
+            _ => Ok(self.seeds),
+        }
+    }
+}
+
+fn seed_list(seeds: &[String]) -> Option<String> {
+    (!seeds.is_empty()).then(|| query_list(seeds))
+}
+
+impl RecommendationsEndpoint<Seeds> {
+    pub(crate) fn with_seeds(seeds: Seeds, market: Option<Market>) -> Self {
+        Self {
+            seed_artists: seed_list(&seeds.artists),
+            seed_genres: seed_list(&seeds.genres),
+            seed_tracks: seed_list(&seeds.tracks),
+            limit: None,
+            market,
+            features: None,
+            marker: PhantomData,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Serialize, IntoStaticStr)]
 #[serde(untagged)]
 #[serde(rename_all = "snake_case")]
@@ -108,56 +243,53 @@ pub enum Feature {
 pub struct TrackEndpoint {
     #[serde(skip)]
     pub(crate) id: String,
-    pub(crate) market: Option<String>,
+    pub(crate) market: Option<Market>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, TrackEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, TrackEndpoint> {
     #[doc = include_str!("../docs/market.md")]
-    pub fn market(mut self, market: impl Into<String>) -> Self {
-        self.endpoint.market = Some(market.into());
+    pub fn market(mut self, market: Market) -> Self {
+        self.endpoint.market = Some(market);
         self
     }
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<Track> {
-        self.spotify
-            .get(format!("/tracks/{}", self.endpoint.id), self.endpoint)
-            .await
+        self.spotify.get(self.endpoint.path(), self.endpoint).await
     }
 }
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct TracksEndpoint {
     pub(crate) ids: String,
-    pub(crate) market: Option<String>,
+    pub(crate) market: Option<Market>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, TracksEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, TracksEndpoint> {
     #[doc = include_str!("../docs/market.md")]
-    pub fn market(mut self, market: impl Into<String>) -> Self {
-        self.endpoint.market = Some(market.into());
+    pub fn market(mut self, market: Market) -> Self {
+        self.endpoint.market = Some(market);
         self
     }
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<Vec<Track>> {
-        self.spotify
-            .get("/tracks".to_owned(), self.endpoint)
+        self.get_chunked(50, |e| &mut e.ids)
             .await
-            .map(|t: Tracks| t.tracks)
+            .map(|t: Vec<Tracks>| t.into_iter().flat_map(|t| t.tracks).collect())
     }
 }
 
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct SavedTracksEndpoint {
-    pub(crate) market: Option<String>,
+    pub(crate) market: Option<Market>,
     pub(crate) limit: Option<Limit>,
     pub(crate) offset: Option<u32>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, SavedTracksEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, SavedTracksEndpoint> {
     #[doc = include_str!("../docs/market.md")]
-    pub fn market(mut self, market: impl Into<String>) -> Self {
-        self.endpoint.market = Some(market.into());
+    pub fn market(mut self, market: Market) -> Self {
+        self.endpoint.market = Some(market);
         self
     }
 
@@ -175,26 +307,123 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, SavedTracksEndpoint> {
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<Page<SavedTrack>> {
-        self.spotify
-            .get("/me/tracks".to_owned(), self.endpoint)
+        self.spotify.get(self.endpoint.path(), self.endpoint).await
+    }
+
+    /// Get all of the user's saved tracks, going through all of the pages.
+    pub async fn get_all(self) -> Result<Vec<SavedTrack>> {
+        self.get_all_added(..).await
+    }
+
+    /// Get all of the user's saved tracks that were added within `range`, e.g. `since..`
+    /// for the ones added since a given time.
+    ///
+    /// Saved tracks are listed newest first, so pages are only requested until the
+    /// first one added before the range.
+    pub async fn get_all_added(
+        self,
+        range: impl RangeBounds<DateTime<Utc>>,
+    ) -> Result<Vec<SavedTrack>> {
+        self.get_all_saved(range, |e| (&mut e.limit, &mut e.offset))
             .await
     }
 }
 
+// Generates the minimum, maximum and target setters of each tunable audio feature of the
+// recommendations, and `check_feature`, which `get` uses to reject values outside of a
+// feature's range (or NaN) before sending the request.
+macro_rules! tunables {
+    ($(
+        $name:ident: $ty:ident $(in $lo:literal..=$hi:literal)?,
+        $min:ident => $min_variant:ident,
+        $max:ident => $max_variant:ident,
+        $target:ident => $target_variant:ident;
+    )*) => {
+        $(
+            #[doc = concat!("The minimum ", tunables!(@describe $name $($lo, $hi)?))]
+            pub fn $min(self, $name: $ty) -> Self {
+                self.feature(Feature::$min_variant($name))
+            }
+
+            #[doc = concat!("The maximum ", tunables!(@describe $name $($lo, $hi)?))]
+            pub fn $max(self, $name: $ty) -> Self {
+                self.feature(Feature::$max_variant($name))
+            }
+
+            #[doc = concat!(
+                "The target ",
+                tunables!(@describe $name $($lo, $hi)?),
+                " The tracks closest to it are preferred."
+            )]
+            pub fn $target(self, $name: $ty) -> Self {
+                self.feature(Feature::$target_variant($name))
+            }
+        )*
+
+        fn check_feature(feature: Feature) -> Result<()> {
+            match feature {
+                $(
+                    Feature::$min_variant($name)
+                    | Feature::$max_variant($name)
+                    | Feature::$target_variant($name) => {
+                        if tunables!(@check $name $($lo, $hi)?) {
+                            Ok(())
+                        } else {
+                            Err(Error::InvalidArgument(format!(
+                                concat!("`{}` must be ", tunables!(@expected $($lo, $hi)?), ", not {}"),
+                                <&str>::from(feature),
+                                $name,
+                            )))
+                        }
+                    }
+                )*
+            }
+        }
+    };
+    (@describe $name:ident $($lo:literal, $hi:literal)?) => {
+        concat!(
+            "`",
+            stringify!($name),
+            "` of the recommended tracks",
+            $(
+                ", from `",
+                stringify!($lo),
+                "` to `",
+                stringify!($hi),
+                "`. Values outside of the range make the request fail with ",
+                "[`Error::InvalidArgument`]",
+            )?
+            "."
+        )
+    };
+    (@check $name:ident $lo:literal, $hi:literal) => {
+        ($lo..=$hi).contains(&$name)
+    };
+    (@check $name:ident) => {
+        f64::from($name).is_finite()
+    };
+    (@expected $lo:literal, $hi:literal) => {
+        concat!("from ", stringify!($lo), " to ", stringify!($hi))
+    };
+    (@expected) => {
+        "a finite number"
+    };
+}
+
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct RecommendationsEndpoint<S: SeedType> {
     pub(crate) seed_artists: Option<String>,
     pub(crate) seed_genres: Option<String>,
     pub(crate) seed_tracks: Option<String>,
     pub(crate) limit: Option<Limit<1, 100>>,
-    pub(crate) market: Option<String>,
+    pub(crate) market: Option<Market>,
     #[serde(flatten)]
     pub(crate) features: Option<HashMap<&'static str, Feature>>,
     #[serde(skip)]
     pub(crate) marker: PhantomData<S>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, RecommendationsEndpoint<SeedArtists>> {
+impl<F: AuthFlow> Builder<'_, F, RecommendationsEndpoint<SeedArtists>> {
     /// Up to 5 Spotify genre IDs used for seeding the recommendations.
     pub fn seed_genres<T: AsRef<str>>(mut self, genres: &[T]) -> Self {
         self.endpoint.seed_genres = Some(query_list(genres));
@@ -202,27 +431,27 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, RecommendationsEndpoint<SeedArt
     }
 
     /// Up to 5 Spotify track IDs used for seeding the recommendations.
-    pub fn seed_tracks<T: AsRef<str>>(mut self, track_ids: &[T]) -> Self {
-        self.endpoint.seed_tracks = Some(query_list(track_ids));
+    pub fn seed_tracks<T: AsId<TrackId>>(mut self, track_ids: &[T]) -> Self {
+        self.endpoint.seed_tracks = Some(query_list(&as_ids(track_ids)));
         self
     }
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, RecommendationsEndpoint<SeedGenres>> {
+impl<F: AuthFlow> Builder<'_, F, RecommendationsEndpoint<SeedGenres>> {
     /// Up to 5 Spotify artist IDs used for seeding the recommendations.
-    pub fn seed_artists<T: AsRef<str>>(mut self, artist_ids: &[T]) -> Self {
-        self.endpoint.seed_genres = Some(query_list(artist_ids));
+    pub fn seed_artists<T: AsId<ArtistId>>(mut self, artist_ids: &[T]) -> Self {
+        self.endpoint.seed_artists = Some(query_list(&as_ids(artist_ids)));
         self
     }
 
     /// Up to 5 Spotify track IDs used for seeding the recommendations.
-    pub fn seed_tracks<T: AsRef<str>>(mut self, track_ids: &[T]) -> Self {
-        self.endpoint.seed_tracks = Some(query_list(track_ids));
+    pub fn seed_tracks<T: AsId<TrackId>>(mut self, track_ids: &[T]) -> Self {
+        self.endpoint.seed_tracks = Some(query_list(&as_ids(track_ids)));
         self
     }
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, RecommendationsEndpoint<SeedTracks>> {
+impl<F: AuthFlow> Builder<'_, F, RecommendationsEndpoint<SeedTracks>> {
     /// Up to 5 Spotify genre IDs used for seeding the recommendations.
     pub fn seed_genres<T: AsRef<str>>(mut self, genres: &[T]) -> Self {
         self.endpoint.seed_genres = Some(query_list(genres));
@@ -230,13 +459,13 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, RecommendationsEndpoint<SeedTra
     }
 
     /// Up to 5 Spotify artist IDs used for seeding the recommendations.
-    pub fn seed_artists<T: AsRef<str>>(mut self, artist_ids: &[T]) -> Self {
-        self.endpoint.seed_genres = Some(query_list(artist_ids));
+    pub fn seed_artists<T: AsId<ArtistId>>(mut self, artist_ids: &[T]) -> Self {
+        self.endpoint.seed_artists = Some(query_list(&as_ids(artist_ids)));
         self
     }
 }
 
-impl<F: AuthFlow, V: Verifier, S: SeedType> Builder<'_, F, V, RecommendationsEndpoint<S>> {
+impl<F: AuthFlow, S: SeedType> Builder<'_, F, RecommendationsEndpoint<S>> {
     #[doc = include_str!("../docs/limit.md")]
     pub fn limit(mut self, limit: u32) -> Self {
         self.endpoint.limit = Some(Limit::new(limit));
@@ -244,27 +473,99 @@ impl<F: AuthFlow, V: Verifier, S: SeedType> Builder<'_, F, V, RecommendationsEnd
     }
 
     #[doc = include_str!("../docs/market.md")]
-    pub fn market(mut self, market: impl Into<String>) -> Self {
-        self.endpoint.market = Some(market.into());
+    pub fn market(mut self, market: Market) -> Self {
+        self.endpoint.market = Some(market);
         self
     }
 
     /// A list of [`Feature`]s. Read more about the available features
     /// [here](https://developer.spotify.com/documentation/web-api/reference/get-recommendations).
+    ///
+    /// This replaces any features set before, including by the typed setters such as
+    /// [`target_energy`](Self::target_energy). Like theirs, the values are checked against
+    /// the features' ranges when the request is sent.
     pub fn features(mut self, features: &[Feature]) -> Self {
         let features: HashMap<&'static str, Feature> = features
             .iter()
             .map(|f| (From::<Feature>::from(*f), *f))
             .collect();
-        dbg!(&features);
         self.endpoint.features = Some(features);
         self
     }
 
+    tunables! {
+        acousticness: f32 in 0.0..=1.0,
+            min_acousticness => MinAcousticness,
+            max_acousticness => MaxAcousticness,
+            target_acousticness => TargetAcousticness;
+        danceability: f32 in 0.0..=1.0,
+            min_danceability => MinDanceability,
+            max_danceability => MaxDanceability,
+            target_danceability => TargetDanceability;
+        duration_ms: u32,
+            min_duration_ms => MinDurationMs,
+            max_duration_ms => MaxDurationMs,
+            target_duration_ms => TargetDurationMs;
+        energy: f32 in 0.0..=1.0,
+            min_energy => MinEnergy,
+            max_energy => MaxEnergy,
+            target_energy => TargetEnergy;
+        instrumentalness: f32 in 0.0..=1.0,
+            min_instrumentalness => MinInstrumentalness,
+            max_instrumentalness => MaxInstrumentalness,
+            target_instrumentalness => TargetInstrumentalness;
+        key: u32 in 0..=11,
+            min_key => MinKey,
+            max_key => MaxKey,
+            target_key => TargetKey;
+        liveness: f32 in 0.0..=1.0,
+            min_liveness => MinLiveness,
+            max_liveness => MaxLiveness,
+            target_liveness => TargetLiveness;
+        loudness: f32,
+            min_loudness => MinLoudness,
+            max_loudness => MaxLoudness,
+            target_loudness => TargetLoudness;
+        mode: u32 in 0..=1,
+            min_mode => MinMode,
+            max_mode => MaxMode,
+            target_mode => TargetMode;
+        popularity: u32 in 0..=100,
+            min_popularity => MinPopularity,
+            max_popularity => MaxPopularity,
+            target_popularity => TargetPopularity;
+        speechiness: f32 in 0.0..=1.0,
+            min_speechiness => MinSpeechiness,
+            max_speechiness => MaxSpeechiness,
+            target_speechiness => TargetSpeechiness;
+        tempo: f32,
+            min_tempo => MinTempo,
+            max_tempo => MaxTempo,
+            target_tempo => TargetTempo;
+        time_signature: u32 in 3..=7,
+            min_time_signature => MinTimeSignature,
+            max_time_signature => MaxTimeSignature,
+            target_time_signature => TargetTimeSignature;
+        valence: f32 in 0.0..=1.0,
+            min_valence => MinValence,
+            max_valence => MaxValence,
+            target_valence => TargetValence;
+    }
+
+    fn feature(mut self, feature: Feature) -> Self {
+        self.endpoint
+            .features
+            .get_or_insert_with(HashMap::new)
+            .insert(feature.into(), feature);
+        self
+    }
+
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<Recommendations> {
-        self.spotify
-            .get("/recommendations".to_owned(), self.endpoint)
-            .await
+        for feature in self.endpoint.features.iter().flat_map(HashMap::values) {
+            Self::check_feature(*feature)?;
+        }
+
+        self.spotify.get(self.endpoint.path(), self.endpoint).await
     }
 }
@@ -1,8 +1,11 @@
-use serde::Serialize;
+use std::marker::PhantomData;
+
+use serde::{de::DeserializeOwned, Serialize};
 use serde_json::json;
 
 use crate::{
-    auth::{AuthFlow, Verifier},
+    auth::AuthFlow,
+    chunked,
     client::Body,
     error::Result,
     model::{
@@ -13,23 +16,76 @@ use crate::{
     query_list, Nil,
 };
 
-use super::{Builder, Endpoint, Limit, PrivateEndpoint};
+#[cfg(feature = "playlists")]
+use super::PrivateEndpoint;
+use super::{Builder, Endpoint, Limit};
 
-impl Endpoint for UserTopItemsEndpoint {}
-impl Endpoint for FollowPlaylistBuilder {}
-impl Endpoint for FollowedArtistsBuilder {}
-impl Endpoint for FollowUserOrArtistEndpoint {}
+impl<T> Endpoint for UserTopItemsEndpoint<T> {
+    fn path(&self) -> String {
+        format!("/me/top/{}", self.r#type)
+    }
+}
 
-#[derive(Clone, Debug, Default, Serialize)]
-pub struct UserTopItemsEndpoint {
+#[cfg(feature = "playlists")]
+impl Endpoint for FollowPlaylistEndpoint {
+    fn path(&self) -> String {
+        format!("/playlists/{}/followers", self.id)
+    }
+
+    fn query(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
+}
+
+impl Endpoint for FollowedArtistsBuilder {
+    fn path(&self) -> String {
+        "/me/following".to_owned()
+    }
+}
+
+impl Endpoint for FollowUserOrArtistEndpoint {
+    fn path(&self) -> String {
+        "/me/following".to_owned()
+    }
+}
+
+impl_into_future! {
+    [T: DeserializeOwned + Send + 's,] UserTopItemsEndpoint<T> => Page<T>, get;
+    FollowedArtistsBuilder => CursorPage<Artist>, get;
+}
+
+#[cfg(feature = "playlists")]
+impl_into_future! {
+    FollowPlaylistEndpoint => Nil, send;
+}
+
+/// Endpoint for getting the current user's top artists or tracks, which are returned
+/// as `T`: [`Artist`], [`Track`](crate::model::track::Track), or [`UserItem`] when the
+/// type is only known at runtime.
+#[derive(Clone, Debug, Serialize)]
+pub struct UserTopItemsEndpoint<T = UserItem> {
     #[serde(skip)]
     pub(crate) r#type: UserItemType,
     pub(crate) time_range: Option<TimeRange>,
     pub(crate) limit: Option<Limit>,
     pub(crate) offset: Option<u32>,
+    #[serde(skip)]
+    pub(crate) item: PhantomData<fn() -> T>,
+}
+
+impl<T> UserTopItemsEndpoint<T> {
+    pub(crate) fn new(r#type: UserItemType, limit: Option<u32>) -> Self {
+        Self {
+            r#type,
+            time_range: None,
+            limit: limit.map(Limit::new),
+            offset: None,
+            item: PhantomData,
+        }
+    }
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, UserTopItemsEndpoint> {
+impl<F: AuthFlow, T: DeserializeOwned> Builder<'_, F, UserTopItemsEndpoint<T>> {
     /// The time frame of the computed affinities.
     pub fn time_range(mut self, time_range: TimeRange) -> Self {
         self.endpoint.time_range = Some(time_range);
@@ -49,22 +105,26 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, UserTopItemsEndpoint> {
     }
 
     #[doc = include_str!("../docs/send.md")]
-    pub async fn get(self) -> Result<Page<UserItem>> {
-        self.spotify
-            .get(format!("/me/top/{}", self.endpoint.r#type), self.endpoint)
-            .await
+    pub async fn get(self) -> Result<Page<T>> {
+        self.spotify.get(self.endpoint.path(), self.endpoint).await
     }
 }
 
+#[cfg(feature = "playlists")]
+#[deprecated(note = "renamed to `FollowPlaylistEndpoint`, like the other endpoints")]
+pub type FollowPlaylistBuilder = FollowPlaylistEndpoint;
+
+#[cfg(feature = "playlists")]
 #[derive(Clone, Debug, Default, Serialize)]
-pub struct FollowPlaylistBuilder {
+pub struct FollowPlaylistEndpoint {
     #[serde(skip)]
     pub(crate) id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) public: Option<bool>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, FollowPlaylistBuilder> {
+#[cfg(feature = "playlists")]
+impl<F: AuthFlow> Builder<'_, F, FollowPlaylistEndpoint> {
     /// If set to `true`, the playlist will be included in the user's
     /// public playlists. Defaults to `true`.
     pub fn public(mut self, public: bool) -> Self {
@@ -75,10 +135,7 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, FollowPlaylistBuilder> {
     #[doc = include_str!("../docs/send.md")]
     pub async fn send(self) -> Result<Nil> {
         self.spotify
-            .put(
-                format!("/playlists/{}/followers", self.endpoint.id),
-                self.endpoint.json(),
-            )
+            .put(self.endpoint.path(), self.endpoint.json())
             .await
     }
 }
@@ -90,7 +147,7 @@ pub struct FollowedArtistsBuilder {
     pub(crate) limit: Option<Limit>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, FollowedArtistsBuilder> {
+impl<F: AuthFlow> Builder<'_, F, FollowedArtistsBuilder> {
     /// The last artist ID retrieved from the previous request.
     pub fn after(mut self, artist_id: impl Into<String>) -> Self {
         self.endpoint.after = Some(artist_id.into());
@@ -106,7 +163,7 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, FollowedArtistsBuilder> {
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<CursorPage<Artist>> {
         self.spotify
-            .get("/me/following".to_owned(), self.endpoint)
+            .get(self.endpoint.path(), self.endpoint)
             .await
             .map(|a: PagedArtists| a.artists)
     }
@@ -119,37 +176,49 @@ pub struct FollowUserOrArtistEndpoint {
     pub(crate) ids: Vec<String>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, FollowUserOrArtistEndpoint> {
+impl<F: AuthFlow> Builder<'_, F, FollowUserOrArtistEndpoint> {
     #[doc = include_str!("../docs/send.md")]
     pub async fn follow(self) -> Result<Nil> {
-        self.spotify
-            .put(
+        let ids: Vec<_> = self.endpoint.ids.iter().map(String::as_str).collect();
+
+        chunked(&ids, 50, |ids| {
+            self.spotify.put(
                 format!("/me/following?type={}", self.endpoint.r#type),
-                Body::Json(json!({ "ids": self.endpoint.ids })),
+                Body::Json(json!({ "ids": ids })),
             )
-            .await
+        })
+        .await
+        .map(|_: Vec<Nil>| Nil)
     }
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn unfollow(self) -> Result<Nil> {
-        self.spotify
-            .delete(
+        let ids: Vec<_> = self.endpoint.ids.iter().map(String::as_str).collect();
+
+        chunked(&ids, 50, |ids| {
+            self.spotify.delete(
                 format!("/me/following?type={}", self.endpoint.r#type),
-                Body::Json(json!({ "ids": self.endpoint.ids })),
+                Body::Json(json!({ "ids": ids })),
             )
-            .await
+        })
+        .await
+        .map(|_: Vec<Nil>| Nil)
     }
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn check(self) -> Result<Vec<bool>> {
-        self.spotify
-            .get(
+        let ids: Vec<_> = self.endpoint.ids.iter().map(String::as_str).collect();
+
+        chunked(&ids, 50, |ids| {
+            self.spotify.get(
                 "/me/following/contains".to_owned(),
                 [
-                    ("type", self.endpoint.r#type),
-                    ("ids", query_list(&self.endpoint.ids)),
+                    ("type", self.endpoint.r#type.clone()),
+                    ("ids", query_list(ids)),
                 ],
             )
-            .await
+        })
+        .await
+        .map(|following: Vec<Vec<bool>>| following.concat())
     }
 }
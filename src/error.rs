@@ -1,3 +1,5 @@
+use std::{sync::Arc, time::Duration};
+
 use oauth2::{basic::BasicErrorResponseType, RequestTokenError, StandardErrorResponse};
 
 use serde::Deserialize;
@@ -12,14 +14,69 @@ pub enum Error {
     #[error("An error occured during authentication: {description}")]
     Authentication { kind: Kind, description: String },
 
+    /// The client was configured incorrectly, e.g. with an invalid URL.
+    #[error("The client configuration is invalid: {0}")]
+    Configuration(String),
+
     /// The token has expired and auto-refresh is turned off.
     #[error("The access token has expired and auto-refresh is turned off.")]
     ExpiredToken,
 
+    /// A response couldn't be deserialised into the model it should match, e.g. because
+    /// Spotify left out a field the model requires.
+    ///
+    /// `body_snippet` is the start of the response's body, to see what was returned.
+    #[error("Failed to deserialise the response from {endpoint}: {source}")]
+    Deserialization {
+        endpoint: String,
+        body_snippet: String,
+        source: Arc<serde_json::Error>,
+    },
+
+    /// Playback couldn't be transferred to a device, because it has no ID or isn't one of
+    /// the user's available devices.
+    #[error("Playback can't be transferred to the device: {0}")]
+    DeviceUnavailable(String),
+
     /// HTTP error returned from the underlying HTTP client.
     #[error("{0}")]
     Http(String),
 
+    /// Spotify responded with an error that isn't in its usual JSON format, e.g. an HTML
+    /// or plain text page from a server error.
+    ///
+    /// `body` is the response's body as is.
+    #[error("The Spotify API returned an unexpected {status} response.")]
+    HttpStatus { status: u16, body: String },
+
+    /// An image is too large to be uploaded as a playlist cover.
+    ///
+    /// `size` is the size of the image once Base64 encoded, in bytes, which can't be more
+    /// than `max`.
+    #[error("The image is {size} bytes once Base64 encoded, but can't be more than {max}.")]
+    ImageTooLarge { size: usize, max: usize },
+
+    /// An argument given to a request is invalid, e.g. because it's outside of the range
+    /// Spotify accepts, so the request wasn't sent.
+    #[error("{0}")]
+    InvalidArgument(String),
+
+    /// A Spotify ID, URI or URL couldn't be parsed.
+    #[error("{0}")]
+    InvalidId(String),
+
+    /// A locale couldn't be parsed from a language and country code.
+    #[error("{0}")]
+    InvalidLocale(String),
+
+    /// A market couldn't be parsed from a country code.
+    #[error("{0}")]
+    InvalidMarket(String),
+
+    /// The seeds given for recommendations aren't valid, e.g. because there are too many.
+    #[error("{0}")]
+    InvalidSeeds(String),
+
     /// The (CSRF) state parameter supplied is not the same as the one initially generated and sent to the server.
     ///
     /// Learn more about CSRF [here](https://datatracker.ietf.org/doc/html/rfc6749#section-10.12).
@@ -28,6 +85,24 @@ pub enum Error {
     )]
     InvalidStateParameter,
 
+    /// Adding several items to the queue failed part of the way through.
+    ///
+    /// `queued` is how many of the items, from the start, were added before `source`
+    /// stopped the rest from being added.
+    #[error("Only the first {queued} items were added to the queue: {source}")]
+    PartiallyQueued {
+        queued: usize,
+        #[source]
+        source: Box<Error>,
+    },
+
+    /// The user hasn't granted the client a scope the request needs, so it wasn't sent.
+    ///
+    /// Only checked when the token says which scopes were granted, which tokens from the
+    /// authorisation code flows do.
+    #[error("The request needs the {0} scope, which the user hasn't granted.")]
+    MissingScope(String),
+
     /// The client has not yet been authenticated.
     #[error("The client has not been authenticated.")]
     NotAuthenticated,
@@ -36,13 +111,80 @@ pub enum Error {
     #[error("The access token has has expired and refreshing it is not available in the current authorisation flow.")]
     RefreshUnavailable,
 
+    /// The request was rate limited by Spotify, and wasn't retried (or ran out of retries).
+    ///
+    /// `retry_after` is how long Spotify asked to wait before trying again, if it said.
+    /// See [`RetryPolicy`](crate::client::RetryPolicy) to retry automatically.
+    #[error("The request was rate limited by the Spotify API.")]
+    RateLimited { retry_after: Option<Duration> },
+
+    /// The token couldn't be loaded from or saved to a [`TokenStore`](crate::token_store::TokenStore).
+    #[error("The token couldn't be loaded or saved: {0}")]
+    TokenStore(String),
+
+    /// An image can't be uploaded as a playlist cover, as it isn't a JPEG (or, with the
+    /// `image` feature, a PNG that can be converted into one).
+    #[error("Playlist cover images have to be JPEGs.")]
+    UnsupportedImageFormat,
+
     /// An error returned from Spotify.
+    ///
+    /// `reason` says why a player request failed, e.g. because the user doesn't have
+    /// Premium, and is `None` for other requests.
     #[error("Error returned from the Spotify API: {status} {message}")]
-    Spotify { status: u16, message: String },
+    Spotify {
+        status: u16,
+        message: String,
+        reason: Option<PlayerErrorReason>,
+    },
+}
+
+/// Why a request to the player failed, as given by Spotify.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[non_exhaustive]
+pub enum PlayerErrorReason {
+    /// There's no previous track in the context.
+    NoPrevTrack,
+    /// There's no next track in the context.
+    NoNextTrack,
+    /// The requested track doesn't exist.
+    NoSpecificTrack,
+    /// Playback is already paused.
+    AlreadyPaused,
+    /// Playback isn't paused.
+    NotPaused,
+    /// Playback isn't happening on the local device.
+    NotPlayingLocally,
+    /// No track is playing.
+    NotPlayingTrack,
+    /// No context is playing.
+    NotPlayingContext,
+    /// The context is endless, so it can't be shuffled.
+    EndlessContext,
+    /// The context doesn't allow the action.
+    ContextDisallow,
+    /// The action would play what's already playing.
+    AlreadyPlaying,
+    /// The user is rate limited because they changed tracks too often.
+    RateLimited,
+    /// The context can't be controlled remotely.
+    RemoteControlDisallow,
+    /// The device can't be controlled.
+    DeviceNotControllable,
+    /// The device's volume can't be controlled.
+    VolumeControlDisallow,
+    /// There's no active device, so there's nothing to control.
+    NoActiveDevice,
+    /// The action needs the user to have Spotify Premium.
+    PremiumRequired,
+    /// Spotify didn't say why, or gave a reason this version of the crate doesn't know.
+    #[serde(other)]
+    Unknown,
 }
 
 #[derive(Deserialize)]
-pub(crate) struct SpotifyError {
+struct SpotifyError {
     error: Details,
 }
 
@@ -50,6 +192,8 @@ pub(crate) struct SpotifyError {
 struct Details {
     status: u16,
     message: String,
+    #[serde(default)]
+    reason: Option<PlayerErrorReason>,
 }
 
 /// The authentication error kind.
@@ -110,6 +254,112 @@ impl
     }
 }
 
+impl Error {
+    /// The HTTP status of the response the error came from, if it came from Spotify.
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Self::Spotify { status, .. } | Self::HttpStatus { status, .. } => Some(*status),
+            Self::RateLimited { .. } => Some(429),
+            Self::PartiallyQueued { source, .. } => source.status(),
+            _ => None,
+        }
+    }
+
+    /// Why a player request failed, if Spotify said.
+    ///
+    #[cfg_attr(feature = "player", doc = "```no_run")]
+    #[cfg_attr(not(feature = "player"), doc = "```ignore")]
+    /// # async fn example(spotify: &spotify_rs::AuthCodeClient) -> spotify_rs::SpotifyResult<()> {
+    /// use spotify_rs::PlayerErrorReason;
+    ///
+    /// match spotify.pause_playback(None).await {
+    ///     Err(e) if e.reason() == Some(PlayerErrorReason::AlreadyPaused) => {}
+    ///     Err(e) if e.reason() == Some(PlayerErrorReason::PremiumRequired) => {
+    ///         println!("Controlling playback needs Spotify Premium.");
+    ///     }
+    ///     result => {
+    ///         result?;
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn reason(&self) -> Option<PlayerErrorReason> {
+        match self {
+            Self::Spotify { reason, .. } => *reason,
+            Self::PartiallyQueued { source, .. } => source.reason(),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if sending the same request again later may succeed, i.e. if it was
+    /// rate limited or Spotify had a temporary server error.
+    ///
+    /// Only retry requests that don't change anything, or that can safely be repeated,
+    /// as a server error doesn't mean the change wasn't made.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::RateLimited { .. } => true,
+            Self::Spotify { status, reason, .. } => {
+                is_retryable_status(*status) || *reason == Some(PlayerErrorReason::RateLimited)
+            }
+            Self::HttpStatus { status, .. } => is_retryable_status(*status),
+            Self::PartiallyQueued { source, .. } => source.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if the error is because the client isn't (or is no longer)
+    /// authorised, e.g. the token expired or was revoked, or lacks a scope, so the user
+    /// has to authorise the client again.
+    pub fn is_auth_error(&self) -> bool {
+        match self {
+            Self::PartiallyQueued { source, .. } => source.is_auth_error(),
+            _ => matches!(
+                self,
+                Self::Authentication { .. }
+                    | Self::ExpiredToken
+                    | Self::InvalidStateParameter
+                    | Self::MissingScope(_)
+                    | Self::NotAuthenticated
+                    | Self::RefreshUnavailable
+                    | Self::Spotify { status: 401, .. }
+                    | Self::HttpStatus { status: 401, .. }
+            ),
+        }
+    }
+
+    // How much of a body that failed to deserialise is kept in the error.
+    const SNIPPET_LEN: usize = 200;
+
+    // An error response, which is usually JSON, but can be anything when Spotify's
+    // servers fail before getting to the API.
+    pub(crate) fn response(status: u16, body: &[u8]) -> Self {
+        match serde_json::from_slice::<SpotifyError>(body) {
+            Ok(error) => error.into(),
+            Err(_) => Self::HttpStatus {
+                status,
+                body: String::from_utf8_lossy(body).into_owned(),
+            },
+        }
+    }
+
+    pub(crate) fn deserialization(endpoint: &str, body: &[u8], source: serde_json::Error) -> Self {
+        let body = String::from_utf8_lossy(body);
+        let mut end = body.len().min(Self::SNIPPET_LEN);
+
+        while !body.is_char_boundary(end) {
+            end -= 1;
+        }
+
+        Self::Deserialization {
+            endpoint: endpoint.to_owned(),
+            body_snippet: body[..end].to_owned(),
+            source: Arc::new(source),
+        }
+    }
+}
+
 impl From<reqwest::Error> for Error {
     fn from(value: reqwest::Error) -> Self {
         Self::Http(value.to_string())
@@ -121,6 +371,12 @@ impl From<SpotifyError> for Error {
         Self::Spotify {
             status: value.error.status,
             message: value.error.message,
+            reason: value.error.reason,
         }
     }
 }
+
+// Rate limiting, and the same server errors the retry policy retries.
+fn is_retryable_status(status: u16) -> bool {
+    matches!(status, 429 | 500 | 502 | 503 | 504)
+}
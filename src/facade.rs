@@ -0,0 +1,82 @@
+// The client behind the facades for other languages, which can't use the generic,
+// typestate `Client`, so any of the authorisation flows is wrapped in a single type.
+
+use crate::{
+    auth::{AuthCodeFlow, AuthCodePkceFlow, ClientCredsFlow},
+    client::{AuthCodeClient, AuthCodePkceClient, ClientBuilder, ClientCredsClient},
+    error::Result,
+};
+
+pub(crate) enum AnyClient {
+    AuthCode(AuthCodeClient),
+    AuthCodePkce(AuthCodePkceClient),
+    ClientCreds(ClientCredsClient),
+}
+
+impl AnyClient {
+    /// Authenticate using the Client Credentials Flow.
+    pub(crate) async fn client_credentials(
+        client_id: String,
+        client_secret: String,
+    ) -> Result<Self> {
+        let client = ClientBuilder::new(ClientCredsFlow::new(client_id, client_secret))
+            .authenticate()
+            .await?;
+
+        Ok(Self::ClientCreds(client))
+    }
+
+    /// Authenticate using a refresh token obtained from the Authorisation Code Flow,
+    /// or from the Authorisation Code Flow with PKCE if there is no client secret.
+    pub(crate) async fn from_refresh_token(
+        client_id: String,
+        client_secret: Option<String>,
+        refresh_token: String,
+    ) -> Result<Self> {
+        let scopes: [&str; 0] = [];
+
+        Ok(match client_secret {
+            Some(secret) => Self::AuthCode(
+                ClientBuilder::new(AuthCodeFlow::new(client_id, secret, scopes))
+                    .auto_refresh(true)
+                    .from_refresh_token(refresh_token)
+                    .await?,
+            ),
+            None => Self::AuthCodePkce(
+                ClientBuilder::new(AuthCodePkceFlow::new(client_id, scopes))
+                    .auto_refresh(true)
+                    .from_refresh_token(refresh_token)
+                    .await?,
+            ),
+        })
+    }
+}
+
+// Runs the same code for every kind of client (an `Arc<AnyClient>`). The client's methods
+// only need a shared reference, so concurrent calls aren't serialised behind a lock.
+macro_rules! with_client {
+    ($inner:expr, $client:ident => $body:expr) => {
+        match &*$inner {
+            $crate::facade::AnyClient::AuthCode($client) => $body,
+            $crate::facade::AnyClient::AuthCodePkce($client) => $body,
+            $crate::facade::AnyClient::ClientCreds($client) => $body,
+        }
+    };
+}
+
+// Like `with_client`, but only for the clients that are authorised by a user.
+// Evaluates `$unauthorised` for the Client Credentials Flow instead.
+#[cfg(feature = "player")]
+macro_rules! with_user_client {
+    ($inner:expr, $client:ident => $body:expr, else $unauthorised:expr) => {
+        match &*$inner {
+            $crate::facade::AnyClient::AuthCode($client) => $body,
+            $crate::facade::AnyClient::AuthCodePkce($client) => $body,
+            $crate::facade::AnyClient::ClientCreds(_) => $unauthorised,
+        }
+    };
+}
+
+pub(crate) use with_client;
+#[cfg(feature = "player")]
+pub(crate) use with_user_client;
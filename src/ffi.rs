@@ -0,0 +1,293 @@
+//! A simplified facade of the client for other languages, exported with
+//! [UniFFI](https://mozilla.github.io/uniffi-rs).
+//!
+//! The generic, typestate [`Client`](crate::client::Client) can't cross the FFI boundary,
+//! so this module wraps it in a single [`SpotifyClient`] object, which covers every
+//! authorisation flow and returns flat records instead of the full models.
+//!
+//! To generate bindings, create a `cdylib` crate that depends on this one with the
+//! `uniffi` feature and re-exports the scaffolding, then run `uniffi-bindgen` on it:
+//!
+//! ```ignore
+//! // lib.rs of the cdylib crate
+//! spotify_rs::uniffi_reexport_scaffolding!();
+//! ```
+//!
+//! All of the methods are async, and run on a Tokio runtime managed by UniFFI.
+
+use std::sync::Arc;
+
+#[cfg(feature = "player")]
+use crate::facade::with_user_client;
+use crate::{
+    error::Error,
+    facade::{with_client, AnyClient},
+    model::{album::Album, artist::Artist, track::Track, Image},
+};
+
+/// A Spotify client, authenticated with any of the authorisation flows.
+#[derive(uniffi::Object)]
+pub struct SpotifyClient {
+    inner: Arc<AnyClient>,
+}
+
+#[uniffi::export(async_runtime = "tokio")]
+impl SpotifyClient {
+    /// Authenticate using the Client Credentials Flow, which can't access user information.
+    #[uniffi::constructor]
+    pub async fn client_credentials(
+        client_id: String,
+        client_secret: String,
+    ) -> Result<Arc<Self>, SpotifyError> {
+        let client = AnyClient::client_credentials(client_id, client_secret).await?;
+        Ok(Self::new(client))
+    }
+
+    /// Authenticate using a refresh token obtained from the Authorisation Code Flow,
+    /// or from the Authorisation Code Flow with PKCE if there is no client secret.
+    ///
+    /// The access token is refreshed automatically when it expires.
+    #[uniffi::constructor]
+    pub async fn from_refresh_token(
+        client_id: String,
+        client_secret: Option<String>,
+        refresh_token: String,
+    ) -> Result<Arc<Self>, SpotifyError> {
+        let client = AnyClient::from_refresh_token(client_id, client_secret, refresh_token).await?;
+        Ok(Self::new(client))
+    }
+
+    /// The current access token.
+    pub async fn access_token(&self) -> String {
+        with_client!(self.inner, client => client.access_token())
+    }
+
+    /// The current refresh token, if there is one.
+    pub async fn refresh_token(&self) -> Option<String> {
+        with_client!(self.inner, client => client.refresh_token())
+    }
+
+    /// Get a track.
+    pub async fn track(&self, id: String) -> Result<TrackInfo, SpotifyError> {
+        let track = with_client!(self.inner, client => client.track(id).get().await)?;
+        Ok(track.into())
+    }
+
+    /// Get an album.
+    pub async fn album(&self, id: String) -> Result<AlbumInfo, SpotifyError> {
+        let album = with_client!(self.inner, client => client.album(id).get().await)?;
+        Ok(album.into())
+    }
+
+    /// Get an artist.
+    pub async fn artist(&self, id: String) -> Result<ArtistInfo, SpotifyError> {
+        let artist = with_client!(self.inner, client => client.artist(id).get().await)?;
+        Ok(artist.into())
+    }
+}
+
+#[cfg(feature = "search")]
+#[uniffi::export(async_runtime = "tokio")]
+impl SpotifyClient {
+    /// Search for tracks.
+    pub async fn search_tracks(
+        &self,
+        query: String,
+        limit: u32,
+    ) -> Result<Vec<TrackInfo>, SpotifyError> {
+        use crate::model::search::Item;
+
+        let results = with_client!(self.inner, client => {
+            client.search(query, &[Item::Track]).limit(limit).get().await
+        })?;
+
+        Ok(results
+            .tracks
+            .map(|page| page.items.into_iter().map(Into::into).collect())
+            .unwrap_or_default())
+    }
+}
+
+#[cfg(feature = "player")]
+#[uniffi::export(async_runtime = "tokio")]
+impl SpotifyClient {
+    /// Resume playback on the user's active device.
+    pub async fn resume_playback(&self) -> Result<(), SpotifyError> {
+        with_user_client!(self.inner, client => {
+            client.start_playback().send().await.map_err(Into::into)
+        }, else Err(unauthorised()))?;
+        Ok(())
+    }
+
+    /// Pause playback on the user's active device.
+    pub async fn pause_playback(&self) -> Result<(), SpotifyError> {
+        with_user_client!(self.inner, client => {
+            client.pause_playback(None).await.map_err(Into::into)
+        }, else Err(unauthorised()))?;
+        Ok(())
+    }
+
+    /// Skip to the next item in the user's queue.
+    pub async fn skip_to_next(&self) -> Result<(), SpotifyError> {
+        with_user_client!(self.inner, client => {
+            client.skip_to_next(None).await.map_err(Into::into)
+        }, else Err(unauthorised()))?;
+        Ok(())
+    }
+
+    /// Skip to the previous item in the user's queue.
+    pub async fn skip_to_previous(&self) -> Result<(), SpotifyError> {
+        with_user_client!(self.inner, client => {
+            client.skip_to_previous(None).await.map_err(Into::into)
+        }, else Err(unauthorised()))?;
+        Ok(())
+    }
+}
+
+impl SpotifyClient {
+    fn new(inner: AnyClient) -> Arc<Self> {
+        Arc::new(Self {
+            inner: Arc::new(inner),
+        })
+    }
+}
+
+/// A track, flattened for use from other languages.
+#[derive(Clone, Debug, uniffi::Record)]
+pub struct TrackInfo {
+    pub id: String,
+    pub name: String,
+    pub artists: Vec<String>,
+    pub album: String,
+    pub duration_ms: u32,
+    pub explicit: bool,
+    pub uri: String,
+    pub image_url: Option<String>,
+}
+
+/// An album, flattened for use from other languages.
+#[derive(Clone, Debug, uniffi::Record)]
+pub struct AlbumInfo {
+    pub id: String,
+    pub name: String,
+    pub artists: Vec<String>,
+    pub release_date: String,
+    pub total_tracks: u32,
+    pub uri: String,
+    pub image_url: Option<String>,
+}
+
+/// An artist, flattened for use from other languages.
+#[derive(Clone, Debug, uniffi::Record)]
+pub struct ArtistInfo {
+    pub id: String,
+    pub name: String,
+    pub genres: Vec<String>,
+    pub popularity: u32,
+    pub uri: String,
+    pub image_url: Option<String>,
+}
+
+/// An error returned by a [`SpotifyClient`].
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+pub enum SpotifyError {
+    /// An error returned from the Spotify API.
+    #[error("Error returned from the Spotify API: {status} {message}")]
+    Api { status: u16, message: String },
+    /// The client couldn't be authenticated, or isn't allowed to use the endpoint.
+    #[error("{message}")]
+    Auth { message: String },
+    /// Any other error, such as a network error.
+    #[error("{message}")]
+    Other { message: String },
+}
+
+impl From<Error> for SpotifyError {
+    fn from(err: Error) -> Self {
+        let message = err.to_string();
+
+        match err {
+            Error::Spotify {
+                status, message, ..
+            } => Self::Api { status, message },
+            Error::RateLimited { .. } => Self::Api {
+                status: 429,
+                message,
+            },
+            Error::Authentication { .. }
+            | Error::ExpiredToken
+            | Error::InvalidStateParameter
+            | Error::MissingScope(_)
+            | Error::NotAuthenticated
+            | Error::RefreshUnavailable => Self::Auth { message },
+            Error::Configuration(_)
+            | Error::Deserialization { .. }
+            | Error::DeviceUnavailable(_)
+            | Error::Http(_)
+            | Error::HttpStatus { .. }
+            | Error::ImageTooLarge { .. }
+            | Error::InvalidArgument(_)
+            | Error::InvalidId(_)
+            | Error::InvalidLocale(_)
+            | Error::InvalidMarket(_)
+            | Error::InvalidSeeds(_)
+            | Error::PartiallyQueued { .. }
+            | Error::TokenStore(_)
+            | Error::UnsupportedImageFormat => Self::Other { message },
+        }
+    }
+}
+
+impl From<Track> for TrackInfo {
+    fn from(track: Track) -> Self {
+        Self {
+            image_url: largest_image(&track.album.images),
+            id: track.id,
+            name: track.name,
+            artists: track.artists.into_iter().map(|a| a.name).collect(),
+            album: track.album.name,
+            duration_ms: track.duration_ms,
+            explicit: track.explicit,
+            uri: track.uri,
+        }
+    }
+}
+
+impl From<Album> for AlbumInfo {
+    fn from(album: Album) -> Self {
+        Self {
+            image_url: largest_image(&album.images),
+            id: album.id,
+            name: album.name,
+            artists: album.artists.into_iter().map(|a| a.name).collect(),
+            release_date: album.release_date,
+            total_tracks: album.total_tracks,
+            uri: album.uri,
+        }
+    }
+}
+
+impl From<Artist> for ArtistInfo {
+    fn from(artist: Artist) -> Self {
+        Self {
+            image_url: largest_image(&artist.images),
+            id: artist.id,
+            name: artist.name,
+            genres: artist.genres,
+            popularity: artist.popularity,
+            uri: artist.uri,
+        }
+    }
+}
+
+#[cfg(feature = "player")]
+fn unauthorised() -> SpotifyError {
+    SpotifyError::Auth {
+        message: "This endpoint requires a user to authorise the client.".to_owned(),
+    }
+}
+
+// Spotify lists images widest first.
+fn largest_image(images: &[Image]) -> Option<String> {
+    images.first().map(|i| i.url.clone())
+}
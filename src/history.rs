@@ -0,0 +1,281 @@
+//! Archiving the user's listening history.
+//!
+//! Spotify only keeps the user's 50 most recently played tracks, so a [`HistoryRecorder`]
+//! polls them at an interval and passes the plays it hasn't seen before to a
+//! [`HistorySink`], oldest first, to be kept for good. Plays are told apart by when they
+//! were played, so each one is recorded once, even though consecutive polls overlap.
+//!
+//! [`JsonlSink`] and [`CsvSink`] write the plays to a file (or anything else that
+//! implements [`Write`]); other sinks can store them in a database, send them to a
+//! scrobbling service, etc.
+//!
+//! Like the client, the recorder isn't tied to an async runtime, so it's given the
+//! function to wait with, such as `tokio::time::sleep`.
+//!
+//! ```no_run
+//! use std::{fs::OpenOptions, time::Duration};
+//! use spotify_rs::{
+//!     history::{HistoryRecorder, JsonlSink},
+//!     AuthCodeClient,
+//! };
+//! # async fn sleep(_: Duration) {}
+//!
+//! # async fn example(spotify: &AuthCodeClient) -> Result<(), Box<dyn std::error::Error>> {
+//! let file = OpenOptions::new()
+//!     .create(true)
+//!     .append(true)
+//!     .open("history.jsonl")?;
+//!
+//! let mut recorder = HistoryRecorder::new(JsonlSink::new(file));
+//! recorder.run(spotify, Duration::from_secs(10 * 60), sleep).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{future::Future, io::Write, time::Duration};
+
+use chrono::{DateTime, Utc};
+use futures_util::StreamExt;
+use thiserror::Error;
+
+use crate::{
+    auth::{AuthFlow, Authorised, Token},
+    client::Client,
+    error::Error,
+    model::player::PlayHistory,
+};
+
+/// A destination for the plays recorded by a [`HistoryRecorder`].
+pub trait HistorySink {
+    /// The error returned when recording fails.
+    type Error;
+
+    /// Record plays that haven't been recorded before, oldest first.
+    fn record(&mut self, plays: &[PlayHistory]) -> impl Future<Output = Result<(), Self::Error>>;
+}
+
+/// An error returned by a [`HistoryRecorder`].
+#[derive(Debug, Error)]
+pub enum HistoryError<E> {
+    /// The recently played tracks couldn't be requested.
+    #[error("Failed to get the recently played tracks: {0}")]
+    Spotify(#[from] Error),
+
+    /// The sink failed to record the plays.
+    #[error("Failed to record the plays")]
+    Sink(#[source] E),
+}
+
+/// Polls the user's recently played tracks and records the new plays to a [`HistorySink`].
+///
+/// The recorder remembers when the last play it recorded was played, and only records
+/// plays made after it. To pick up where a previous recorder left off, e.g. after a
+/// restart, give it that time with [`since`](Self::since).
+#[derive(Debug)]
+pub struct HistoryRecorder<S: HistorySink> {
+    sink: S,
+    last_played_at: Option<DateTime<Utc>>,
+}
+
+impl<S: HistorySink> HistoryRecorder<S> {
+    /// Create a recorder that records to `sink`, starting with the plays Spotify still has.
+    pub fn new(sink: S) -> Self {
+        Self {
+            sink,
+            last_played_at: None,
+        }
+    }
+
+    /// Only record plays made after the given time, e.g. the last one recorded before.
+    pub fn since(mut self, played_at: DateTime<Utc>) -> Self {
+        self.last_played_at = Some(played_at);
+        self
+    }
+
+    /// When the last play that was recorded was played.
+    pub fn last_played_at(&self) -> Option<DateTime<Utc>> {
+        self.last_played_at
+    }
+
+    /// Get a reference to the sink.
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    /// Consume the recorder, returning the sink.
+    pub fn into_sink(self) -> S {
+        self.sink
+    }
+
+    /// Get the recently played tracks once, and record the plays that are new, returning
+    /// how many there were.
+    ///
+    /// If the sink fails, the plays aren't counted as recorded, so the next poll tries
+    /// to record them again, as long as Spotify still has them.
+    pub async fn poll<F: AuthFlow + Authorised>(
+        &mut self,
+        spotify: &Client<Token, F>,
+    ) -> Result<usize, HistoryError<S::Error>> {
+        let mut plays = Vec::new();
+        let mut pages = Box::pin(spotify.recently_played_tracks().limit(50).stream());
+
+        // The pages go back in time, so stop at the first one reaching recorded plays.
+        while let Some(page) = pages.next().await {
+            let page = page?;
+            let reached_recorded = page.items.iter().any(|p| !self.is_new(p));
+
+            plays.extend(page.items.into_iter().filter(|p| self.is_new(p)));
+
+            if reached_recorded {
+                break;
+            }
+        }
+
+        plays.sort_by_key(|p| p.played_at);
+        plays.dedup_by_key(|p| p.played_at);
+
+        let Some(last) = plays.last().map(|p| p.played_at) else {
+            return Ok(0);
+        };
+
+        self.sink.record(&plays).await.map_err(HistoryError::Sink)?;
+        self.last_played_at = Some(last);
+
+        Ok(plays.len())
+    }
+
+    /// Poll the recently played tracks every `interval`, waiting with `sleep`, until
+    /// polling fails.
+    ///
+    /// Spotify only keeps 50 plays, so the interval should be short enough for the user
+    /// not to play more than that in between, e.g. 10 minutes.
+    pub async fn run<F, W, Fut>(
+        &mut self,
+        spotify: &Client<Token, F>,
+        interval: Duration,
+        sleep: W,
+    ) -> Result<(), HistoryError<S::Error>>
+    where
+        F: AuthFlow + Authorised,
+        W: Fn(Duration) -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        loop {
+            self.poll(spotify).await?;
+            sleep(interval).await;
+        }
+    }
+
+    fn is_new(&self, play: &PlayHistory) -> bool {
+        self.last_played_at.is_none_or(|last| play.played_at > last)
+    }
+}
+
+/// A [`HistorySink`] that writes each play as a line of JSON, in the same format as the
+/// API returns it.
+#[derive(Debug)]
+pub struct JsonlSink<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> JsonlSink<W> {
+    /// Create a sink that writes to `writer`, e.g. a file opened for appending.
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Consume the sink, returning the writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> HistorySink for JsonlSink<W> {
+    type Error = std::io::Error;
+
+    async fn record(&mut self, plays: &[PlayHistory]) -> Result<(), Self::Error> {
+        for play in plays {
+            serde_json::to_writer(&mut self.writer, play)?;
+            self.writer.write_all(b"\n")?;
+        }
+
+        self.writer.flush()
+    }
+}
+
+/// A [`HistorySink`] that writes each play as a row of CSV, with the columns
+/// `played_at`, `track_id`, `track_name`, `artists`, `album`, `duration_ms` and `context_uri`.
+///
+/// Multiple artists are separated by `; `.
+#[derive(Debug)]
+pub struct CsvSink<W: Write> {
+    writer: W,
+    header: bool,
+}
+
+impl<W: Write> CsvSink<W> {
+    /// Create a sink that writes to `writer`, starting with a header row.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            header: true,
+        }
+    }
+
+    /// Create a sink that writes to `writer` without a header row, e.g. to append to a
+    /// file that already has one.
+    pub fn without_header(writer: W) -> Self {
+        Self {
+            writer,
+            header: false,
+        }
+    }
+
+    /// Consume the sink, returning the writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> HistorySink for CsvSink<W> {
+    type Error = std::io::Error;
+
+    async fn record(&mut self, plays: &[PlayHistory]) -> Result<(), Self::Error> {
+        if self.header {
+            writeln!(
+                self.writer,
+                "played_at,track_id,track_name,artists,album,duration_ms,context_uri"
+            )?;
+            self.header = false;
+        }
+
+        for play in plays {
+            let track = &play.track;
+            let artists: Vec<_> = track.artists.iter().map(|a| a.name.as_str()).collect();
+            let context_uri = play.context.as_ref().map_or("", |c| c.uri.as_str());
+
+            writeln!(
+                self.writer,
+                "{},{},{},{},{},{},{}",
+                play.played_at.to_rfc3339(),
+                csv_field(&track.id),
+                csv_field(&track.name),
+                csv_field(&artists.join("; ")),
+                csv_field(&track.album.name),
+                track.duration_ms,
+                csv_field(context_uri),
+            )?;
+        }
+
+        self.writer.flush()
+    }
+}
+
+// Quotes a field if it contains anything that would otherwise break the row.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
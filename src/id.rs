@@ -0,0 +1,301 @@
+//! Typed Spotify IDs and URIs.
+//!
+//! Every kind of object has its own ID type, such as [`TrackId`] and [`AlbumId`], which
+//! can be parsed from a plain ID, a Spotify URI (`spotify:track:...`) or an
+//! `open.spotify.com` URL. The client's methods accept them wherever they take the ID
+//! of that kind of object, so passing an album's ID to [`track`](crate::client::Client::track)
+//! doesn't compile. Plain strings are still accepted as well, unchecked.
+//!
+//! ```
+//! use spotify_rs::id::{SpotifyUri, TrackId};
+//!
+//! let from_uri: TrackId = "spotify:track:6rqhFgbbKwnb9MLmUQDhG6".parse()?;
+//! let from_url: TrackId = "https://open.spotify.com/track/6rqhFgbbKwnb9MLmUQDhG6?si=abc".parse()?;
+//! assert_eq!(from_uri, from_url);
+//! assert_eq!(from_uri.id(), "6rqhFgbbKwnb9MLmUQDhG6");
+//!
+//! let uri: SpotifyUri = "spotify:album:4aawyAB9vmqN3uQ7FjRGTy".parse()?;
+//! assert!(matches!(uri, SpotifyUri::Album(_)));
+//! assert!("spotify:album:4aawyAB9vmqN3uQ7FjRGTy".parse::<TrackId>().is_err());
+//! # Ok::<(), spotify_rs::Error>(())
+//! ```
+
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+
+/// A value that can be used as the ID of an object of kind `T`: either the typed ID
+/// itself, or any string, which is used as-is.
+pub trait AsId<T> {
+    /// The ID, as sent to the API.
+    fn as_id(&self) -> &str;
+}
+
+impl<T, S: AsRef<str> + ?Sized> AsId<T> for S {
+    fn as_id(&self) -> &str {
+        self.as_ref()
+    }
+}
+
+macro_rules! ids {
+    ($($(#[$meta:meta])* $name:ident => $kind:literal, $variant:ident;)+) => {
+        $(
+            $(#[$meta])*
+            #[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+            #[serde(try_from = "String", into = "String")]
+            #[cfg_attr(
+                feature = "diesel",
+                derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+                diesel(sql_type = diesel::sql_types::Text)
+            )]
+            pub struct $name(String);
+
+            impl $name {
+                #[doc = concat!("Create a ", $kind, " ID from a plain ID, checking that it's valid.")]
+                pub fn from_id(id: impl Into<String>) -> Result<Self> {
+                    let id = id.into();
+
+                    if is_valid(&id, $kind) {
+                        Ok(Self(id))
+                    } else {
+                        Err(invalid(&id, $kind))
+                    }
+                }
+
+                #[doc = concat!("Parse a ", $kind, " ID from a URI, e.g. `spotify:", $kind, ":{id}`.")]
+                pub fn from_uri(uri: &str) -> Result<Self> {
+                    match parse_uri(uri) {
+                        Some(($kind, id)) => Self::from_id(id),
+                        _ => Err(invalid(uri, $kind)),
+                    }
+                }
+
+                #[doc = concat!("Parse a ", $kind, " ID from a URL, e.g. `https://open.spotify.com/", $kind, "/{id}`.")]
+                pub fn from_url(url: &str) -> Result<Self> {
+                    match parse_url(url) {
+                        Some(($kind, id)) => Self::from_id(id),
+                        _ => Err(invalid(url, $kind)),
+                    }
+                }
+
+                /// The plain ID.
+                pub fn id(&self) -> &str {
+                    &self.0
+                }
+
+                #[doc = concat!("The URI, i.e. `spotify:", $kind, ":{id}`.")]
+                pub fn uri(&self) -> String {
+                    format!("spotify:{}:{}", $kind, self.0)
+                }
+
+                /// The URL for opening the object in the Spotify web player or app.
+                pub fn url(&self) -> String {
+                    format!("https://open.spotify.com/{}/{}", $kind, self.0)
+                }
+            }
+
+            impl AsId<$name> for $name {
+                fn as_id(&self) -> &str {
+                    &self.0
+                }
+            }
+
+            impl AsId<$name> for &$name {
+                fn as_id(&self) -> &str {
+                    &self.0
+                }
+            }
+
+            /// Parses a plain ID, a URI or a URL.
+            impl FromStr for $name {
+                type Err = Error;
+
+                fn from_str(s: &str) -> Result<Self> {
+                    if s.starts_with("spotify:") {
+                        Self::from_uri(s)
+                    } else if s.contains('/') {
+                        Self::from_url(s)
+                    } else {
+                        Self::from_id(s)
+                    }
+                }
+            }
+
+            impl TryFrom<String> for $name {
+                type Error = Error;
+
+                fn try_from(value: String) -> Result<Self> {
+                    value.parse()
+                }
+            }
+
+            impl From<$name> for String {
+                fn from(value: $name) -> Self {
+                    value.0
+                }
+            }
+
+            impl fmt::Display for $name {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    f.write_str(&self.0)
+                }
+            }
+
+            impl From<$name> for SpotifyUri {
+                fn from(value: $name) -> Self {
+                    Self::$variant(value)
+                }
+            }
+        )+
+
+        /// The URI of any kind of object, e.g. `spotify:track:{id}`.
+        ///
+        /// It can also be parsed from an `open.spotify.com` URL.
+        #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+        #[serde(try_from = "String", into = "String")]
+        pub enum SpotifyUri {
+            $($variant($name),)+
+        }
+
+        impl SpotifyUri {
+            /// The kind of object, as it appears in the URI, e.g. `track`.
+            pub fn kind(&self) -> &'static str {
+                match self {
+                    $(Self::$variant(_) => $kind,)+
+                }
+            }
+
+            /// The plain ID of the object.
+            pub fn id(&self) -> &str {
+                match self {
+                    $(Self::$variant(id) => id.id(),)+
+                }
+            }
+
+            /// The URL for opening the object in the Spotify web player or app.
+            pub fn url(&self) -> String {
+                format!("https://open.spotify.com/{}/{}", self.kind(), self.id())
+            }
+
+            fn from_parts(kind: &str, id: &str, input: &str) -> Result<Self> {
+                match kind {
+                    $($kind => $name::from_id(id).map(Self::$variant),)+
+                    _ => Err(Error::InvalidId(format!(
+                        "`{input}` is not a valid Spotify URI or URL"
+                    ))),
+                }
+            }
+        }
+    };
+}
+
+ids! {
+    /// The ID of an album.
+    AlbumId => "album", Album;
+    /// The ID of an artist.
+    ArtistId => "artist", Artist;
+    /// The ID of an audiobook.
+    AudiobookId => "audiobook", Audiobook;
+    /// The ID of an audiobook chapter.
+    ChapterId => "chapter", Chapter;
+    /// The ID of a show episode.
+    EpisodeId => "episode", Episode;
+    /// The ID of a playlist.
+    PlaylistId => "playlist", Playlist;
+    /// The ID of a show.
+    ShowId => "show", Show;
+    /// The ID of a track.
+    TrackId => "track", Track;
+    /// The ID of a user, which unlike other IDs isn't limited to letters and digits.
+    UserId => "user", User;
+}
+
+/// Parses a URI or a URL.
+impl FromStr for SpotifyUri {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let parsed = if s.starts_with("spotify:") {
+            parse_uri(s)
+        } else {
+            parse_url(s)
+        };
+
+        match parsed {
+            Some((kind, id)) => Self::from_parts(kind, id, s),
+            None => Err(Error::InvalidId(format!(
+                "`{s}` is not a valid Spotify URI or URL"
+            ))),
+        }
+    }
+}
+
+impl TryFrom<String> for SpotifyUri {
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self> {
+        value.parse()
+    }
+}
+
+impl From<SpotifyUri> for String {
+    fn from(value: SpotifyUri) -> Self {
+        value.to_string()
+    }
+}
+
+impl fmt::Display for SpotifyUri {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "spotify:{}:{}", self.kind(), self.id())
+    }
+}
+
+// User IDs can contain other characters (e.g. `.` and `_`), but none of these.
+fn is_valid(id: &str, kind: &str) -> bool {
+    if kind == "user" {
+        !id.is_empty() && !id.contains(|c: char| c == ':' || c == '/' || c.is_whitespace())
+    } else {
+        !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric())
+    }
+}
+
+fn invalid(input: &str, kind: &str) -> Error {
+    Error::InvalidId(format!(
+        "`{input}` is not a valid Spotify {kind} ID, URI or URL"
+    ))
+}
+
+// `spotify:{kind}:{id}`
+fn parse_uri(uri: &str) -> Option<(&str, &str)> {
+    let mut parts = uri.strip_prefix("spotify:")?.split(':');
+    let (kind, id) = (parts.next()?, parts.next()?);
+
+    parts.next().is_none().then_some((kind, id))
+}
+
+// `https://open.spotify.com/{kind}/{id}`, optionally with a localised path
+// (e.g. `/intl-de/track/{id}`) and a query string (e.g. `?si=...`).
+fn parse_url(url: &str) -> Option<(&str, &str)> {
+    let url = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .unwrap_or(url);
+    let path = url.strip_prefix("open.spotify.com/")?;
+    let path = path.split(['?', '#']).next()?;
+
+    let mut segments = path.split('/').filter(|s| !s.is_empty());
+    let mut kind = segments.next()?;
+
+    if kind.starts_with("intl-") {
+        kind = segments.next()?;
+    }
+
+    let id = segments.next()?;
+    segments.next().is_none().then_some((kind, id))
+}
+
+pub(crate) fn as_ids<K, T: AsId<K>>(ids: &[T]) -> Vec<&str> {
+    ids.iter().map(AsId::as_id).collect()
+}
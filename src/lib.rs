@@ -1,5 +1,5 @@
 //! spotify-rs is a Rust wrapper for the Spotify API. It has full API coverage
-//! and supports all the authorisation flows (except for the implicit grant flow).
+//! and supports all the authorisation flows.
 //!
 //! # Getting Started
 //! First, you'll need to
@@ -13,30 +13,47 @@
 //! To use the Spotify API, you'll need to authenticate using your client credentials,
 //! and if you want to access user resources, you'll need to go through one of the authorisation flows.
 //!
+//! Most of the commonly used types can be imported at once with `use spotify_rs::prelude::*;`.
+//!
+//! Methods that take the ID of an object accept either a plain string or a typed ID from
+//! the [`id`] module, such as [`TrackId`](id::TrackId), which can be parsed from a Spotify
+//! URI or URL and can't be mixed up with the ID of another kind of object.
+//!
 //! # Authorisation
 //! You will need to set some scopes, redirect the user to a generated URL, which will
 //! redirect them again to your app's *redirect URI*, which will contain a code that allows
 //! your app to be authorised.
 //!
-//! spotify-rs supports 3 of the 4 OAuth2 authorisation flows the API supports:
-//! the authorisation code flow, authorisation code with PKCE flow and the client credentials flow.
-//!
-//! The [implicit grant flow](https://developer.spotify.com/documentation/web-api/tutorials/implicit-flow)
-//! is not supported for 2 reasons:
-//! - it returns the access token in the URL, which is insecure and leaves your app vulnerable to all kinds of attacks;
-//! - doesn't support refreshing the access token.
+//! spotify-rs supports all 4 of the OAuth2 authorisation flows the API supports:
+//! the authorisation code flow, authorisation code with PKCE flow, client credentials flow
+//! and implicit grant flow.
 //!
 //! The auth flow you should use depends on the use case:
 //! - the authorisation code flow is recommended for long-running applications
-//! where you can safely store the client secret (e.g. web and mobile apps)
+//!   where you can safely store the client secret (e.g. web and mobile apps)
 //! - the authorisation code with PKCE flow is recommended for long-running applications
-//! where you *can't* safely store the client secret (e.g. desktop apps and single page web apps)
+//!   where you *can't* safely store the client secret (e.g. desktop apps and single page web apps)
 //! - the client credentials flow doesn't include authorisation, thus letting you only access public information
+//! - the [implicit grant flow](https://developer.spotify.com/documentation/web-api/tutorials/implicit-flow)
+//!   is only meant for front-end integrations where an access token is all you get (e.g. from a
+//!   browser redirect's URL fragment). It returns the access token in the URL, which is insecure,
+//!   and the token can't be refreshed, so prefer the PKCE flow for new apps. Use
+//!   [`ImplicitGrantClient::new`] to start the flow, or [`ImplicitGrantClient::from_access_token`]
+//!   if the token was obtained elsewhere.
+//!
+//! If your app already goes through the authorisation elsewhere (e.g. in a web framework
+//! or another service), the resulting token can be handed to
+//! [`Client::from_access_token`](client::Client::from_access_token) instead of going
+//! through the flow again.
+//!
+//! When the token says which scopes the user granted, as it does after the authorisation
+//! code flows, a request that needs a scope the user didn't grant fails with
+//! [`Error::MissingScope`] before it's sent, instead of with a `403` from Spotify.
 //!
 //! Below is an example for each auth flow:
 //! ## Authorisation Code Flow
 //! ```no_run
-//! use spotify_rs::{AuthCodeClient, AuthCodeFlow, RedirectUrl};
+//! use spotify_rs::{market::Market, AuthCodeClient, AuthCodeFlow, RedirectUrl};
 //! # use std::error::Error;
 //!
 //! #[tokio::main]
@@ -54,17 +71,20 @@
 //!     // with those two parameters present in the URL
 //!
 //!     // Finally, exchange the auth code for an access token
-//!     let mut spotify = client.authenticate("auth_code", "csrf_token").await?;
+//!     let spotify = client.authenticate("auth_code", "csrf_token").await?;
 //!
 //!     // Get an album with the specified ID (requires no scopes to be set)
 //!     let album = spotify.album("album_id").get().await?;
 //!
 //!     // The `album` method returns a builder with optional parameters you can set
-//!     // For example, this sets the market to "GB".
-//!     let album_gb = spotify.album("album_id").market("GB").get().await?;
+//!     // For example, this sets the market to the UK.
+//!     let album_gb = spotify.album("album_id").market(Market::GB).get().await?;
+//!
+//!     // Builders can also be awaited directly, which is the same as calling `.get()`
+//!     let album_de = spotify.album("album_id").market(Market::DE).await?;
 //!
-//!     // Get 5 of the current user's playlists (requires the playlist-read-private scope)
-//!     let user_playlists = spotify.current_user_playlists().limit(5).get().await?;
+//!     // Get 5 of the current user's saved tracks (requires the user-library-read scope)
+//!     let saved_tracks = spotify.saved_tracks().limit(5).get().await?;
 //!
 //!     Ok(())
 //! }
@@ -87,7 +107,7 @@
 //!
 //! ## Client Credentials Flow
 //! ```no_run
-//! use spotify_rs::{ClientCredsClient, ClientCredsFlow, RedirectUrl};
+//! use spotify_rs::{ClientCredsClient, ClientCredsFlow};
 //! # use std::error::Error;
 //!
 //! #[tokio::main]
@@ -95,7 +115,7 @@
 //!     let auth_flow = ClientCredsFlow::new("client_id", "client_secret");
 //!
 //!     // Create an authenticate the client
-//!     let mut spotify = ClientCredsClient::authenticate(auth_flow).await?;
+//!     let spotify = ClientCredsClient::authenticate(auth_flow).await?;
 //!
 //!     let album = spotify.album("album_id").get().await?;
 //!
@@ -104,6 +124,8 @@
 //! ```
 //! This flow doesn't require anything besides the client credentials,
 //! but you cannot access any user information.
+//! [`ClientCredsClient::with_client_credentials`](client::Client::with_client_credentials)
+//! does the same given just the client ID and secret.
 //!
 //! You can see all of the available optional parameters in the [`Builder`] documentation.
 //! They show up after each `impl Builder<'_, F, SomeEndpoint`, where `SomeEndpoint`
@@ -114,17 +136,304 @@
 //! the client will check if the token is about to expire. If the token is close
 //! to expiring, it will refresh the token for you.
 //!
+//! If Spotify rejects the token before it was due to expire, e.g. because it was revoked,
+//! the client also refreshes it and sends the request once more, unless that's turned off
+//! with [`ClientBuilder::refresh_on_unauthorised`].
+//!
 //! If you disable this feature, you'll have to refresh the token yourself using [`Client::request_refresh_token()`].
 //!
+//! To keep the token across restarts, give the client a [`TokenStore`](token_store::TokenStore),
+//! which every new token is saved to, and create it from the saved token next time.
+//! To be told about every refreshed token instead, e.g. to store it somewhere else, give the
+//! [`ClientBuilder`] an [`on_token_refresh`](ClientBuilder::on_token_refresh) callback.
+//!
+//! The refresh happens as part of the request that needed it, not in a background task.
+//! When several requests find the token expired at the same time, only the first one
+//! refreshes it, and the others wait for it and then use the new token.
+//! In fact, the client never spawns tasks at all and isn't tied to any async runtime:
+//! all of its work happens inside the futures (and streams) it returns. Dropping the
+//! client, or a future in progress, stops everything it was doing, so there's nothing
+//! to shut down when a service exits.
+//!
+//! That includes a refresh, which is cancelled along with the request that started it.
+//! If it's dropped while the new token is being requested, Spotify may already have
+//! replaced the refresh token, leaving the client with one that no longer works. If it's
+//! dropped after the client has the new token but before it's saved to the
+//! [`TokenStore`](token_store::TokenStore), the store keeps the old one. When that matters,
+//! e.g. with requests that are given a timeout, refresh the token with
+//! [`Client::request_refresh_token()`] in a task of its own (such as one spawned with
+//! `tokio::spawn`) and wait for it before sending requests.
+//!
+//! # Sharing the Client
+//! Every request only needs a shared reference to the client, as the token is kept
+//! behind a lock that's updated when it's refreshed. The client can therefore be put
+//! in an [`Arc`](std::sync::Arc) and used by multiple tasks at once, without a mutex
+//! around it.
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//! use spotify_rs::{ClientCredsClient, ClientCredsFlow};
+//! # use std::error::Error;
+//!
+//! # async fn example() -> Result<(), Box<dyn Error>> {
+//! let auth_flow = ClientCredsFlow::new("client_id", "client_secret");
+//! let spotify = Arc::new(ClientCredsClient::authenticate(auth_flow).await?);
+//!
+//! let task = tokio::spawn({
+//!     let spotify = Arc::clone(&spotify);
+//!     async move { spotify.album("album_id").get().await }
+//! });
+//!
+//! let track = spotify.track("track_id").get().await?;
+//! let album = task.await??;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Configuring the Client
+//! The constructors above cover the common case. For anything else, such as request
+//! timeouts, a user agent or proxy, your own `reqwest::Client`, a different base URL
+//! (e.g. a mock server in tests), or a default market, locale and page size used by every
+//! request that supports them, use a [`ClientBuilder`], which collects all of the client's
+//! configuration in one place.
+//!
+//! The base URLs can also be set with the `SPOTIFY_API_URL` and `SPOTIFY_ACCOUNTS_URL`
+//! environment variables, which apply to every client, including those created by the
+//! constructors above. This makes it easy to point a whole app at a mock server such as
+//! wiremock or httpmock in integration tests. URLs set on the builder take precedence.
+//! As the variables are read in production too, only HTTP(S) URLs on the local machine
+//! (`localhost`, `127.0.0.1` or `[::1]`) are accepted, and any other value is ignored, so
+//! that they can't be used to send the client's secrets to another host.
+//!
+//! By default, a request that's rate limited by Spotify fails with [`Error::RateLimited`].
+//! To have the client wait and retry it instead, give the builder a
+//! [`RetryPolicy`](client::RetryPolicy), which can also retry requests that fail with
+//! one of the server errors Spotify returns now and then, backing off exponentially.
+//! To avoid being rate limited in the first place, give it a
+//! [`RateLimiter`](client::RateLimiter), which spaces out the client's requests.
+//!
+//! Read-heavy apps can also give the builder a response [`cache`], which serves repeated
+//! reads from memory (or a backend of your own) according to Spotify's `ETag` and
+//! `Cache-Control` headers, so that they don't all count towards the rate limit.
+//!
+//! To add headers, collect metrics, sign or capture the requests sent to the API, give the
+//! builder a [`middleware`], which can inspect and modify every request and its response.
+//!
+//! # Bulk Jobs
+//! Methods that take a list of IDs, such as [`tracks`](client::Client::tracks) or
+//! [`save_tracks`](client::Client::save_tracks), accept lists of any length. Spotify limits
+//! how many IDs each request can contain (usually 20 or 50), so longer lists are split into
+//! as many requests as needed, which are made one after another, and their results are
+//! merged in order.
+//!
+//! For jobs that take thousands of requests, such as getting the audio features of a large
+//! library, the [`batch`] module spreads the requests out over time to stay within a request
+//! budget, and keeps track of the job's progress so it can be resumed after an interruption.
+//!
+//! To make a playlist match a list of tracks, the [`playlist_sync`] module works out the
+//! fewest items to remove, move and add, instead of replacing all of them.
+//!
+//! # WebAssembly
+//! The crate also compiles for `wasm32-unknown-unknown`, where reqwest sends requests with the
+//! browser's `fetch`, so it can be used directly from Yew, Leptos and other frontend frameworks.
+//! Futures aren't `Send` there, and the builder has no `timeout`, `connect_timeout` or `proxy`
+//! (the browser handles those). Where the client waits, such as in a [`RetryPolicy`], pass it a
+//! browser timer, e.g. from [gloo-timers](https://docs.rs/gloo-timers). The `blocking`,
+//! `python` and `uniffi` features need a native target.
+//!
+//! # Features
+//! The endpoints are split into groups which can be toggled using Cargo features,
+//! so that you only compile what you use. All of them are enabled by default through the `full` feature.
+//!
+//! - `audiobooks`: audiobook and chapter endpoints
+//! - `browse`: browse categories, new releases and (with `playlists`) featured and category playlists
+//! - `player`: playback control, queue and recently played tracks, the `scrobble` module
+//!   for reporting listening activity to services like Last.fm, and the `history` module
+//!   for archiving the recently played tracks
+//! - `playlists`: playlist endpoints, including following playlists
+//! - `search`: the search endpoint
+//! - `shows`: show and episode endpoints
+//! - `user`: the current user's profile, top items and followed artists and users
+//!
+//! Albums, artists, tracks and public user profiles are always available.
+//!
+//! For lookups only, such as in a serverless function using the Client Credentials Flow,
+//! disable the default features to get a minimal build with just the catalog endpoints:
+//!
+//! ```toml
+//! spotify-rs = { version = "0.3", default-features = false }
+//! ```
+//!
+//! The optional `simd-json` feature uses [simd-json](https://docs.rs/simd-json) to parse
+//! responses, which is faster for large payloads such as big playlist pages or audio analyses.
+//!
+//! The `lenient` feature makes the models keep values Spotify added after this version of
+//! the crate, such as a new album type, in an `Other` variant of their enum, instead of
+//! failing the whole request with [`Error::Deserialization`].
+//!
+//! The `url` feature adds accessors that parse the links in the models into `url::Url`s,
+//! such as `open_url()` for the link that opens an object in Spotify, `href_url()` for
+//! its API endpoint and `Image::to_url()`.
+//!
+//! The `image` feature converts PNGs into JPEGs, and downscales images that are too large,
+//! when they're uploaded as playlist covers, which Spotify only accepts as small JPEGs.
+//!
+//! The `otel` feature creates an [OpenTelemetry](https://docs.rs/opentelemetry) client span
+//! for every request to the API, using the global tracer provider, and propagates the
+//! current trace context to Spotify using the global propagator (e.g. a `traceparent` header).
+//!
+//! The `tracing` feature instruments the client with [tracing](https://docs.rs/tracing):
+//! every request to the API gets a `spotify_request` span with its method, endpoint, status,
+//! latency and number of retries. Within it, each retry is logged as a `retry` event with
+//! the attempt number, status and delay, and waiting for the client's
+//! [`RateLimiter`](client::RateLimiter) as a `rate_limited` event with the delay. Token
+//! refreshes are logged as events too, and the auth flows get spans of their own, which
+//! record their errors.
+//!
+//! The `polars` feature adds the `dataframe` module, for converting tracks, audio features
+//! and play history into [polars](https://docs.rs/polars) `DataFrame`s.
+//!
+//! The `schemars` feature derives [`JsonSchema`](https://docs.rs/schemars) for the models,
+//! for validating stored payloads or generating types in other languages.
+//!
+//! The `sqlx` and `diesel` features implement the traits for storing the typed IDs (such
+//! as [`TrackId`](id::TrackId)) and [`Market`](market::Market) in text columns with
+//! [sqlx](https://docs.rs/sqlx) and [Diesel](https://diesel.rs), for any database. IDs are
+//! stored as plain IDs, but URIs and URLs can be read back as well.
+//!
+//! The `uniffi` feature adds the `ffi` module, a simplified facade of the client that can
+//! be used from Kotlin, Swift and other languages through [UniFFI](https://mozilla.github.io/uniffi-rs).
+//!
+//! The `python` feature adds the `python` module, an async Python facade of the client
+//! built with [PyO3](https://pyo3.rs).
+//!
+//! The `blocking` feature adds the [`blocking`] module, a runtime helper that runs the async
+//! client's requests to completion on a private Tokio runtime, for programs that don't
+//! otherwise use an async runtime.
+//!
+//! The `mock` feature adds [`Client::mock`] and the [`mock`] module, for testing code
+//! that uses the client without network access.
+//!
+//! The `offline` feature adds [`Client::offline`] and the [`offline`] module, for serving
+//! responses from local fixtures instead of the API.
+//!
+//! The `vcr` feature adds the [`vcr`] module, for recording real interactions with the API
+//! to fixture files and replaying them in tests.
+//!
+//! The `test-support` feature adds the [`test_support`] module, with sample JSON payloads
+//! and constructors for models, for writing unit tests.
+//!
+//! Secrets (client secrets, access and refresh tokens) are kept in a [`Secret`](auth::Secret),
+//! which is never printed by `Debug`. The `zeroize` feature also overwrites every `Secret`
+//! with zeroes when it's dropped, so they don't linger in freed memory. This doesn't reach
+//! the copies oauth2 and reqwest make while a token is requested or sent, e.g. the client
+//! secret kept by the OAuth client and the `Authorization` header, which those crates
+//! don't wipe.
+//!
 //! [`AuthCodePkceFlow`]: auth::AuthCodePkceFlow
+//! [`Token`]: auth::Token
 //! [`Builder`]: endpoint::Builder
 //! [`Client::request_refresh_token()`]: client::Client::request_refresh_token()
+//! [`RetryPolicy`]: client::RetryPolicy
+// Items behind features that aren't enabled are linked to on docs.rs instead.
+#![cfg_attr(feature = "blocking", doc = "[`blocking`]: crate::blocking")]
+#![cfg_attr(
+    not(feature = "blocking"),
+    doc = "[`blocking`]: https://docs.rs/spotify-rs/latest/spotify_rs/blocking/index.html"
+)]
+#![cfg_attr(feature = "mock", doc = "[`Client::mock`]: client::Client::mock")]
+#![cfg_attr(
+    not(feature = "mock"),
+    doc = "[`Client::mock`]: https://docs.rs/spotify-rs/latest/spotify_rs/client/struct.Client.html#method.mock"
+)]
+#![cfg_attr(feature = "mock", doc = "[`mock`]: crate::mock")]
+#![cfg_attr(
+    not(feature = "mock"),
+    doc = "[`mock`]: https://docs.rs/spotify-rs/latest/spotify_rs/mock/index.html"
+)]
+#![cfg_attr(
+    feature = "offline",
+    doc = "[`Client::offline`]: client::Client::offline"
+)]
+#![cfg_attr(
+    not(feature = "offline"),
+    doc = "[`Client::offline`]: https://docs.rs/spotify-rs/latest/spotify_rs/client/struct.Client.html#method.offline"
+)]
+#![cfg_attr(feature = "offline", doc = "[`offline`]: crate::offline")]
+#![cfg_attr(
+    not(feature = "offline"),
+    doc = "[`offline`]: https://docs.rs/spotify-rs/latest/spotify_rs/offline/index.html"
+)]
+#![cfg_attr(feature = "vcr", doc = "[`vcr`]: crate::vcr")]
+#![cfg_attr(
+    not(feature = "vcr"),
+    doc = "[`vcr`]: https://docs.rs/spotify-rs/latest/spotify_rs/vcr/index.html"
+)]
+#![cfg_attr(
+    feature = "test-support",
+    doc = "[`test_support`]: crate::test_support"
+)]
+#![cfg_attr(
+    not(feature = "test-support"),
+    doc = "[`test_support`]: https://docs.rs/spotify-rs/latest/spotify_rs/test_support/index.html"
+)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
 
 pub mod auth;
+pub mod batch;
+#[cfg(feature = "blocking")]
+#[cfg_attr(docsrs, doc(cfg(feature = "blocking")))]
+pub mod blocking;
+pub mod cache;
 pub mod client;
+#[cfg(feature = "polars")]
+#[cfg_attr(docsrs, doc(cfg(feature = "polars")))]
+pub mod dataframe;
+#[cfg(any(feature = "sqlx", feature = "diesel"))]
+mod db;
 pub mod endpoint;
 mod error;
+#[cfg(any(feature = "python", feature = "uniffi"))]
+mod facade;
+#[cfg(feature = "uniffi")]
+#[cfg_attr(docsrs, doc(cfg(feature = "uniffi")))]
+pub mod ffi;
+#[cfg(feature = "player")]
+#[cfg_attr(docsrs, doc(cfg(feature = "player")))]
+pub mod history;
+pub mod id;
+pub mod locale;
+pub mod market;
+pub mod middleware;
+#[cfg(feature = "mock")]
+#[cfg_attr(docsrs, doc(cfg(feature = "mock")))]
+pub mod mock;
 pub mod model;
+#[cfg(feature = "offline")]
+#[cfg_attr(docsrs, doc(cfg(feature = "offline")))]
+pub mod offline;
+#[cfg(feature = "otel")]
+mod otel;
+#[cfg(feature = "player")]
+#[cfg_attr(docsrs, doc(cfg(feature = "player")))]
+pub mod player;
+#[cfg(feature = "playlists")]
+#[cfg_attr(docsrs, doc(cfg(feature = "playlists")))]
+pub mod playlist_sync;
+pub mod prelude;
+#[cfg(feature = "python")]
+#[cfg_attr(docsrs, doc(cfg(feature = "python")))]
+pub mod python;
+#[cfg(feature = "player")]
+#[cfg_attr(docsrs, doc(cfg(feature = "player")))]
+pub mod scrobble;
+#[cfg(feature = "test-support")]
+#[cfg_attr(docsrs, doc(cfg(feature = "test-support")))]
+pub mod test_support;
+pub mod token_store;
+#[cfg(feature = "vcr")]
+#[cfg_attr(docsrs, doc(cfg(feature = "vcr")))]
+pub mod vcr;
 
 use client::Body;
 use serde::{Deserialize, Deserializer};
@@ -141,11 +450,36 @@ pub(crate) fn body_list<T: AsRef<str>>(name: &str, list: &[T]) -> Body<serde_jso
     Body::Json(serde_json::json!({ name: list }))
 }
 
-pub use auth::{AuthCodeFlow, AuthCodePkceFlow, ClientCredsFlow};
-pub use client::{AuthCodeClient, AuthCodePkceClient, ClientCredsClient};
-pub use error::{Error, Result as SpotifyResult};
+// Endpoints that take a list of IDs only accept so many per request (usually 20 or 50),
+// so longer lists are split into chunks of at most `size`, which are requested one after
+// another. The results are returned in order, for the caller to merge.
+pub(crate) async fn chunked<'a, R, Fut>(
+    ids: &[&'a str],
+    size: usize,
+    mut request: impl FnMut(&[&'a str]) -> Fut,
+) -> error::Result<Vec<R>>
+where
+    Fut: std::future::Future<Output = error::Result<R>>,
+{
+    let mut results = Vec::with_capacity(ids.len().div_ceil(size));
+
+    for chunk in ids.chunks(size) {
+        results.push(request(chunk).await?);
+    }
+
+    Ok(results)
+}
+
+pub use auth::{AuthCodeFlow, AuthCodePkceFlow, ClientCredsFlow, ImplicitGrantFlow};
+pub use client::{
+    AuthCodeClient, AuthCodePkceClient, ClientBuilder, ClientCredsClient, ImplicitGrantClient,
+};
+pub use error::{Error, PlayerErrorReason, Result as SpotifyResult};
 pub use oauth2::RedirectUrl;
 
+#[cfg(feature = "uniffi")]
+uniffi::setup_scaffolding!();
+
 /// Represents an empty API response.
 pub struct Nil;
 
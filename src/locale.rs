@@ -0,0 +1,121 @@
+//! Typed locales, i.e. the language and country content such as browse categories and
+//! featured playlists is localised for.
+//!
+//! A [`Locale`] pairs an [ISO 639-1](https://en.wikipedia.org/wiki/ISO_639-1) language
+//! code with a [`Market`], and is sent to the API as e.g. `es_MX`.
+//!
+//! ```
+//! use spotify_rs::{locale::Locale, market::Market};
+//!
+//! let locale: Locale = "es_MX".parse()?;
+//! assert_eq!(locale, Locale::new("es", Market::MX)?);
+//! assert_eq!(locale.language(), "es");
+//! assert_eq!(locale.country(), Market::MX);
+//! assert_eq!(locale.to_string(), "es_MX");
+//! assert!("spanish_MX".parse::<Locale>().is_err());
+//! # Ok::<(), spotify_rs::Error>(())
+//! ```
+
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    error::{Error, Result},
+    market::Market,
+};
+
+/// A locale: a language and the country it's spoken in, such as `es_MX` for Spanish
+/// (Mexico).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Locale {
+    language: [u8; 2],
+    country: Market,
+}
+
+impl Locale {
+    /// Create a locale from an ISO 639-1 language code, in either case, and a country.
+    ///
+    /// The country can't be [`Market::FromToken`], since Spotify wouldn't know which
+    /// language it's paired with.
+    pub fn new(language: &str, country: Market) -> Result<Self> {
+        let invalid = || {
+            Error::InvalidLocale(format!(
+                "`{language}_{country}` is not a valid locale, which must be an ISO 639-1 \
+                 language code followed by an ISO 3166-1 alpha-2 country code"
+            ))
+        };
+
+        let language: [u8; 2] = language
+            .as_bytes()
+            .try_into()
+            .ok()
+            .filter(|l: &[u8; 2]| l.iter().all(u8::is_ascii_alphabetic))
+            .ok_or_else(invalid)?;
+
+        if country == Market::FromToken {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            language: language.map(|b| b.to_ascii_lowercase()),
+            country,
+        })
+    }
+
+    /// The language code, e.g. `es`.
+    pub fn language(&self) -> &str {
+        // The language is always two ASCII letters.
+        std::str::from_utf8(&self.language).unwrap_or_default()
+    }
+
+    /// The country, e.g. [`Market::MX`].
+    pub fn country(&self) -> Market {
+        self.country
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}_{}", self.language(), self.country)
+    }
+}
+
+impl From<Locale> for String {
+    fn from(locale: Locale) -> Self {
+        locale.to_string()
+    }
+}
+
+impl FromStr for Locale {
+    type Err = Error;
+
+    /// Parse a locale such as `es_MX`. A hyphen can be used instead of the underscore,
+    /// as in `es-MX`.
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || {
+            Error::InvalidLocale(format!(
+                "`{s}` is not a valid locale, which must be an ISO 639-1 language code \
+                 followed by an ISO 3166-1 alpha-2 country code, e.g. `es_MX`"
+            ))
+        };
+
+        let (language, country) = s.split_once(['_', '-']).ok_or_else(invalid)?;
+        let country = country.parse().map_err(|_| invalid())?;
+
+        Self::new(language, country).map_err(|_| invalid())
+    }
+}
+
+impl Serialize for Locale {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Locale {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
@@ -0,0 +1,116 @@
+//! Typed markets, i.e. the countries content is requested for.
+//!
+//! Endpoints that support a market take a [`Market`] rather than a string, so a code
+//! that isn't a country, such as `UK` instead of `GB`, is rejected when it's parsed
+//! instead of by Spotify.
+//!
+//! ```
+//! use spotify_rs::market::Market;
+//!
+//! let market: Market = "gb".parse()?;
+//! assert_eq!(market, Market::GB);
+//! assert_eq!(market.as_str(), "GB");
+//! assert!("UK".parse::<Market>().is_err());
+//! # Ok::<(), spotify_rs::Error>(())
+//! ```
+
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error::{Error, Result};
+
+macro_rules! markets {
+    ($($code:ident),+ $(,)?) => {
+        /// A market: an [ISO 3166-1 alpha-2](https://en.wikipedia.org/wiki/ISO_3166-1_alpha-2)
+        /// country code, or the country of the user the access token belongs to.
+        ///
+        /// `XK` (Kosovo) isn't officially assigned, but is included because Spotify uses it.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        #[cfg_attr(
+            feature = "diesel",
+            derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+            diesel(sql_type = diesel::sql_types::Text)
+        )]
+        pub enum Market {
+            $($code,)+
+            /// The country of the user the access token belongs to, which only works with
+            /// tokens from the authorisation code flows.
+            FromToken,
+        }
+
+        impl Market {
+            /// The market as sent to the API, e.g. `GB` or `from_token`.
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(Self::$code => stringify!($code),)+
+                    Self::FromToken => "from_token",
+                }
+            }
+        }
+
+        impl FromStr for Market {
+            type Err = Error;
+
+            /// Parse a market from a country code, in either case, or `from_token`.
+            fn from_str(s: &str) -> Result<Self> {
+                match s.to_ascii_uppercase().as_str() {
+                    $(stringify!($code) => Ok(Self::$code),)+
+                    "FROM_TOKEN" => Ok(Self::FromToken),
+                    _ => Err(Error::InvalidMarket(format!(
+                        "`{s}` is not a valid market, which must be an ISO 3166-1 alpha-2 \
+                         country code or `from_token`"
+                    ))),
+                }
+            }
+        }
+    };
+}
+
+markets! {
+    AD, AE, AF, AG, AI, AL, AM, AO, AQ, AR, AS, AT, AU, AW, AX, AZ,
+    BA, BB, BD, BE, BF, BG, BH, BI, BJ, BL, BM, BN, BO, BQ, BR, BS, BT, BV, BW, BY, BZ,
+    CA, CC, CD, CF, CG, CH, CI, CK, CL, CM, CN, CO, CR, CU, CV, CW, CX, CY, CZ,
+    DE, DJ, DK, DM, DO, DZ,
+    EC, EE, EG, EH, ER, ES, ET,
+    FI, FJ, FK, FM, FO, FR,
+    GA, GB, GD, GE, GF, GG, GH, GI, GL, GM, GN, GP, GQ, GR, GS, GT, GU, GW, GY,
+    HK, HM, HN, HR, HT, HU,
+    ID, IE, IL, IM, IN, IO, IQ, IR, IS, IT,
+    JE, JM, JO, JP,
+    KE, KG, KH, KI, KM, KN, KP, KR, KW, KY, KZ,
+    LA, LB, LC, LI, LK, LR, LS, LT, LU, LV, LY,
+    MA, MC, MD, ME, MF, MG, MH, MK, ML, MM, MN, MO, MP, MQ, MR, MS, MT, MU, MV, MW, MX, MY, MZ,
+    NA, NC, NE, NF, NG, NI, NL, NO, NP, NR, NU, NZ,
+    OM,
+    PA, PE, PF, PG, PH, PK, PL, PM, PN, PR, PS, PT, PW, PY,
+    QA,
+    RE, RO, RS, RU, RW,
+    SA, SB, SC, SD, SE, SG, SH, SI, SJ, SK, SL, SM, SN, SO, SR, SS, ST, SV, SX, SY, SZ,
+    TC, TD, TF, TG, TH, TJ, TK, TL, TM, TN, TO, TR, TT, TV, TW, TZ,
+    UA, UG, UM, US, UY, UZ,
+    VA, VC, VE, VG, VI, VN, VU,
+    WF, WS,
+    XK,
+    YE, YT,
+    ZA, ZM, ZW,
+}
+
+impl fmt::Display for Market {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for Market {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Market {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
@@ -0,0 +1,128 @@
+//! Inspecting and modifying the requests the client sends to the API, and their responses.
+//!
+//! A [`Middleware`] given to the client (with [`ClientBuilder::with_middleware`]) sees every
+//! request right before it's sent, and decides what to do with it: it can change the request,
+//! pass it on to the rest of the chain with [`Next::run`], look at or replace the response,
+//! or answer the request itself without sending it. This can be used for custom headers,
+//! metrics, request signing, or capturing requests in tests.
+//!
+//! ```no_run
+//! use reqwest::{header::HeaderValue, Request, Response};
+//! use spotify_rs::{
+//!     middleware::{Middleware, Next},
+//!     ClientBuilder, ClientCredsFlow, SpotifyResult,
+//! };
+//!
+//! struct AppVersion;
+//!
+//! impl Middleware for AppVersion {
+//!     async fn handle(&self, mut req: Request, next: Next<'_>) -> SpotifyResult<Response> {
+//!         req.headers_mut()
+//!             .insert("x-app-version", HeaderValue::from_static("1.2.0"));
+//!
+//!         let res = next.run(req).await?;
+//!         println!("{} {}", res.status(), res.url());
+//!
+//!         Ok(res)
+//!     }
+//! }
+//!
+//! # async fn example() -> SpotifyResult<()> {
+//! let spotify = ClientBuilder::new(ClientCredsFlow::new("client_id", "client_secret"))
+//!     .with_middleware(AppVersion)
+//!     .authenticate()
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Middleware runs in the order it was added, so the first one sees the request first and
+//! the response last. It runs for every attempt at a request, including those made by a
+//! [`RetryPolicy`](crate::client::RetryPolicy), but not for the requests of the auth flows,
+//! or for responses served from a [`cache`](crate::cache), mock or recording.
+//!
+//! [`ClientBuilder::with_middleware`]: crate::ClientBuilder::with_middleware
+
+use std::{fmt, future::Future, sync::Arc};
+
+use reqwest::{Request, Response};
+
+use crate::{endpoint::BoxFuture, error::Result};
+
+/// Something that handles the client's requests before they're sent.
+pub trait Middleware: Send + Sync {
+    /// Handle `req`, usually by passing it (or a modified version of it) to `next`.
+    fn handle(&self, req: Request, next: Next<'_>)
+        -> impl Future<Output = Result<Response>> + Send;
+}
+
+// See `ErasedTokenStore`.
+trait ErasedMiddleware: Send + Sync {
+    fn handle<'a>(&'a self, req: Request, next: Next<'a>) -> BoxFuture<'a, Result<Response>>;
+}
+
+impl<T: Middleware> ErasedMiddleware for T {
+    fn handle<'a>(&'a self, req: Request, next: Next<'a>) -> BoxFuture<'a, Result<Response>> {
+        Box::pin(Middleware::handle(self, req, next))
+    }
+}
+
+/// The rest of the middleware chain, which ends with sending the request.
+#[derive(Clone, Copy)]
+pub struct Next<'a> {
+    http: &'a reqwest::Client,
+    chain: &'a [Arc<dyn ErasedMiddleware>],
+}
+
+impl<'a> Next<'a> {
+    /// Pass `req` on to the next middleware, or send it if there is none left.
+    pub async fn run(self, req: Request) -> Result<Response> {
+        match self.chain.split_first() {
+            Some((middleware, chain)) => {
+                let next = Next {
+                    http: self.http,
+                    chain,
+                };
+
+                middleware.handle(req, next).await
+            }
+            None => Ok(self.http.execute(req).await?),
+        }
+    }
+}
+
+impl fmt::Debug for Next<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Next")
+            .field("remaining", &self.chain.len())
+            .finish()
+    }
+}
+
+/// The middleware of a client.
+#[derive(Clone, Default)]
+pub(crate) struct MiddlewareChain(Vec<Arc<dyn ErasedMiddleware>>);
+
+impl MiddlewareChain {
+    pub(crate) fn push(&mut self, middleware: impl Middleware + 'static) {
+        self.0.push(Arc::new(middleware));
+    }
+
+    /// Send `req` through the chain.
+    pub(crate) async fn send(&self, http: &reqwest::Client, req: Request) -> Result<Response> {
+        Next {
+            http,
+            chain: &self.0,
+        }
+        .run(req)
+        .await
+    }
+}
+
+impl fmt::Debug for MiddlewareChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MiddlewareChain")
+            .field("len", &self.0.len())
+            .finish()
+    }
+}
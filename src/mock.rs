@@ -0,0 +1,170 @@
+//! A mocked Spotify API, for testing code that uses a [`Client`] without network access.
+//!
+//! A mocked client is a regular [`Client`], so every endpoint and builder is available
+//! and it can be passed to anything expecting, for example, an [`AuthCodeClient`].
+//! Instead of sending requests to Spotify, it responds with the responses set on a [`Mock`],
+//! matched by HTTP method and path, and records every request for later assertions.
+//!
+//! ```
+//! use spotify_rs::{mock::Mock, AuthCodeClient, Error};
+//! use serde_json::json;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!
+//! let mock = Mock::new()
+//!     .on_get("/me/tracks/contains", json!([true, false]))
+//!     .on_error("GET", "/albums/missing", 404, "Non existing id");
+//!
+//! let spotify = AuthCodeClient::mock(mock.clone());
+//!
+//! let saved = spotify.check_saved_tracks(&["a", "b"]).await?;
+//! assert_eq!(saved, [true, false]);
+//!
+//! let err = spotify.album("missing").get().await.unwrap_err();
+//! assert!(matches!(err, Error::Spotify { status: 404, .. }));
+//!
+//! let requests = mock.requests();
+//! assert_eq!(requests[0].path, "/me/tracks/contains");
+//! assert_eq!(requests[0].query.as_deref(), Some("ids=a%2Cb"));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Client`]: crate::client::Client
+//! [`AuthCodeClient`]: crate::AuthCodeClient
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use bytes::Bytes;
+use reqwest::{Method, StatusCode};
+use serde::Serialize;
+use serde_json::json;
+
+/// A set of canned responses returned by a mocked client.
+///
+/// Cloning a `Mock` is cheap, and all clones share the same responses and recorded
+/// requests, so you can keep one around to inspect the requests after handing a clone
+/// to [`Client::mock`](crate::client::Client::mock).
+#[derive(Clone, Debug, Default)]
+pub struct Mock {
+    inner: Arc<Mutex<State>>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    responses: HashMap<(Method, String), (StatusCode, Bytes)>,
+    requests: Vec<MockRequest>,
+}
+
+/// A request received by a mocked client.
+#[derive(Clone, Debug)]
+pub struct MockRequest {
+    /// The HTTP method of the request.
+    pub method: Method,
+    /// The path of the request, relative to the API's base URL (e.g. `/albums/{id}`).
+    pub path: String,
+    /// The URL-encoded query string, if there is one.
+    pub query: Option<String>,
+    /// The raw request body, if there is one.
+    pub body: Option<Vec<u8>>,
+}
+
+impl Mock {
+    /// Create a new mock without any responses.
+    ///
+    /// Requests without a matching response fail with a `404` [`Error::Spotify`](crate::Error::Spotify).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Respond to requests with the given method and path with a `200 OK` and `body` as JSON.
+    ///
+    /// The method is case-insensitive, e.g. `"GET"` or `"put"`. Setting a response
+    /// for the same method and path again replaces the previous one.
+    pub fn on(self, method: &str, path: impl Into<String>, body: impl Serialize) -> Self {
+        let body = serde_json::to_vec(&body).expect("mock response body should serialise");
+        self.respond(method, path.into(), StatusCode::OK, body)
+    }
+
+    /// Shorthand for [`on`](Self::on) with the `GET` method.
+    pub fn on_get(self, path: impl Into<String>, body: impl Serialize) -> Self {
+        self.on("GET", path, body)
+    }
+
+    /// Respond to requests with the given method and path with an error, in the same
+    /// format as the Spotify API.
+    pub fn on_error(
+        self,
+        method: &str,
+        path: impl Into<String>,
+        status: u16,
+        message: impl Into<String>,
+    ) -> Self {
+        let status = StatusCode::from_u16(status).expect("mock status should be valid");
+        let body = json!({ "error": { "status": status.as_u16(), "message": message.into() } });
+
+        self.respond(method, path.into(), status, body.to_string().into_bytes())
+    }
+
+    /// Get all of the requests received so far, in the order they were sent.
+    pub fn requests(&self) -> Vec<MockRequest> {
+        self.state().requests.clone()
+    }
+
+    fn respond(self, method: &str, path: String, status: StatusCode, body: Vec<u8>) -> Self {
+        let method = Method::from_bytes(method.to_uppercase().as_bytes())
+            .expect("mock method should be valid");
+
+        self.state()
+            .responses
+            .insert((method, path), (status, body.into()));
+        self
+    }
+
+    pub(crate) fn handle(
+        &self,
+        method: Method,
+        path: &str,
+        query: Option<&str>,
+        body: Option<Vec<u8>>,
+    ) -> (StatusCode, Bytes) {
+        let mut state = self.state();
+
+        // Some endpoints contain query parameters in their path, which are only
+        // used for matching the response if registered that way.
+        let key = (method.clone(), path.to_owned());
+        let stripped = (
+            method.clone(),
+            path.split('?').next().unwrap_or(path).to_owned(),
+        );
+
+        let response = state
+            .responses
+            .get(&key)
+            .or_else(|| state.responses.get(&stripped))
+            .cloned();
+
+        state.requests.push(MockRequest {
+            method: method.clone(),
+            path: stripped.1,
+            query: query.map(ToOwned::to_owned),
+            body,
+        });
+
+        response.unwrap_or_else(|| {
+            let message = format!("No mock response for {method} {path}");
+            let body = json!({ "error": { "status": 404, "message": message } });
+
+            (StatusCode::NOT_FOUND, body.to_string().into())
+        })
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        // A panic while holding the lock can only come from a failed assertion
+        // in a test, so the state is still usable.
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -1,4 +1,7 @@
-use serde::Deserialize;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{de, Deserialize, Deserializer, Serialize};
 
 pub mod album;
 pub mod artist;
@@ -14,7 +17,13 @@ pub mod show;
 pub mod track;
 pub mod user;
 
-#[derive(Clone, Debug, Deserialize)]
+// An item in the user's library, along with when they saved it.
+pub(crate) trait SavedItem {
+    fn added_at(&self) -> DateTime<Utc>;
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Page<T> {
     pub href: String,
     pub limit: u32,
@@ -25,7 +34,8 @@ pub struct Page<T> {
     pub items: Vec<T>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CursorPage<T> {
     pub href: String,
     pub limit: u32,
@@ -35,56 +45,85 @@ pub struct CursorPage<T> {
     pub items: Vec<T>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Cursor {
     pub after: Option<String>,
     pub before: Option<String>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Image {
     pub url: String,
     pub height: Option<u32>,
     pub width: Option<u32>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+impl Image {
+    /// Download the image with the given HTTP client, returning its bytes.
+    ///
+    /// The images are hosted outside of the API, so no token is needed.
+    pub async fn fetch(&self, http: &reqwest::Client) -> crate::error::Result<Vec<u8>> {
+        let response = http.get(&self.url).send().await?.error_for_status()?;
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    // The largest of the images, by area. Spotify lists them largest first, and leaves out
+    // the sizes of some (e.g. uploaded playlist covers), so ties go to the first one.
+    #[cfg_attr(not(feature = "playlists"), allow(dead_code))]
+    pub(crate) fn largest(images: &[Image]) -> Option<&Image> {
+        images
+            .iter()
+            .rev()
+            .max_by_key(|i| u64::from(i.width.unwrap_or(0)) * u64::from(i.height.unwrap_or(0)))
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Copyright {
     pub text: String,
     pub r#type: CopyrightType,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Restrictions {
     pub reason: RestrictionReason,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ExternalIds {
     pub isrc: Option<String>,
     pub ean: Option<String>,
     pub upc: Option<String>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ExternalUrls {
     pub spotify: String,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Followers {
     /// This will always be set to null, as the Web API does not support it at the moment.
     pub href: Option<String>,
     pub total: u32,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ResumePoint {
     pub fully_played: bool,
     pub resume_position_ms: u32,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum RestrictionReason {
     Market,
@@ -94,25 +133,198 @@ pub enum RestrictionReason {
     Unknown,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum CopyrightType {
     #[serde(rename = "C")]
     Copyright,
     #[serde(rename = "P")]
     Performance,
+    /// A value this version of the crate doesn't know about, which is only kept (instead
+    /// of failing to deserialise) with the `lenient` feature.
+    #[serde(untagged)]
+    #[cfg_attr(not(feature = "lenient"), serde(skip_deserializing))]
+    Other(String),
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum DatePrecision {
     Year,
     Month,
     Day,
+    /// A value this version of the crate doesn't know about, which is only kept (instead
+    /// of failing to deserialise) with the `lenient` feature.
+    #[serde(untagged)]
+    #[cfg_attr(not(feature = "lenient"), serde(skip_deserializing))]
+    Other(String),
 }
 
-#[derive(Clone, Debug, Deserialize)]
+/// An item that can be played, which is either a track or a podcast episode.
+#[derive(Clone, Debug, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum PlayableItem {
     Track(track::Track),
     Episode(show::Episode),
 }
+
+// The variant is picked by the item's `type`, rather than by trying each one in turn,
+// so that an item that fails to deserialize gets the error of its own type.
+impl<'de> Deserialize<'de> for PlayableItem {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+
+        let item = match value.get("type").and_then(serde_json::Value::as_str) {
+            Some("episode") => serde_json::from_value(value).map(Self::Episode),
+            _ => serde_json::from_value(value).map(Self::Track),
+        };
+
+        item.map_err(de::Error::custom)
+    }
+}
+
+impl PlayableItem {
+    /// The Spotify ID of the item.
+    pub fn id(&self) -> &str {
+        match self {
+            Self::Track(track) => &track.id,
+            Self::Episode(episode) => &episode.id,
+        }
+    }
+
+    /// The Spotify URI of the item.
+    pub fn uri(&self) -> &str {
+        match self {
+            Self::Track(track) => &track.uri,
+            Self::Episode(episode) => &episode.uri,
+        }
+    }
+
+    /// The name of the item.
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Track(track) => &track.name,
+            Self::Episode(episode) => &episode.name,
+        }
+    }
+
+    /// How long the item is.
+    pub fn duration(&self) -> Duration {
+        let duration_ms = match self {
+            Self::Track(track) => track.duration_ms,
+            Self::Episode(episode) => episode.duration_ms,
+        };
+
+        Duration::from_millis(duration_ms.into())
+    }
+
+    /// The item, if it's a track.
+    pub fn as_track(&self) -> Option<&track::Track> {
+        match self {
+            Self::Track(track) => Some(track),
+            Self::Episode(_) => None,
+        }
+    }
+
+    /// The item, if it's an episode.
+    pub fn as_episode(&self) -> Option<&show::Episode> {
+        match self {
+            Self::Track(_) => None,
+            Self::Episode(episode) => Some(episode),
+        }
+    }
+}
+
+#[cfg(feature = "url")]
+mod urls {
+    use url::Url;
+
+    use super::{
+        album::*, artist::*, audiobook::*, category::Category, player::Context, playlist::*,
+        recommendation::RecommendationSeed, show::*, track::*, user::*, CursorPage, ExternalUrls,
+        Image, Page,
+    };
+
+    impl ExternalUrls {
+        /// The Spotify URL, parsed.
+        pub fn to_url(&self) -> Option<Url> {
+            Url::parse(&self.spotify).ok()
+        }
+    }
+
+    impl Image {
+        /// The image URL, parsed.
+        pub fn to_url(&self) -> Option<Url> {
+            Url::parse(&self.url).ok()
+        }
+    }
+
+    macro_rules! impl_href_url {
+        ($($model:ty),+ $(,)?) => {
+            $(
+                impl $model {
+                    /// The API endpoint for this object, parsed.
+                    pub fn href_url(&self) -> Option<Url> {
+                        Url::parse(&self.href).ok()
+                    }
+                }
+            )+
+        };
+    }
+
+    macro_rules! impl_open_url {
+        ($($model:ty),+ $(,)?) => {
+            $(
+                impl $model {
+                    /// The URL for opening this object in the Spotify web player or app.
+                    pub fn open_url(&self) -> Option<Url> {
+                        self.external_urls.to_url()
+                    }
+                }
+            )+
+            impl_href_url!($($model),+);
+        };
+    }
+
+    impl_href_url!(Category, RecommendationSeed, TrackReference);
+
+    impl_open_url!(
+        Album,
+        SimplifiedAlbum,
+        Artist,
+        SimplifiedArtist,
+        Audiobook,
+        SimplifiedAudiobook,
+        Chapter,
+        SimplifiedChapter,
+        Context,
+        Playlist,
+        SimplifiedPlaylist,
+        Show,
+        SimplifiedShow,
+        Episode,
+        SimplifiedEpisode,
+        Track,
+        SimplifiedTrack,
+        LinkedFrom,
+        PrivateUser,
+        User,
+        ReferenceUser,
+    );
+
+    impl<T> Page<T> {
+        /// The API endpoint for this page, parsed.
+        pub fn href_url(&self) -> Option<Url> {
+            Url::parse(&self.href).ok()
+        }
+    }
+
+    impl<T> CursorPage<T> {
+        /// The API endpoint for this page, parsed.
+        pub fn href_url(&self) -> Option<Url> {
+            Url::parse(&self.href).ok()
+        }
+    }
+}
@@ -3,7 +3,8 @@ use serde::{Deserialize, Serialize};
 
 use super::{artist::SimplifiedArtist, track::SimplifiedTrack, *};
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Album {
     pub album_type: AlbumType,
     pub total_tracks: u32,
@@ -27,7 +28,8 @@ pub struct Album {
     pub tracks: Page<SimplifiedTrack>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SimplifiedAlbum {
     pub album_type: AlbumType,
     pub total_tracks: u32,
@@ -48,23 +50,32 @@ pub struct SimplifiedAlbum {
     pub artists: Vec<SimplifiedArtist>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SavedAlbum {
     pub added_at: DateTime<Utc>,
     pub album: Album,
 }
 
+impl super::SavedItem for SavedAlbum {
+    fn added_at(&self) -> DateTime<Utc> {
+        self.added_at
+    }
+}
+
 #[derive(Clone, Debug, Deserialize)]
 pub(crate) struct Albums {
     pub(crate) albums: Vec<Album>,
 }
 
+#[cfg(feature = "browse")]
 #[derive(Clone, Debug, Deserialize)]
 pub(crate) struct PagedAlbums {
     pub(crate) albums: Page<SimplifiedAlbum>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum AlbumType {
     #[serde(alias = "ALBUM")]
@@ -73,15 +84,26 @@ pub enum AlbumType {
     Single,
     #[serde(alias = "COMPILATION")]
     Compilation,
+    /// A value this version of the crate doesn't know about, which is only kept (instead
+    /// of failing to deserialise) with the `lenient` feature.
+    #[serde(untagged)]
+    #[cfg_attr(not(feature = "lenient"), serde(skip_deserializing))]
+    Other(String),
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum AlbumGroup {
     Album,
     Single,
     Compilation,
     AppearsOn,
+    /// A value this version of the crate doesn't know about, which is only kept (instead
+    /// of failing to deserialise) with the `lenient` feature.
+    #[serde(untagged)]
+    #[cfg_attr(not(feature = "lenient"), serde(skip_deserializing))]
+    Other(String),
 }
 
 impl AsRef<str> for AlbumGroup {
@@ -91,6 +113,7 @@ impl AsRef<str> for AlbumGroup {
             AlbumGroup::Single => "single",
             AlbumGroup::Compilation => "compilation",
             AlbumGroup::AppearsOn => "appears_on",
+            AlbumGroup::Other(group) => group,
         }
     }
 }
@@ -1,8 +1,9 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use super::*;
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Artist {
     pub external_urls: ExternalUrls,
     pub followers: Followers,
@@ -16,7 +17,8 @@ pub struct Artist {
     pub uri: String,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SimplifiedArtist {
     pub external_urls: ExternalUrls,
     pub href: String,
@@ -31,6 +33,7 @@ pub(crate) struct Artists {
     pub(crate) artists: Vec<Artist>,
 }
 
+#[cfg(feature = "user")]
 #[derive(Clone, Debug, Deserialize)]
 pub(crate) struct PagedArtists {
     pub(crate) artists: CursorPage<Artist>,
@@ -1,7 +1,7 @@
-use serde::Deserialize;
-use serde_repr::*;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct AudioFeatures {
     pub acousticness: f32,
     pub analysis_url: String,
@@ -27,10 +27,11 @@ pub struct AudioFeatures {
 
 #[derive(Clone, Debug, Deserialize)]
 pub(crate) struct AudioFeaturesResult {
-    pub(crate) audio_features: Vec<AudioFeatures>,
+    pub(crate) audio_features: Vec<Option<AudioFeatures>>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct AudioAnalysis {
     pub meta: Meta,
     pub track: TrackAnalysis,
@@ -41,7 +42,8 @@ pub struct AudioAnalysis {
     pub tatums: Vec<Tatum>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Meta {
     pub analyzer_version: String,
     pub platform: String,
@@ -53,7 +55,8 @@ pub struct Meta {
     pub input_process: String,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct TrackAnalysis {
     pub num_samples: u32,
     pub duration: f32,
@@ -84,21 +87,24 @@ pub struct TrackAnalysis {
     pub rhythm_version: f32,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Bar {
     pub start: f32,
     pub duration: f32,
     pub confidence: f32,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Beat {
     pub start: f32,
     pub duration: f32,
     pub confidence: f32,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Section {
     pub start: f32,
     pub duration: f32,
@@ -114,7 +120,8 @@ pub struct Section {
     pub time_signature_confidence: f32,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Segment {
     pub start: f32,
     pub duration: f32,
@@ -127,16 +134,67 @@ pub struct Segment {
     pub timbre: Vec<f32>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Tatum {
     pub start: f32,
     pub duration: f32,
     pub confidence: f32,
 }
 
-#[derive(Clone, Copy, Debug, Deserialize_repr)]
-#[repr(u8)]
+#[derive(Clone, Copy, Debug)]
 pub enum Mode {
     Minor,
     Major,
+    /// A value this version of the crate doesn't know about, which is only kept (instead
+    /// of failing to deserialise) with the `lenient` feature.
+    Other(i32),
+}
+
+impl From<Mode> for i32 {
+    fn from(mode: Mode) -> Self {
+        match mode {
+            Mode::Minor => 0,
+            Mode::Major => 1,
+            Mode::Other(value) => value,
+        }
+    }
+}
+
+impl Serialize for Mode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        i32::from(*self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Mode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match i32::deserialize(deserializer)? {
+            0 => Ok(Self::Minor),
+            1 => Ok(Self::Major),
+            #[cfg(feature = "lenient")]
+            value => Ok(Self::Other(value)),
+            #[cfg(not(feature = "lenient"))]
+            value => Err(serde::de::Error::invalid_value(
+                serde::de::Unexpected::Signed(value.into()),
+                &"0 or 1",
+            )),
+        }
+    }
+}
+
+// `Mode` is (de)serialised as its integer value, which the derive wouldn't know about.
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for Mode {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Mode".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "The modality of a track: 0 for minor, 1 for major.",
+            "type": "integer",
+            "enum": [0, 1]
+        })
+    }
 }
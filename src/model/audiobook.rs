@@ -1,8 +1,9 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use super::*;
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Audiobook {
     pub authors: Vec<Author>,
     #[serde(default)]
@@ -27,7 +28,8 @@ pub struct Audiobook {
     pub chapters: Page<SimplifiedChapter>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SimplifiedAudiobook {
     pub authors: Vec<Author>,
     #[serde(default)]
@@ -51,12 +53,14 @@ pub struct SimplifiedAudiobook {
     pub total_chapters: Option<u32>,
 }
 
+#[cfg(feature = "audiobooks")]
 #[derive(Clone, Debug, Deserialize)]
 pub(crate) struct Audiobooks {
     pub(crate) audiobooks: Vec<Audiobook>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Chapter {
     pub audio_preview_url: Option<String>,
     #[serde(default)]
@@ -82,7 +86,8 @@ pub struct Chapter {
     pub audiobook: SimplifiedAudiobook,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SimplifiedChapter {
     pub audio_preview_url: Option<String>,
     #[serde(default)]
@@ -107,17 +112,20 @@ pub struct SimplifiedChapter {
     pub restrictions: Option<Restrictions>,
 }
 
+#[cfg(feature = "audiobooks")]
 #[derive(Clone, Debug, Deserialize)]
 pub(crate) struct Chapters {
     pub(crate) chapters: Vec<Chapter>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Author {
     pub name: String,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Narrator {
     pub name: String,
 }
@@ -1,8 +1,11 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-use super::{Image, Page};
+use super::Image;
+#[cfg(feature = "browse")]
+use super::Page;
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Category {
     pub href: String,
     pub icons: Vec<Image>,
@@ -10,6 +13,7 @@ pub struct Category {
     pub name: String,
 }
 
+#[cfg(feature = "browse")]
 #[derive(Clone, Debug, Deserialize)]
 pub(crate) struct Categories {
     pub(crate) categories: Page<Category>,
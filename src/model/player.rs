@@ -1,9 +1,10 @@
 use chrono::{DateTime, Utc};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use super::{track::Track, *};
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PlaybackState {
     pub device: Option<Device>,
     pub repeat_state: Option<RepeatState>,
@@ -17,7 +18,8 @@ pub struct PlaybackState {
     pub actions: Actions,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Device {
     pub id: Option<String>,
     pub is_active: bool,
@@ -28,12 +30,14 @@ pub struct Device {
     pub volume_percent: Option<u32>,
 }
 
+#[cfg(feature = "player")]
 #[derive(Clone, Debug, Deserialize)]
 pub(crate) struct Devices {
     pub(crate) devices: Vec<Device>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Context {
     pub r#type: String,
     pub href: String,
@@ -42,12 +46,14 @@ pub struct Context {
 }
 
 /// Allows to update the user interface based on which playback actions are available within the current context.
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Actions {
     pub disallows: Disallows,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Disallows {
     pub interrupting_playback: Option<bool>,
     pub pausing: Option<bool>,
@@ -61,20 +67,43 @@ pub struct Disallows {
     pub transferring_playback: Option<bool>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PlayHistory {
     pub track: Track,
     pub played_at: DateTime<Utc>,
     pub context: Option<Context>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Queue {
     pub currently_playing: Option<PlayableItem>,
     pub queue: Vec<PlayableItem>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+impl Queue {
+    /// Whether the item with the given URI is currently playing or in the queue.
+    pub fn contains(&self, uri: &str) -> bool {
+        self.currently_playing
+            .iter()
+            .chain(&self.queue)
+            .any(|item| item.uri() == uri)
+    }
+
+    /// The tracks in the queue, leaving out any episodes.
+    pub fn tracks(&self) -> impl Iterator<Item = &Track> {
+        self.queue.iter().filter_map(PlayableItem::as_track)
+    }
+
+    /// The episodes in the queue, leaving out any tracks.
+    pub fn episodes(&self) -> impl Iterator<Item = &show::Episode> {
+        self.queue.iter().filter_map(PlayableItem::as_episode)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CurrentlyPlayingTrack {
     pub context: Option<Context>,
     pub timestamp: u64,
@@ -85,19 +114,31 @@ pub struct CurrentlyPlayingTrack {
     pub actions: Actions,
 }
 
-#[derive(Clone, Copy, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum RepeatState {
     Off,
     Track,
     Context,
+    /// A value this version of the crate doesn't know about, which is only kept (instead
+    /// of failing to deserialise) with the `lenient` feature.
+    #[serde(untagged)]
+    #[cfg_attr(not(feature = "lenient"), serde(skip_deserializing))]
+    Other(String),
 }
 
-#[derive(Clone, Copy, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum CurrentlyPlayingType {
     Track,
     Episode,
     Ad,
     Unknown,
+    /// A value this version of the crate doesn't know about, which is only kept (instead
+    /// of failing to deserialise) with the `lenient` feature.
+    #[serde(untagged)]
+    #[cfg_attr(not(feature = "lenient"), serde(skip_deserializing))]
+    Other(String),
 }
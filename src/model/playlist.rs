@@ -1,9 +1,10 @@
 use chrono::{DateTime, Utc};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use super::{user::ReferenceUser, *};
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Playlist {
     pub collaborative: bool,
     pub description: Option<String>,
@@ -21,7 +22,8 @@ pub struct Playlist {
     pub uri: String,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SimplifiedPlaylist {
     pub collaborative: bool,
     pub description: Option<String>,
@@ -40,12 +42,14 @@ pub struct SimplifiedPlaylist {
     pub uri: String,
 }
 
+#[cfg(feature = "playlists")]
 #[derive(Clone, Debug, Deserialize)]
 pub(crate) struct Playlists {
     pub(crate) playlists: Page<SimplifiedPlaylist>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PlaylistTrack {
     /// The date and time the track or episode was added. Note: some very old playlists may return null in this field.
     pub added_at: Option<DateTime<Utc>>,
@@ -55,7 +59,8 @@ pub struct PlaylistTrack {
     pub track: PlayableItem,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct FeaturedPlaylists {
     pub message: String,
     pub playlists: Page<SimplifiedPlaylist>,
@@ -63,13 +68,83 @@ pub struct FeaturedPlaylists {
 
 /// A collection containing a link (`href`) to the Web API endpoint where full details of the playlist's tracks can be retrieved,
 /// along with the total number of tracks in the playlist. Note, a track object may be `null`. This can happen if a track is no longer available.
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct TrackReference {
     pub href: String,
     pub total: u32,
 }
 
+/// The ID of a version of a playlist, which changes every time the playlist is modified.
+///
+/// It's returned by the requests that modify a playlist's items, and can be given to the
+/// next one so that it's made against that version of the playlist.
+#[cfg(feature = "playlists")]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(transparent)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct SnapshotId(String);
+
+#[cfg(feature = "playlists")]
+impl SnapshotId {
+    /// The snapshot ID as a string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(feature = "playlists")]
+impl std::fmt::Display for SnapshotId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(feature = "playlists")]
+impl AsRef<str> for SnapshotId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(feature = "playlists")]
+impl From<String> for SnapshotId {
+    fn from(snapshot_id: String) -> Self {
+        Self(snapshot_id)
+    }
+}
+
+#[cfg(feature = "playlists")]
+impl From<&str> for SnapshotId {
+    fn from(snapshot_id: &str) -> Self {
+        Self(snapshot_id.to_owned())
+    }
+}
+
+#[cfg(feature = "playlists")]
+impl From<&String> for SnapshotId {
+    fn from(snapshot_id: &String) -> Self {
+        Self(snapshot_id.clone())
+    }
+}
+
+#[cfg(feature = "playlists")]
+impl From<&SnapshotId> for SnapshotId {
+    fn from(snapshot_id: &SnapshotId) -> Self {
+        snapshot_id.clone()
+    }
+}
+
+#[cfg(feature = "playlists")]
+impl From<SnapshotId> for String {
+    fn from(snapshot_id: SnapshotId) -> Self {
+        snapshot_id.0
+    }
+}
+
+// The body of the responses to the requests that modify a playlist's items.
+#[cfg(feature = "playlists")]
 #[derive(Clone, Debug, Deserialize)]
-pub(crate) struct SnapshotId {
-    pub(crate) snapshot_id: String,
+pub(crate) struct SnapshotResponse {
+    pub(crate) snapshot_id: SnapshotId,
 }
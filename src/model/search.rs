@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use super::{
     album::SimplifiedAlbum,
@@ -10,7 +10,8 @@ use super::{
     Page,
 };
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SearchResults {
     pub tracks: Option<Page<Track>>,
     pub artists: Option<Page<Artist>>,
@@ -21,6 +22,43 @@ pub struct SearchResults {
     pub audiobooks: Option<Page<SimplifiedAudiobook>>,
 }
 
+impl SearchResults {
+    /// The tracks that were found, if tracks were searched for.
+    pub fn tracks(&self) -> Option<&Page<Track>> {
+        self.tracks.as_ref()
+    }
+
+    /// The artists that were found, if artists were searched for.
+    pub fn artists(&self) -> Option<&Page<Artist>> {
+        self.artists.as_ref()
+    }
+
+    /// The albums that were found, if albums were searched for.
+    pub fn albums(&self) -> Option<&Page<SimplifiedAlbum>> {
+        self.albums.as_ref()
+    }
+
+    /// The playlists that were found, if playlists were searched for.
+    pub fn playlists(&self) -> Option<&Page<SimplifiedPlaylist>> {
+        self.playlists.as_ref()
+    }
+
+    /// The shows that were found, if shows were searched for.
+    pub fn shows(&self) -> Option<&Page<SimplifiedShow>> {
+        self.shows.as_ref()
+    }
+
+    /// The episodes that were found, if episodes were searched for.
+    pub fn episodes(&self) -> Option<&Page<SimplifiedEpisode>> {
+        self.episodes.as_ref()
+    }
+
+    /// The audiobooks that were found, if audiobooks were searched for.
+    pub fn audiobooks(&self) -> Option<&Page<SimplifiedAudiobook>> {
+        self.audiobooks.as_ref()
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum Item {
     Album,
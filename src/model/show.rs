@@ -1,9 +1,10 @@
 use chrono::{DateTime, Utc};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use super::*;
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Show {
     #[serde(default)]
     pub available_markets: Vec<String>,
@@ -26,7 +27,8 @@ pub struct Show {
     pub episodes: Page<SimplifiedEpisode>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SimplifiedShow {
     #[serde(default)]
     pub available_markets: Vec<String>,
@@ -48,18 +50,27 @@ pub struct SimplifiedShow {
     pub total_episodes: u32,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SavedShow {
     pub added_at: DateTime<Utc>,
     pub show: SimplifiedShow,
 }
 
+impl super::SavedItem for SavedShow {
+    fn added_at(&self) -> DateTime<Utc> {
+        self.added_at
+    }
+}
+
+#[cfg(feature = "shows")]
 #[derive(Clone, Debug, Deserialize)]
 pub(crate) struct Shows {
     pub(crate) shows: Vec<Option<SimplifiedShow>>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Episode {
     pub audio_preview_url: Option<String>,
     pub description: String,
@@ -72,6 +83,9 @@ pub struct Episode {
     pub images: Vec<Image>,
     pub is_externally_hosted: bool,
     pub is_playable: bool,
+    /// The language of the episode, deprecated by Spotify in favour of `languages`.
+    #[serde(default)]
+    pub language: Option<String>,
     pub languages: Vec<String>,
     pub name: String,
     pub release_date: String,
@@ -83,7 +97,15 @@ pub struct Episode {
     pub show: SimplifiedShow,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+impl Episode {
+    /// Why the episode can't be played, if it's restricted.
+    pub fn restriction_reason(&self) -> Option<&RestrictionReason> {
+        self.restrictions.as_ref().map(|r| &r.reason)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SimplifiedEpisode {
     pub audio_preview_url: Option<String>,
     pub description: String,
@@ -96,6 +118,9 @@ pub struct SimplifiedEpisode {
     pub images: Vec<Image>,
     pub is_externally_hosted: bool,
     pub is_playable: bool,
+    /// The language of the episode, deprecated by Spotify in favour of `languages`.
+    #[serde(default)]
+    pub language: Option<String>,
     pub languages: Vec<String>,
     pub name: String,
     pub release_date: String,
@@ -106,13 +131,106 @@ pub struct SimplifiedEpisode {
     pub restrictions: Option<Restrictions>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+impl SimplifiedEpisode {
+    /// Why the episode can't be played, if it's restricted.
+    pub fn restriction_reason(&self) -> Option<&RestrictionReason> {
+        self.restrictions.as_ref().map(|r| &r.reason)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SavedEpisode {
     pub added_at: DateTime<Utc>,
     pub episode: Episode,
 }
 
+impl super::SavedItem for SavedEpisode {
+    fn added_at(&self) -> DateTime<Utc> {
+        self.added_at
+    }
+}
+
+#[cfg(feature = "shows")]
 #[derive(Clone, Debug, Deserialize)]
 pub(crate) struct Episodes {
     pub(crate) episodes: Vec<Episode>,
 }
+
+/// Shows and episodes, which are in one or more languages.
+///
+/// ```
+/// use spotify_rs::model::show::{Languages, SimplifiedShow};
+///
+/// fn in_spanish(shows: Vec<SimplifiedShow>) -> Vec<SimplifiedShow> {
+///     // Matches `es` as well as regional variants such as `es-MX`.
+///     shows.into_iter().filter(|s| s.is_in_language("es")).collect()
+/// }
+/// ```
+pub trait Languages {
+    /// The languages the item is in, as [BCP 47](https://www.rfc-editor.org/info/bcp47)
+    /// language tags, e.g. `en` or `en-US`.
+    fn language_tags(&self) -> impl Iterator<Item = &str>;
+
+    /// Whether the item is in a language matching the given language range,
+    /// following [`language_matches`].
+    fn is_in_language(&self, range: &str) -> bool {
+        self.language_tags().any(|tag| language_matches(tag, range))
+    }
+}
+
+impl Languages for Show {
+    fn language_tags(&self) -> impl Iterator<Item = &str> {
+        self.languages.iter().map(String::as_str)
+    }
+}
+
+impl Languages for SimplifiedShow {
+    fn language_tags(&self) -> impl Iterator<Item = &str> {
+        self.languages.iter().map(String::as_str)
+    }
+}
+
+impl Languages for Episode {
+    fn language_tags(&self) -> impl Iterator<Item = &str> {
+        self.languages
+            .iter()
+            .chain(&self.language)
+            .map(String::as_str)
+    }
+}
+
+impl Languages for SimplifiedEpisode {
+    fn language_tags(&self) -> impl Iterator<Item = &str> {
+        self.languages
+            .iter()
+            .chain(&self.language)
+            .map(String::as_str)
+    }
+}
+
+/// Whether a language tag (e.g. `en-US`) matches a language range (e.g. `en`), using
+/// the basic filtering of [RFC 4647](https://www.rfc-editor.org/rfc/rfc4647#section-3.3.1).
+///
+/// The range matches if it's equal to the tag or to a prefix of it that ends before
+/// a `-`, ignoring case, so `en` matches `en` and `en-GB`, but not `eng`. The range `*`
+/// matches every tag. Underscores, as in `en_GB`, are treated like hyphens.
+///
+/// ```
+/// use spotify_rs::model::show::language_matches;
+///
+/// assert!(language_matches("en-GB", "en"));
+/// assert!(language_matches("pt_BR", "pt-br"));
+/// assert!(!language_matches("en", "en-GB"));
+/// ```
+pub fn language_matches(tag: &str, range: &str) -> bool {
+    if range == "*" {
+        return true;
+    }
+
+    let normalise = |s: &str| s.replace('_', "-").to_ascii_lowercase();
+    let (tag, range) = (normalise(tag), normalise(range));
+
+    tag.strip_prefix(&range)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('-'))
+}
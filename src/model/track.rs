@@ -1,9 +1,10 @@
 use chrono::{DateTime, Utc};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use super::{album::SimplifiedAlbum, artist::SimplifiedArtist, *};
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Track {
     pub album: SimplifiedAlbum,
     pub artists: Vec<SimplifiedArtist>,
@@ -27,12 +28,57 @@ pub struct Track {
     pub is_local: bool,
 }
 
+// Track relinking: when a request has a market, Spotify replaces tracks that aren't
+// available in it with a version of them that is, and says which track was requested
+// in `linked_from`. See https://developer.spotify.com/documentation/web-api/concepts/track-relinking.
+macro_rules! impl_relinking {
+    ($($track:ty),*) => {$(
+        impl $track {
+            /// Returns `false` if Spotify said the track can't be played in the market the
+            /// request was made with, or that it's restricted.
+            ///
+            /// Spotify only says so when a market is given, e.g. with
+            /// [`ClientBuilder::default_market`](crate::client::ClientBuilder::default_market),
+            /// so without one, this is always `true`.
+            pub fn playable(&self) -> bool {
+                self.is_playable.unwrap_or(true) && self.restrictions.is_none()
+            }
+
+            /// Returns `true` if the track was relinked, i.e. it's a different version of the
+            /// track that was requested, which is available in the request's market.
+            pub fn is_relinked(&self) -> bool {
+                self.linked_from.is_some()
+            }
+
+            /// The ID of the track that was requested, which is the track's own ID unless it
+            /// was relinked. This is the ID to use for saving or removing the track.
+            pub fn requested_id(&self) -> &str {
+                self.linked_from.as_ref().map_or(&self.id, |l| &l.id)
+            }
+
+            /// The URI of the track that was requested, which is the track's own URI unless it
+            /// was relinked.
+            pub fn requested_uri(&self) -> &str {
+                self.linked_from.as_ref().map_or(&self.uri, |l| &l.uri)
+            }
+
+            /// Why the track can't be played, if it's restricted.
+            pub fn restriction_reason(&self) -> Option<&RestrictionReason> {
+                self.restrictions.as_ref().map(|r| &r.reason)
+            }
+        }
+    )*};
+}
+
+impl_relinking!(Track, SimplifiedTrack);
+
 #[derive(Clone, Debug, Deserialize)]
 pub(crate) struct Tracks {
     pub(crate) tracks: Vec<Track>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SimplifiedTrack {
     pub artists: Vec<SimplifiedArtist>,
     pub available_markets: Option<Vec<String>>,
@@ -53,13 +99,21 @@ pub struct SimplifiedTrack {
     pub is_local: bool,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SavedTrack {
     pub added_at: DateTime<Utc>,
     pub track: Track,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+impl super::SavedItem for SavedTrack {
+    fn added_at(&self) -> DateTime<Utc> {
+        self.added_at
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct LinkedFrom {
     pub external_urls: ExternalUrls,
     pub href: String,
@@ -4,7 +4,8 @@ use serde::{Deserialize, Serialize};
 
 use super::{artist::Artist, track::Track, *};
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PrivateUser {
     pub country: String,
     pub display_name: Option<String>,
@@ -22,7 +23,8 @@ pub struct PrivateUser {
     pub uri: String,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct User {
     pub display_name: Option<String>,
     pub external_urls: ExternalUrls,
@@ -35,7 +37,8 @@ pub struct User {
 }
 
 // Returned by the get/playlist/{id} endpoint; also called "PlaylistUserObject" in the schema
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ReferenceUser {
     pub external_urls: ExternalUrls,
     pub followers: Option<Followers>,
@@ -46,7 +49,8 @@ pub struct ReferenceUser {
     pub display_name: Option<String>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ExplicitContent {
     pub filter_enabled: bool,
     pub filter_locked: bool,
@@ -71,6 +75,7 @@ impl Display for UserItemType {
 }
 
 #[derive(Clone, Debug, Default, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum TimeRange {
     LongTerm,
@@ -79,7 +84,8 @@ pub enum TimeRange {
     ShortTerm,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum UserItem {
     Artist(Box<Artist>),
@@ -0,0 +1,124 @@
+//! Serving responses from local fixtures instead of the Spotify API.
+//!
+//! A client created with [`Client::offline`](crate::client::Client::offline) never sends
+//! requests over the network. Instead, it looks up each response in a [`FixtureStore`],
+//! keyed by endpoint. This is useful for demos, CI and developing against endpoints
+//! while rate-limited.
+//!
+//! The [`DirectoryStore`] reads the fixtures from JSON files, laid out like the API's paths:
+//!
+//! ```text
+//! fixtures/
+//! ├── albums/
+//! │   └── 4OHNH3sDzIxnmUADXzv2kT.json   GET /albums/4OHNH3sDzIxnmUADXzv2kT
+//! ├── me/
+//! │   ├── player.json                   GET /me/player
+//! │   └── player/
+//! │       └── play.put.json             PUT /me/player/play
+//! └── me.json                           GET /me
+//! ```
+//!
+//! ```no_run
+//! use spotify_rs::{offline::DirectoryStore, AuthCodeClient};
+//! # use std::error::Error;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn Error>> {
+//! let spotify = AuthCodeClient::offline(DirectoryStore::new("fixtures"));
+//!
+//! let album = spotify.album("4OHNH3sDzIxnmUADXzv2kT").get().await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Any other source of fixtures can be used by implementing [`FixtureStore`], which is
+//! already implemented for a `HashMap` from paths to JSON strings.
+
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use bytes::Bytes;
+use reqwest::{Method, StatusCode};
+use serde_json::json;
+
+/// A source of responses for a client in offline mode.
+pub trait FixtureStore: Debug + Send + Sync {
+    /// Returns the response body for a request to the endpoint at `path`
+    /// (e.g. `/albums/{id}`), or `None` if there isn't a fixture for it.
+    ///
+    /// The query parameters are passed along, but are usually not needed to pick a fixture.
+    fn fixture(&self, method: &Method, path: &str, query: Option<&str>) -> Option<Vec<u8>>;
+}
+
+/// A [`FixtureStore`] that reads fixtures from JSON files in a directory.
+///
+/// `GET` requests are served from `{path}.json`, and other requests from
+/// `{path}.{method}.json`, with the method in lowercase (e.g. `me/player/play.put.json`).
+///
+/// Since most endpoints that modify data don't return anything, requests other than
+/// `GET` succeed with an empty response if there isn't a fixture for them.
+#[derive(Clone, Debug)]
+pub struct DirectoryStore {
+    root: PathBuf,
+}
+
+impl DirectoryStore {
+    /// Create a store that reads fixtures from the directory at `root`.
+    pub fn new(root: impl AsRef<Path>) -> Self {
+        Self {
+            root: root.as_ref().to_owned(),
+        }
+    }
+}
+
+impl FixtureStore for DirectoryStore {
+    fn fixture(&self, method: &Method, path: &str, _query: Option<&str>) -> Option<Vec<u8>> {
+        let path = path.trim_matches('/');
+
+        let file = if method == Method::GET {
+            format!("{path}.json")
+        } else {
+            format!("{path}.{}.json", method.as_str().to_lowercase())
+        };
+
+        match fs::read(self.root.join(file)) {
+            Ok(body) => Some(body),
+            Err(_) if method != Method::GET => Some(Vec::new()),
+            Err(_) => None,
+        }
+    }
+}
+
+/// Serves `GET` requests by looking up the path (e.g. `/albums/{id}`) in the map.
+impl FixtureStore for HashMap<String, String> {
+    fn fixture(&self, method: &Method, path: &str, _query: Option<&str>) -> Option<Vec<u8>> {
+        match self.get(path) {
+            Some(body) if method == Method::GET => Some(body.clone().into_bytes()),
+            _ if method != Method::GET => Some(Vec::new()),
+            _ => None,
+        }
+    }
+}
+
+pub(crate) fn respond(
+    store: &dyn FixtureStore,
+    method: &Method,
+    path: &str,
+    query: Option<&str>,
+) -> (StatusCode, Bytes) {
+    let path = path.split('?').next().unwrap_or(path);
+
+    match store.fixture(method, path, query) {
+        Some(body) => (StatusCode::OK, body.into()),
+        None => {
+            let message = format!("No fixture for {method} {path}");
+            let body = json!({ "error": { "status": 404, "message": message } });
+
+            (StatusCode::NOT_FOUND, body.to_string().into())
+        }
+    }
+}
@@ -0,0 +1,78 @@
+// OpenTelemetry instrumentation of the requests sent to the Spotify API.
+//
+// Every request gets a client span, which is a child of the current context, and the
+// context is propagated to Spotify using the globally configured propagator (usually
+// the W3C `traceparent` header).
+
+use opentelemetry::{
+    global,
+    propagation::Injector,
+    trace::{SpanKind, Status, TraceContextExt, Tracer},
+    Context, KeyValue,
+};
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue},
+    Method, StatusCode, Url,
+};
+
+/// Starts the span for a request, returning a context that contains it.
+pub(crate) fn start(method: &Method, url: &Url) -> Context {
+    let tracer = global::tracer("spotify-rs");
+
+    let span = tracer
+        .span_builder(method.to_string())
+        .with_kind(SpanKind::Client)
+        .with_attributes([
+            KeyValue::new("http.request.method", method.to_string()),
+            KeyValue::new(
+                "server.address",
+                url.host_str().unwrap_or_default().to_owned(),
+            ),
+            KeyValue::new("url.full", url.to_string()),
+        ])
+        .start(&tracer);
+
+    Context::current_with_span(span)
+}
+
+/// The headers that propagate the context to Spotify.
+pub(crate) fn headers(cx: &Context) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    global::get_text_map_propagator(|p| p.inject_context(cx, &mut HeaderInjector(&mut headers)));
+
+    headers
+}
+
+/// Ends the span for a request, with the response's status or the error that occurred.
+pub(crate) fn end(cx: &Context, result: Result<StatusCode, &crate::error::Error>) {
+    let span = cx.span();
+
+    match result {
+        Ok(status) => {
+            span.set_attribute(KeyValue::new(
+                "http.response.status_code",
+                i64::from(status.as_u16()),
+            ));
+
+            if status.is_client_error() || status.is_server_error() {
+                span.set_status(Status::error(status.to_string()));
+            }
+        }
+        Err(e) => span.set_status(Status::error(e.to_string())),
+    }
+
+    span.end();
+}
+
+struct HeaderInjector<'a>(&'a mut HeaderMap);
+
+impl Injector for HeaderInjector<'_> {
+    fn set(&mut self, key: &str, value: String) {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(key.as_bytes()),
+            HeaderValue::from_str(&value),
+        ) {
+            self.0.insert(name, value);
+        }
+    }
+}
@@ -0,0 +1,98 @@
+//! Watching the user's playback for changes.
+//!
+//! Spotify doesn't push playback changes to Web API clients, so [`watch_playback`] polls
+//! the playback state at an interval and only yields it when something has changed, such
+//! as the track, the device or whether it's paused.
+//!
+//! Like the client, it isn't tied to an async runtime, so it's given the function to
+//! wait with, such as `tokio::time::sleep`.
+//!
+//! ```no_run
+//! use std::time::Duration;
+//! use futures_util::StreamExt;
+//! use spotify_rs::{player::watch_playback, AuthCodeClient};
+//! # async fn sleep(_: Duration) {}
+//!
+//! # async fn example(spotify: &AuthCodeClient) -> spotify_rs::SpotifyResult<()> {
+//! let mut states = Box::pin(watch_playback(spotify, Duration::from_secs(5), sleep));
+//!
+//! while let Some(state) = states.next().await {
+//!     match state? {
+//!         Some(state) => println!("Playing: {}", state.is_playing),
+//!         None => println!("Nothing is playing"),
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{future::Future, time::Duration};
+
+use futures_util::{stream, Stream};
+use serde_json::Value;
+
+use crate::{
+    auth::{AuthFlow, Authorised, Token},
+    client::Client,
+    error::Result,
+    model::player::PlaybackState,
+};
+
+/// Poll the user's playback state every `interval`, waiting with `sleep`, and yield it
+/// whenever it changes.
+///
+/// The state is `None` while nothing is playing, which is yielded once when playback
+/// stops, rather than as an error. States that only differ in how far the item has
+/// played aren't yielded, so seeking within an item isn't noticed either.
+///
+/// Errors are yielded as they happen, and polling carries on after them, so it's up to
+/// the caller to stop on the ones it can't recover from.
+pub fn watch_playback<'a, F, S, Fut>(
+    spotify: &'a Client<Token, F>,
+    interval: Duration,
+    sleep: S,
+) -> impl Stream<Item = Result<Option<PlaybackState>>> + 'a
+where
+    F: AuthFlow + Authorised,
+    S: Fn(Duration) -> Fut + 'a,
+    Fut: Future<Output = ()> + 'a,
+{
+    // Whether to wait before polling, and the last state yielded without its progress.
+    let start: (bool, Option<Option<Value>>) = (false, None);
+
+    stream::unfold(
+        (start, sleep),
+        move |((mut wait, last), sleep)| async move {
+            loop {
+                if wait {
+                    sleep(interval).await;
+                }
+
+                wait = true;
+
+                let state = match spotify.current_playback().await {
+                    Ok(state) => state,
+                    Err(err) => return Some((Err(err), ((wait, last), sleep))),
+                };
+
+                let key = state.as_ref().map(without_progress);
+
+                if last.as_ref() != Some(&key) {
+                    return Some((Ok(state), ((wait, Some(key)), sleep)));
+                }
+            }
+        },
+    )
+}
+
+// The parts of a playback state that are compared to tell whether it has changed.
+fn without_progress(state: &PlaybackState) -> Value {
+    let mut value = serde_json::to_value(state).unwrap_or_default();
+
+    if let Some(fields) = value.as_object_mut() {
+        fields.remove("progress_ms");
+        fields.remove("timestamp");
+    }
+
+    value
+}
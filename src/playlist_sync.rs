@@ -0,0 +1,273 @@
+//! Making a playlist's items match a list of URIs with as few changes as possible.
+//!
+//! Replacing every item of a playlist resets when each of them was added and by whom,
+//! and makes the playlist's history harder to follow. [`sync`] instead works out which
+//! items have to be removed, moved and added, and only changes those, splitting the
+//! changes into requests of at most 100 items and chaining their snapshot IDs.
+//!
+//! ```no_run
+//! use spotify_rs::{playlist_sync, AuthCodeClient};
+//!
+//! # async fn example(spotify: &AuthCodeClient) -> spotify_rs::SpotifyResult<()> {
+//! let desired = [
+//!     "spotify:track:4iV5W9uYEdYUVa79Axb7Rh",
+//!     "spotify:track:1301WleyT98MSxVHPZCA6M",
+//! ];
+//!
+//! let snapshot_id = playlist_sync::sync(spotify, "3cEYpjA9oz9GiPac4AsH4n", &desired).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The changes can also be computed with [`diff`] without making any requests, e.g. to
+//! show them to the user first, and made later with [`apply`].
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::{
+    auth::{AuthFlow, Token},
+    client::Client,
+    error::Result,
+    id::{AsId, PlaylistId},
+    model::playlist::SnapshotId,
+};
+
+// The most items that can be added or removed in one request.
+const CHUNK_SIZE: usize = 100;
+
+/// A change to a playlist's items, which is made with a single request.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Operation {
+    /// Remove every occurrence of the items with these URIs.
+    Remove { uris: Vec<String> },
+    /// Move the item at `range_start` so that it's right before the item that's
+    /// currently at `insert_before`.
+    Move {
+        range_start: u32,
+        insert_before: u32,
+    },
+    /// Insert the items with these URIs at `position`.
+    Add { uris: Vec<String>, position: u32 },
+}
+
+/// Compute the operations that turn a playlist with the `current` items into one with
+/// the `desired` items, in the order in which they have to be applied.
+///
+/// Spotify can only remove an item by removing every occurrence of its URI, so a URI
+/// that occurs more often than desired is removed entirely and then added back.
+///
+/// ```
+/// use spotify_rs::playlist_sync::{diff, Operation};
+///
+/// let operations = diff(&["a", "b", "c"], &["c", "a", "d"]);
+///
+/// assert_eq!(
+///     operations,
+///     [
+///         Operation::Remove { uris: vec!["b".to_owned()] },
+///         Operation::Move { range_start: 0, insert_before: 2 },
+///         Operation::Add { uris: vec!["d".to_owned()], position: 2 },
+///     ]
+/// );
+/// ```
+pub fn diff<C: AsRef<str>, D: AsRef<str>>(current: &[C], desired: &[D]) -> Vec<Operation> {
+    let current: Vec<&str> = current.iter().map(AsRef::as_ref).collect();
+    let desired: Vec<&str> = desired.iter().map(AsRef::as_ref).collect();
+
+    let mut operations = vec![];
+
+    let mut current_count: HashMap<&str, usize> = HashMap::new();
+    for &uri in &current {
+        *current_count.entry(uri).or_default() += 1;
+    }
+
+    // The desired positions of each URI, in order.
+    let mut positions: HashMap<&str, VecDeque<usize>> = HashMap::new();
+    for (i, &uri) in desired.iter().enumerate() {
+        positions.entry(uri).or_default().push_back(i);
+    }
+
+    let mut removed = HashSet::new();
+    let mut removed_uris = vec![];
+
+    for &uri in &current {
+        let desired_count = positions.get(uri).map_or(0, VecDeque::len);
+
+        if current_count[uri] > desired_count && removed.insert(uri) {
+            removed_uris.push(uri);
+        }
+    }
+
+    operations.extend(
+        removed_uris
+            .chunks(CHUNK_SIZE)
+            .map(|uris| Operation::Remove {
+                uris: uris.iter().map(ToString::to_string).collect(),
+            }),
+    );
+
+    // The items left after removing, each as the desired position it's matched with.
+    let mut items: Vec<usize> = current
+        .iter()
+        .filter(|uri| !removed.contains(*uri))
+        .filter_map(|uri| positions.get_mut(uri)?.pop_front())
+        .collect();
+
+    let mut matched = vec![false; desired.len()];
+    for &position in &items {
+        matched[position] = true;
+    }
+
+    // The longest run of items already in the desired order stays in place, and every
+    // other item is moved right after the items that should come before it.
+    let mut in_order = longest_increasing(&items);
+    let mut to_move: Vec<usize> = items
+        .iter()
+        .zip(&in_order)
+        .filter(|(_, &in_order)| !in_order)
+        .map(|(&position, _)| position)
+        .collect();
+    to_move.sort_unstable();
+
+    for position in to_move {
+        let Some(from) = items.iter().position(|&p| p == position) else {
+            continue;
+        };
+
+        items.remove(from);
+        in_order.remove(from);
+
+        let to = items
+            .iter()
+            .zip(&in_order)
+            .position(|(&p, &in_order)| in_order && p > position)
+            .unwrap_or(items.len());
+
+        items.insert(to, position);
+        in_order.insert(to, true);
+
+        if to != from {
+            // The position to insert before is counted before the item is taken out.
+            let insert_before = if to < from { to } else { to + 1 };
+
+            operations.push(Operation::Move {
+                range_start: from as u32,
+                insert_before: insert_before as u32,
+            });
+        }
+    }
+
+    // The items left are now in the desired order, so every missing item goes at its
+    // desired position, once the missing items before it have been added.
+    let mut start = 0;
+
+    while start < desired.len() {
+        if matched[start] {
+            start += 1;
+            continue;
+        }
+
+        let end = (start..desired.len())
+            .find(|&i| matched[i])
+            .unwrap_or(desired.len());
+
+        for (i, uris) in desired[start..end].chunks(CHUNK_SIZE).enumerate() {
+            operations.push(Operation::Add {
+                uris: uris.iter().map(ToString::to_string).collect(),
+                position: (start + i * CHUNK_SIZE) as u32,
+            });
+        }
+
+        start = end;
+    }
+
+    operations
+}
+
+// Which of the values make up the longest strictly increasing subsequence.
+fn longest_increasing(values: &[usize]) -> Vec<bool> {
+    // The index of the last value of the best subsequence of each length.
+    let mut tails: Vec<usize> = vec![];
+    // The index of the value before each value in its best subsequence.
+    let mut previous = vec![None; values.len()];
+
+    for (i, &value) in values.iter().enumerate() {
+        let length = tails.partition_point(|&t| values[t] < value);
+
+        if length > 0 {
+            previous[i] = Some(tails[length - 1]);
+        }
+
+        if length == tails.len() {
+            tails.push(i);
+        } else {
+            tails[length] = i;
+        }
+    }
+
+    let mut in_subsequence = vec![false; values.len()];
+    let mut next = tails.last().copied();
+
+    while let Some(i) = next {
+        in_subsequence[i] = true;
+        next = previous[i];
+    }
+
+    in_subsequence
+}
+
+/// Apply the `operations` (as computed by [`diff`]) to a playlist, one request at a time.
+///
+/// Each request is made against the snapshot of the playlist returned by the one before,
+/// starting with `snapshot_id` if given. Returns the snapshot ID of the playlist after the
+/// last request, or `snapshot_id` if there were no operations.
+pub async fn apply<F: AuthFlow>(
+    spotify: &Client<Token, F>,
+    playlist_id: impl AsId<PlaylistId>,
+    snapshot_id: Option<SnapshotId>,
+    operations: &[Operation],
+) -> Result<Option<SnapshotId>> {
+    let mut editor = spotify.edit_playlist(playlist_id);
+    editor.snapshot_id = snapshot_id;
+
+    for operation in operations {
+        match operation {
+            Operation::Remove { uris } => editor.remove(uris).await?,
+            Operation::Move {
+                range_start,
+                insert_before,
+            } => editor.reorder(*range_start, *insert_before, 1).await?,
+            Operation::Add { uris, position } => editor.add(uris, Some(*position)).await?,
+        };
+    }
+
+    Ok(editor.snapshot_id)
+}
+
+/// Make a playlist's items match the `desired` URIs, changing as few items as possible.
+///
+/// Gets all of the playlist's items, then [`diff`]s and [`apply`]s them. Returns the
+/// snapshot ID of the playlist once it's been synced.
+pub async fn sync<F: AuthFlow, T: AsRef<str>>(
+    spotify: &Client<Token, F>,
+    playlist_id: impl AsId<PlaylistId>,
+    desired: &[T],
+) -> Result<SnapshotId> {
+    let id = playlist_id.as_id();
+
+    let mut playlist = spotify.playlist(id).get().await?;
+    spotify.get_remaining_playlist_items(&mut playlist).await?;
+
+    let current: Vec<&str> = playlist
+        .tracks
+        .items
+        .iter()
+        .map(|item| item.track.uri())
+        .collect();
+
+    let operations = diff(&current, desired);
+    let snapshot_id = SnapshotId::from(playlist.snapshot_id);
+    let new_snapshot_id = apply(spotify, id, Some(snapshot_id.clone()), &operations).await?;
+
+    Ok(new_snapshot_id.unwrap_or(snapshot_id))
+}
@@ -0,0 +1,39 @@
+//! Re-exports of the most commonly used types, so getting started only takes one `use` line.
+//!
+//! ```
+//! use spotify_rs::prelude::*;
+//! ```
+
+pub use oauth2::{RedirectUrl, Scope};
+
+pub use crate::{
+    auth::{AuthCodeFlow, AuthCodePkceFlow, ClientCredsFlow, ImplicitGrantFlow, Token},
+    client::{
+        AuthCodeClient, AuthCodePkceClient, Client, ClientBuilder, ClientCredsClient,
+        ImplicitGrantClient,
+    },
+    endpoint::{
+        album::AlbumEndpoint,
+        artist::ArtistEndpoint,
+        track::{Feature, Seed, Seeds, TrackEndpoint},
+        Builder, FetchById,
+    },
+    error::{Error, Result as SpotifyResult},
+    id::{
+        AlbumId, ArtistId, AsId, AudiobookId, ChapterId, EpisodeId, PlaylistId, ShowId, SpotifyUri,
+        TrackId, UserId,
+    },
+    model::{
+        album::AlbumGroup,
+        user::{TimeRange, UserItemType},
+        CursorPage, Image, Page, PlayableItem,
+    },
+    Nil,
+};
+
+#[cfg(feature = "player")]
+pub use crate::{client::PreferredDevice, endpoint::player::RepeatMode};
+#[cfg(feature = "playlists")]
+pub use crate::{endpoint::playlist::PlaylistEndpoint, model::playlist::SnapshotId};
+#[cfg(feature = "search")]
+pub use crate::{endpoint::search::SearchQuery, model::search::Item};
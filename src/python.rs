@@ -0,0 +1,364 @@
+//! A Python facade of the client, built with [PyO3](https://pyo3.rs).
+//!
+//! The [`SpotifyClient`] class covers every authorisation flow, and all of its methods
+//! return awaitables, which run on a Tokio runtime in the background. Responses are
+//! returned as plain Python dictionaries and lists, with the same structure as the
+//! models (and the Spotify API), so they can be loaded straight into e.g. pandas.
+//!
+//! To build the Python module, create a `cdylib` crate named `spotify_rs` (e.g. with
+//! [maturin](https://www.maturin.rs)) that depends on this one with the `python` feature
+//! and re-exports the module function:
+//!
+//! ```ignore
+//! // lib.rs of the cdylib crate
+//! pub use spotify_rs::python::spotify_rs;
+//! ```
+//!
+//! ```python
+//! import asyncio
+//! from spotify_rs import SpotifyClient
+//!
+//! async def main():
+//!     spotify = await SpotifyClient.client_credentials("client_id", "client_secret")
+//!     results = await spotify.search("Daft Punk", ["artist"], limit=5)
+//!     print([artist["name"] for artist in results["artists"]["items"]])
+//!
+//! asyncio.run(main())
+//! ```
+
+use std::{future::Future, sync::Arc};
+
+use pyo3::{create_exception, exceptions::PyException, prelude::*};
+use serde::Serialize;
+
+#[cfg(feature = "player")]
+use crate::facade::with_user_client;
+use crate::{
+    error::Error,
+    facade::{with_client, AnyClient},
+};
+
+create_exception!(
+    spotify_rs,
+    SpotifyError,
+    PyException,
+    "An error returned by the Spotify client or the Spotify API."
+);
+
+/// A Spotify client, authenticated with any of the authorisation flows.
+#[pyclass(module = "spotify_rs")]
+pub struct SpotifyClient {
+    inner: Arc<AnyClient>,
+}
+
+#[pymethods]
+impl SpotifyClient {
+    /// Authenticate using the Client Credentials Flow, which can't access user information.
+    #[staticmethod]
+    fn client_credentials(
+        py: Python<'_>,
+        client_id: String,
+        client_secret: String,
+    ) -> PyResult<Bound<'_, PyAny>> {
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let client = AnyClient::client_credentials(client_id, client_secret)
+                .await
+                .map_err(to_py_err)?;
+
+            Ok(Self::new(client))
+        })
+    }
+
+    /// Authenticate using a refresh token obtained from the Authorisation Code Flow,
+    /// or from the Authorisation Code Flow with PKCE if there is no client secret.
+    ///
+    /// The access token is refreshed automatically when it expires.
+    #[staticmethod]
+    #[pyo3(signature = (client_id, refresh_token, client_secret = None))]
+    fn from_refresh_token(
+        py: Python<'_>,
+        client_id: String,
+        refresh_token: String,
+        client_secret: Option<String>,
+    ) -> PyResult<Bound<'_, PyAny>> {
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let client = AnyClient::from_refresh_token(client_id, client_secret, refresh_token)
+                .await
+                .map_err(to_py_err)?;
+
+            Ok(Self::new(client))
+        })
+    }
+
+    /// Get a track.
+    fn track<'py>(&self, py: Python<'py>, id: String) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        to_py_future(py, async move {
+            with_client!(inner, client => client.track(id).get().await)
+        })
+    }
+
+    /// Get an album.
+    fn album<'py>(&self, py: Python<'py>, id: String) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        to_py_future(py, async move {
+            with_client!(inner, client => client.album(id).get().await)
+        })
+    }
+
+    /// Get an artist.
+    fn artist<'py>(&self, py: Python<'py>, id: String) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        to_py_future(py, async move {
+            with_client!(inner, client => client.artist(id).get().await)
+        })
+    }
+
+    /// Search for items of the given types, e.g. `["track", "album"]`.
+    #[cfg(feature = "search")]
+    #[pyo3(signature = (query, types, limit = None))]
+    fn search<'py>(
+        &self,
+        py: Python<'py>,
+        query: String,
+        types: Vec<String>,
+        limit: Option<u32>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        use crate::model::search::Item;
+
+        let types = types
+            .iter()
+            .map(|t| {
+                Item::all()
+                    .iter()
+                    .find(|i| i.as_ref() == t)
+                    .cloned()
+                    .ok_or_else(|| {
+                        pyo3::exceptions::PyValueError::new_err(format!("Unknown item type: {t}"))
+                    })
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+
+        let inner = self.inner.clone();
+        to_py_future(py, async move {
+            with_client!(inner, client => {
+                let mut search = client.search(query, &types);
+
+                if let Some(limit) = limit {
+                    search = search.limit(limit);
+                }
+
+                search.get().await
+            })
+        })
+    }
+
+    /// Get a playlist.
+    #[cfg(feature = "playlists")]
+    fn playlist<'py>(&self, py: Python<'py>, id: String) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        to_py_future(py, async move {
+            with_client!(inner, client => client.playlist(id).get().await)
+        })
+    }
+
+    /// Create a playlist for a user.
+    #[cfg(feature = "playlists")]
+    #[pyo3(signature = (user_id, name, public = None, description = None))]
+    fn create_playlist<'py>(
+        &self,
+        py: Python<'py>,
+        user_id: String,
+        name: String,
+        public: Option<bool>,
+        description: Option<String>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        to_py_future(py, async move {
+            with_client!(inner, client => {
+                let mut playlist = client.create_playlist(user_id, name);
+
+                if let Some(public) = public {
+                    playlist = playlist.public(public);
+                }
+
+                if let Some(description) = description {
+                    playlist = playlist.description(description);
+                }
+
+                playlist.send().await
+            })
+        })
+    }
+
+    /// Add items to a playlist, returning the playlist's new snapshot ID.
+    #[cfg(feature = "playlists")]
+    #[pyo3(signature = (id, uris, position = None))]
+    fn add_items_to_playlist<'py>(
+        &self,
+        py: Python<'py>,
+        id: String,
+        uris: Vec<String>,
+        position: Option<u32>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        to_py_future(py, async move {
+            with_client!(inner, client => {
+                let mut add = client.add_items_to_playlist(id, &uris);
+
+                if let Some(position) = position {
+                    add = add.position(position);
+                }
+
+                add.send().await
+            })
+        })
+    }
+
+    /// Remove items from a playlist, returning the playlist's new snapshot ID.
+    #[cfg(feature = "playlists")]
+    fn remove_playlist_items<'py>(
+        &self,
+        py: Python<'py>,
+        id: String,
+        uris: Vec<String>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        to_py_future(py, async move {
+            with_client!(inner, client => client.remove_playlist_items(id, &uris).send().await)
+        })
+    }
+
+    /// Get the user's current playback state.
+    #[cfg(feature = "player")]
+    fn playback_state<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        to_py_future(py, async move {
+            with_user_client!(inner, client => {
+                client.get_playback_state(None).await
+            }, else Err(unauthorised()))
+        })
+    }
+
+    /// Start or resume playback, optionally on a specific device.
+    #[cfg(feature = "player")]
+    #[pyo3(signature = (device_id = None))]
+    fn start_playback<'py>(
+        &self,
+        py: Python<'py>,
+        device_id: Option<String>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        to_py_none_future(py, async move {
+            with_user_client!(inner, client => {
+                let mut playback = client.start_playback();
+
+                if let Some(device_id) = device_id {
+                    playback = playback.device_id(device_id);
+                }
+
+                playback.send().await
+            }, else Err(unauthorised()))
+        })
+    }
+
+    /// Pause playback, optionally on a specific device.
+    #[cfg(feature = "player")]
+    #[pyo3(signature = (device_id = None))]
+    fn pause_playback<'py>(
+        &self,
+        py: Python<'py>,
+        device_id: Option<String>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        to_py_none_future(py, async move {
+            with_user_client!(inner, client => {
+                client.pause_playback(device_id.as_deref()).await
+            }, else Err(unauthorised()))
+        })
+    }
+
+    /// Skip to the next item in the user's queue, optionally on a specific device.
+    #[cfg(feature = "player")]
+    #[pyo3(signature = (device_id = None))]
+    fn skip_to_next<'py>(
+        &self,
+        py: Python<'py>,
+        device_id: Option<String>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        to_py_none_future(py, async move {
+            with_user_client!(inner, client => {
+                client.skip_to_next(device_id.as_deref()).await
+            }, else Err(unauthorised()))
+        })
+    }
+
+    /// Skip to the previous item in the user's queue, optionally on a specific device.
+    #[cfg(feature = "player")]
+    #[pyo3(signature = (device_id = None))]
+    fn skip_to_previous<'py>(
+        &self,
+        py: Python<'py>,
+        device_id: Option<String>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        to_py_none_future(py, async move {
+            with_user_client!(inner, client => {
+                client.skip_to_previous(device_id.as_deref()).await
+            }, else Err(unauthorised()))
+        })
+    }
+}
+
+impl SpotifyClient {
+    fn new(inner: AnyClient) -> Self {
+        Self {
+            inner: Arc::new(inner),
+        }
+    }
+}
+
+/// The `spotify_rs` Python module.
+#[pymodule]
+pub fn spotify_rs(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<SpotifyClient>()?;
+    m.add("SpotifyError", m.py().get_type::<SpotifyError>())?;
+
+    Ok(())
+}
+
+// Converts a future returning a model into an awaitable returning its Python representation.
+fn to_py_future<F, T>(py: Python<'_>, fut: F) -> PyResult<Bound<'_, PyAny>>
+where
+    F: Future<Output = crate::error::Result<T>> + Send + 'static,
+    T: Serialize + Send + 'static,
+{
+    pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let value = fut.await.map_err(to_py_err)?;
+
+        Python::attach(|py| Ok(pythonize::pythonize(py, &value)?.unbind()))
+    })
+}
+
+// Converts a future returning nothing into an awaitable returning `None`.
+#[cfg(feature = "player")]
+fn to_py_none_future<F, T>(py: Python<'_>, fut: F) -> PyResult<Bound<'_, PyAny>>
+where
+    F: Future<Output = crate::error::Result<T>> + Send + 'static,
+    T: Send + 'static,
+{
+    pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        fut.await.map_err(to_py_err)?;
+        Ok(())
+    })
+}
+
+fn to_py_err(err: Error) -> PyErr {
+    SpotifyError::new_err(err.to_string())
+}
+
+#[cfg(feature = "player")]
+fn unauthorised() -> Error {
+    Error::Configuration("This endpoint requires a user to authorise the client.".to_owned())
+}
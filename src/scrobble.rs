@@ -0,0 +1,276 @@
+//! Recording listening sessions and submitting them to scrobbling services.
+//!
+//! A [`SessionRecorder`] is fed the user's playback state, e.g. by polling
+//! [`Client::current_playback`](crate::client::Client::current_playback), and works out
+//! what is playing and which tracks have been listened to. It reports both to a
+//! [`ScrobbleSink`], which can submit them to Last.fm, ListenBrainz, a database, etc.
+//!
+//! A track counts as listened to (and is scrobbled) once it has played for half of its
+//! duration or four minutes, whichever comes first, and only if it's longer than 30 seconds,
+//! following the rules used by Last.fm. Only the time spent actually playing counts,
+//! so pausing or seeking forward doesn't.
+//!
+//! ```no_run
+//! use spotify_rs::{
+//!     model::track::Track,
+//!     scrobble::{Scrobble, ScrobbleSink, SessionRecorder},
+//!     AuthCodeClient,
+//! };
+//! # use std::error::Error;
+//! # async fn wait_a_bit() {}
+//!
+//! struct Printer;
+//!
+//! impl ScrobbleSink for Printer {
+//!     type Error = std::io::Error;
+//!
+//!     async fn now_playing(&mut self, track: &Track) -> Result<(), Self::Error> {
+//!         println!("Now playing: {}", track.name);
+//!         Ok(())
+//!     }
+//!
+//!     async fn scrobble(&mut self, scrobbles: &[Scrobble]) -> Result<(), Self::Error> {
+//!         for scrobble in scrobbles {
+//!             println!("Listened to {} at {}", scrobble.track.name, scrobble.started_at);
+//!         }
+//!         Ok(())
+//!     }
+//! }
+//!
+//! # async fn example(spotify: &AuthCodeClient) -> Result<(), Box<dyn Error>> {
+//! let mut recorder = SessionRecorder::new(Printer).batch_size(10);
+//!
+//! loop {
+//!     // `None` when nothing is playing. Errors are returned rather than treated as
+//!     // nothing playing, which would end the play in progress.
+//!     let state = spotify.current_playback().await?;
+//!     recorder.record(state.as_ref()).await?;
+//!     wait_a_bit().await;
+//! }
+//! # }
+//! ```
+
+use std::future::Future;
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{
+    client::RetryPolicy,
+    model::{player::PlaybackState, track::Track, PlayableItem},
+};
+
+/// A destination for the listening activity recorded by a [`SessionRecorder`].
+pub trait ScrobbleSink {
+    /// The error returned when submitting fails.
+    type Error;
+
+    /// Report that the user started playing `track`.
+    fn now_playing(&mut self, track: &Track) -> impl Future<Output = Result<(), Self::Error>>;
+
+    /// Submit a batch of tracks the user listened to, oldest first.
+    fn scrobble(&mut self, scrobbles: &[Scrobble])
+        -> impl Future<Output = Result<(), Self::Error>>;
+}
+
+/// A track the user listened to.
+#[derive(Clone, Debug)]
+pub struct Scrobble {
+    /// The track that was played.
+    pub track: Track,
+    /// When the track started playing.
+    pub started_at: DateTime<Utc>,
+    /// How long the track was actually played for, in milliseconds.
+    pub played_ms: u32,
+}
+
+/// Tracks the playback state over time and reports listening activity to a [`ScrobbleSink`].
+///
+/// Completed plays are collected and submitted in batches. If submitting a batch fails
+/// (even after [retrying](Self::retry_policy)), the scrobbles are kept and submitted again by the next flush,
+/// so nothing is lost when a service is temporarily unavailable.
+#[derive(Debug)]
+pub struct SessionRecorder<S: ScrobbleSink> {
+    sink: S,
+    batch_size: usize,
+    retry_policy: Option<RetryPolicy>,
+    current: Option<Current>,
+    pending: Vec<Scrobble>,
+}
+
+#[derive(Debug)]
+struct Current {
+    track: Track,
+    started_at: DateTime<Utc>,
+    played_ms: u32,
+    last_progress_ms: u32,
+    last_seen: DateTime<Utc>,
+    now_playing_sent: bool,
+}
+
+impl<S: ScrobbleSink> SessionRecorder<S> {
+    /// Create a recorder that reports to `sink`, submitting every completed play immediately.
+    pub fn new(sink: S) -> Self {
+        Self {
+            sink,
+            batch_size: 1,
+            retry_policy: None,
+            current: None,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Submit completed plays in batches of `batch_size` (at least 1).
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Retry submitting a batch that failed, up to the policy's
+    /// [`max_retries`](RetryPolicy::max_retries) times, before giving up until the next flush.
+    ///
+    /// Each retry waits a second, or backs off from the policy's
+    /// [`retry_server_errors`](RetryPolicy::retry_server_errors) delay if it's set.
+    /// Without a policy, a failed batch is only submitted again by the next flush.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// The completed plays that haven't been submitted yet.
+    pub fn pending(&self) -> &[Scrobble] {
+        &self.pending
+    }
+
+    /// Get a reference to the sink.
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    /// Update the recorder with the current playback state, or `None` if nothing is playing.
+    ///
+    /// This should be called regularly (e.g. every 15 seconds); the more often it's called,
+    /// the more accurately the time spent listening is measured.
+    pub async fn record(&mut self, state: Option<&PlaybackState>) -> Result<(), S::Error> {
+        let now = Utc::now();
+        let playing = state.and_then(|s| match &s.item {
+            Some(PlayableItem::Track(track)) => {
+                Some((track, s.progress_ms.unwrap_or(0), s.is_playing))
+            }
+            _ => None,
+        });
+
+        let Some((track, progress_ms, is_playing)) = playing else {
+            self.finish_current();
+            return self.flush_full_batch().await;
+        };
+
+        let same_track = self
+            .current
+            .as_ref()
+            .is_some_and(|c| c.track.id == track.id);
+        // The track was played again from the start, e.g. with repeat turned on.
+        let restarted = same_track
+            && self.current.as_ref().is_some_and(|c| {
+                progress_ms < c.last_progress_ms && qualifies(&c.track, c.played_ms)
+            });
+
+        if !same_track || restarted {
+            self.finish_current();
+            self.current = Some(Current {
+                track: track.clone(),
+                started_at: now - Duration::milliseconds(i64::from(progress_ms)),
+                played_ms: 0,
+                last_progress_ms: progress_ms,
+                last_seen: now,
+                now_playing_sent: false,
+            });
+        } else if let Some(current) = &mut self.current {
+            // Only count the progress that could have been made since the last update,
+            // so seeking forward doesn't count as listening.
+            let elapsed = (now - current.last_seen).num_milliseconds().max(0);
+            let progressed = i64::from(progress_ms) - i64::from(current.last_progress_ms);
+            let played = progressed.clamp(0, elapsed);
+
+            current.played_ms = current.played_ms.saturating_add(played as u32);
+            current.last_progress_ms = progress_ms;
+            current.last_seen = now;
+        }
+
+        let mut result = Ok(());
+
+        if let Some(current) = self
+            .current
+            .as_mut()
+            .filter(|c| is_playing && !c.now_playing_sent)
+        {
+            result = self.sink.now_playing(&current.track).await;
+            current.now_playing_sent = result.is_ok();
+        }
+
+        self.flush_full_batch().await.and(result)
+    }
+
+    /// Submit all of the pending plays, regardless of the batch size.
+    ///
+    /// This should be called before dropping the recorder, along with `record(None)` to
+    /// count the play in progress, if it qualifies.
+    pub async fn flush(&mut self) -> Result<(), S::Error> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+
+        let mut retries = 0;
+
+        loop {
+            let err = match self.sink.scrobble(&self.pending).await {
+                Ok(()) => {
+                    self.pending.clear();
+                    return Ok(());
+                }
+                Err(err) => err,
+            };
+
+            let Some((policy, wait)) = self.retry_policy.as_ref().and_then(|policy| {
+                let wait = policy.server_error_wait(retries);
+                Some((policy, wait.or_else(|| policy.wait(retries, None))?))
+            }) else {
+                return Err(err);
+            };
+
+            policy.sleep(wait).await;
+            retries += 1;
+        }
+    }
+
+    /// Consume the recorder, returning the sink.
+    pub fn into_sink(self) -> S {
+        self.sink
+    }
+
+    fn finish_current(&mut self) {
+        if let Some(current) = self.current.take() {
+            if qualifies(&current.track, current.played_ms) {
+                self.pending.push(Scrobble {
+                    track: current.track,
+                    started_at: current.started_at,
+                    played_ms: current.played_ms,
+                });
+            }
+        }
+    }
+
+    async fn flush_full_batch(&mut self) -> Result<(), S::Error> {
+        if self.pending.len() >= self.batch_size {
+            self.flush().await
+        } else {
+            Ok(())
+        }
+    }
+}
+
+fn qualifies(track: &Track, played_ms: u32) -> bool {
+    const MIN_DURATION_MS: u32 = 30_000;
+    const MAX_REQUIRED_MS: u32 = 4 * 60 * 1000;
+
+    track.duration_ms > MIN_DURATION_MS && played_ms >= (track.duration_ms / 2).min(MAX_REQUIRED_MS)
+}
@@ -0,0 +1,81 @@
+//! Sample payloads and model constructors for writing tests.
+//!
+//! The raw JSON fixtures in [`fixtures`] are real-world shaped responses, which can
+//! be served by a mocked client or parsed directly. The functions in this module
+//! parse them into the matching models.
+//!
+//! All of the model fields are public, so a sample can be adjusted using
+//! struct update syntax:
+//!
+//! ```
+//! use spotify_rs::{model::track::Track, test_support};
+//!
+//! let track = Track {
+//!     name: "Custom Name".to_owned(),
+//!     explicit: true,
+//!     ..test_support::track()
+//! };
+//!
+//! let page = test_support::page(vec![track]);
+//! assert_eq!(page.total, 1);
+//!
+//! let state = test_support::playback_state();
+//! assert!(state.is_playing);
+//! assert_eq!(test_support::playlist_page().items.len(), 2);
+//! ```
+
+use crate::model::{
+    player::PlaybackState, playlist::PlaylistTrack, track::Track, Page, PlayableItem,
+};
+
+/// Raw JSON responses, as returned by the Spotify API.
+pub mod fixtures {
+    /// A full track, as returned by `GET /tracks/{id}`.
+    pub const TRACK: &str = include_str!("fixtures/track.json");
+    /// A page of playlist items containing two tracks, as returned by `GET /playlists/{id}/tracks`.
+    pub const PLAYLIST_PAGE: &str = include_str!("fixtures/playlist_page.json");
+    /// The state of an active playback, as returned by `GET /me/player`.
+    pub const PLAYBACK_STATE: &str = include_str!("fixtures/playback_state.json");
+}
+
+/// A full track, parsed from [`fixtures::TRACK`].
+pub fn track() -> Track {
+    parse(fixtures::TRACK)
+}
+
+/// A page of playlist items, parsed from [`fixtures::PLAYLIST_PAGE`].
+pub fn playlist_page() -> Page<PlaylistTrack> {
+    parse(fixtures::PLAYLIST_PAGE)
+}
+
+/// The state of an active playback of [`track`], parsed from [`fixtures::PLAYBACK_STATE`].
+pub fn playback_state() -> PlaybackState {
+    parse(fixtures::PLAYBACK_STATE)
+}
+
+/// A single, complete page containing `items`.
+pub fn page<T>(items: Vec<T>) -> Page<T> {
+    let total = items.len() as u32;
+
+    Page {
+        href: "https://api.spotify.com/v1".to_owned(),
+        limit: total.max(1),
+        next: None,
+        offset: 0,
+        previous: None,
+        total,
+        items,
+    }
+}
+
+/// A playlist item for `track`, added by the same user as the items in [`playlist_page`].
+pub fn playlist_track(track: Track) -> PlaylistTrack {
+    PlaylistTrack {
+        track: PlayableItem::Track(track),
+        ..playlist_page().items.remove(0)
+    }
+}
+
+fn parse<T: serde::de::DeserializeOwned>(json: &str) -> T {
+    serde_json::from_str(json).expect("the bundled fixtures should match the models")
+}
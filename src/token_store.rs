@@ -0,0 +1,178 @@
+//! Persisting the client's token, so an app doesn't have to go through the authorisation
+//! flow again every time it starts.
+//!
+//! Once a client is given a [`TokenStore`] (with [`ClientBuilder::token_store`]), every new
+//! token it gets, from authenticating or refreshing, is saved to it. A client can then be
+//! created from the saved token with [`Client::from_token_store`].
+//!
+//! ```no_run
+//! use spotify_rs::{token_store::FileTokenStore, AuthCodeClient, AuthCodeFlow};
+//! # use std::error::Error;
+//!
+//! # async fn example() -> Result<(), Box<dyn Error>> {
+//! let auth_flow = AuthCodeFlow::new("client_id", "client_secret", ["user-library-read"]);
+//! let store = FileTokenStore::new("spotify_token.json");
+//!
+//! let spotify = AuthCodeClient::from_token_store(auth_flow, store, true).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`ClientBuilder::token_store`]: crate::ClientBuilder::token_store
+//! [`Client::from_token_store`]: crate::client::Client::from_token_store
+
+use std::{
+    fmt,
+    future::Future,
+    io::ErrorKind,
+    path::PathBuf,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use crate::{
+    auth::Token,
+    endpoint::BoxFuture,
+    error::{Error, Result},
+};
+
+/// Somewhere the client's token is saved to and loaded from.
+pub trait TokenStore: Send + Sync {
+    /// Load the saved token, or `None` if no token has been saved yet.
+    fn load(&self) -> impl Future<Output = Result<Option<Token>>> + Send;
+
+    /// Save `token`, replacing the previously saved one.
+    fn save(&self, token: &Token) -> impl Future<Output = Result<()>> + Send;
+}
+
+// `TokenStore` can't be used as a trait object, as its methods return `impl Future`,
+// so the client stores it through this trait instead.
+trait ErasedTokenStore: Send + Sync {
+    fn load(&self) -> BoxFuture<'_, Result<Option<Token>>>;
+    fn save<'a>(&'a self, token: &'a Token) -> BoxFuture<'a, Result<()>>;
+}
+
+impl<T: TokenStore> ErasedTokenStore for T {
+    fn load(&self) -> BoxFuture<'_, Result<Option<Token>>> {
+        Box::pin(TokenStore::load(self))
+    }
+
+    fn save<'a>(&'a self, token: &'a Token) -> BoxFuture<'a, Result<()>> {
+        Box::pin(TokenStore::save(self, token))
+    }
+}
+
+/// The token store of a client.
+#[derive(Clone)]
+pub(crate) struct SharedTokenStore(Arc<dyn ErasedTokenStore>);
+
+impl SharedTokenStore {
+    pub(crate) fn new(store: impl TokenStore + 'static) -> Self {
+        Self(Arc::new(store))
+    }
+
+    pub(crate) async fn save(&self, token: &Token) -> Result<()> {
+        self.0.save(token).await
+    }
+
+    pub(crate) async fn load(&self) -> Result<Option<Token>> {
+        self.0.load().await
+    }
+}
+
+impl fmt::Debug for SharedTokenStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedTokenStore").finish_non_exhaustive()
+    }
+}
+
+/// Saves the token as JSON in a file.
+///
+/// The file is replaced as a whole on every save, so a crash can't leave it half-written,
+/// and on Unix it's only readable by its owner. It's read and written with blocking I/O,
+/// which is fine for a file this small that's only written when the token changes.
+#[derive(Clone, Debug)]
+pub struct FileTokenStore {
+    path: PathBuf,
+}
+
+impl FileTokenStore {
+    /// Create a store that saves the token to the file at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl TokenStore for FileTokenStore {
+    async fn load(&self) -> Result<Option<Token>> {
+        let json = match std::fs::read(&self.path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(Error::TokenStore(e.to_string())),
+        };
+
+        serde_json::from_slice(&json)
+            .map(Some)
+            .map_err(|e| Error::TokenStore(e.to_string()))
+    }
+
+    async fn save(&self, token: &Token) -> Result<()> {
+        let json =
+            serde_json::to_vec_pretty(token).map_err(|e| Error::TokenStore(e.to_string()))?;
+        let tmp = self.path.with_extension("tmp");
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        let write = || {
+            std::io::Write::write_all(&mut options.open(&tmp)?, &json)?;
+            std::fs::rename(&tmp, &self.path)
+        };
+
+        write().map_err(|e| Error::TokenStore(e.to_string()))
+    }
+}
+
+/// Keeps the token in memory, e.g. for tests, or to hand it to another part of an app
+/// that persists it some other way.
+///
+/// Clones of the store share the same token.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryTokenStore {
+    token: Arc<Mutex<Option<Token>>>,
+}
+
+impl MemoryTokenStore {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a store that already contains `token`.
+    pub fn with_token(token: Token) -> Self {
+        Self {
+            token: Arc::new(Mutex::new(Some(token))),
+        }
+    }
+
+    /// Get the saved token, if there is one.
+    pub fn token(&self) -> Option<Token> {
+        self.token
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+impl TokenStore for MemoryTokenStore {
+    async fn load(&self) -> Result<Option<Token>> {
+        Ok(self.token())
+    }
+
+    async fn save(&self, token: &Token) -> Result<()> {
+        *self.token.lock().unwrap_or_else(PoisonError::into_inner) = Some(token.clone());
+        Ok(())
+    }
+}
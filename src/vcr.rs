@@ -0,0 +1,297 @@
+//! Recording real API interactions to fixture files and replaying them in tests.
+//!
+//! A [`Cassette`] in record mode lets requests through to Spotify and saves every
+//! interaction to a JSON file. The same file can then be loaded in replay mode, where
+//! requests are answered from the file in the order they were recorded, without
+//! network access.
+//!
+//! Only the method, path, query, status and response body of each interaction are saved.
+//! Request headers (including the access token) and request bodies are never written
+//! to the file.
+//!
+//! Response bodies can contain personal data, so before they're saved, the user objects
+//! in them (such as the current user's profile, or a playlist's owner) have their ID,
+//! display name, email address, country, birthdate and images replaced. Anything else,
+//! including user IDs in the path or query (e.g. `/users/{user_id}/playlists`), is saved
+//! as is; more can be redacted with [`Cassette::redact`]. Check a fixture before
+//! committing it.
+//!
+//! ```no_run
+//! use spotify_rs::{vcr::Cassette, AuthCodeClient, ClientBuilder, AuthCodeFlow};
+//! # use std::error::Error;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn Error>> {
+//! // Record once, against the real API...
+//! let auth_flow = AuthCodeFlow::new("client_id", "client_secret", ["user-library-read"]);
+//! let spotify = ClientBuilder::new(auth_flow)
+//!     .cassette(Cassette::record("tests/fixtures/album.json"))
+//!     .from_refresh_token("refresh_token")
+//!     .await?;
+//!
+//! spotify.album("album_id").get().await?;
+//!
+//! // ...then replay it in tests.
+//! let spotify = AuthCodeClient::replay(Cassette::replay("tests/fixtures/album.json")?);
+//! let album = spotify.album("album_id").get().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use bytes::Bytes;
+use reqwest::{Method, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{Error, Result};
+
+/// What the personal data in user objects is replaced with.
+const REDACTED: &str = "redacted";
+
+type Redact = Arc<dyn Fn(&mut Value) + Send + Sync>;
+
+/// A fixture file that API interactions are recorded to or replayed from.
+///
+/// Cloning a `Cassette` is cheap, and all clones share the same state.
+#[derive(Clone, Debug)]
+pub struct Cassette {
+    inner: Arc<Mutex<State>>,
+}
+
+struct State {
+    mode: Mode,
+    path: PathBuf,
+    interactions: Vec<Interaction>,
+    // Whether each interaction has already been replayed.
+    replayed: Vec<bool>,
+    redact: Option<Redact>,
+}
+
+impl fmt::Debug for State {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("State")
+            .field("mode", &self.mode)
+            .field("path", &self.path)
+            .field("interactions", &self.interactions)
+            .field("replayed", &self.replayed)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Mode {
+    Record,
+    Replay,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct Interaction {
+    method: String,
+    path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    query: Option<String>,
+    status: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<Value>,
+}
+
+impl Cassette {
+    /// Record every interaction to the file at `path`, replacing it if it already exists.
+    ///
+    /// The file is written after every interaction, so it's complete even if a test panics.
+    pub fn record(path: impl AsRef<Path>) -> Self {
+        Self::new(Mode::Record, path.as_ref().to_owned(), Vec::new())
+    }
+
+    /// Replay the interactions recorded in the file at `path`.
+    ///
+    /// Requests are matched by method, path and query. If the same request was recorded
+    /// more than once, the recorded responses are returned in order.
+    pub fn replay(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_owned();
+        let interactions = serde_json::from_slice(&fs::read(&path)?)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        Ok(Self::new(Mode::Replay, path, interactions))
+    }
+
+    /// Also redact the JSON response bodies with `redact` before they're recorded, after the
+    /// user objects in them are redacted. Only the saved copy is changed, not the response
+    /// the client returns.
+    ///
+    /// ```no_run
+    /// use spotify_rs::vcr::Cassette;
+    ///
+    /// // Hide the names of the user's playlists.
+    /// let cassette = Cassette::record("tests/fixtures/playlists.json").redact(|body| {
+    ///     for playlist in body["items"].as_array_mut().into_iter().flatten() {
+    ///         playlist["name"] = "Playlist".into();
+    ///     }
+    /// });
+    /// ```
+    pub fn redact(self, redact: impl Fn(&mut serde_json::Value) + Send + Sync + 'static) -> Self {
+        self.state().redact = Some(Arc::new(redact));
+        self
+    }
+
+    fn new(mode: Mode, path: PathBuf, interactions: Vec<Interaction>) -> Self {
+        let replayed = vec![false; interactions.len()];
+
+        Self {
+            inner: Arc::new(Mutex::new(State {
+                mode,
+                path,
+                interactions,
+                replayed,
+                redact: None,
+            })),
+        }
+    }
+
+    /// Returns the recorded response for the request in replay mode, or `None` in record mode.
+    pub(crate) fn replay_request(
+        &self,
+        method: &Method,
+        path: &str,
+        query: Option<&str>,
+    ) -> Result<Option<(StatusCode, Bytes)>> {
+        let mut state = self.state();
+
+        if state.mode == Mode::Record {
+            return Ok(None);
+        }
+
+        let path = path.split('?').next().unwrap_or(path);
+
+        let index = state
+            .interactions
+            .iter()
+            .zip(&state.replayed)
+            .position(|(i, replayed)| {
+                !replayed
+                    && i.method == method.as_str()
+                    && i.path == path
+                    && i.query.as_deref() == query
+            })
+            .ok_or_else(|| {
+                let query = query.map(|q| format!("?{q}")).unwrap_or_default();
+                Error::Http(format!(
+                    "No recorded interaction left for {method} {path}{query} in {}",
+                    state.path.display()
+                ))
+            })?;
+
+        state.replayed[index] = true;
+
+        let interaction = &state.interactions[index];
+        let status =
+            StatusCode::from_u16(interaction.status).map_err(|e| Error::Http(e.to_string()))?;
+        let body = match &interaction.body {
+            Some(body) => serde_json::to_vec(body)
+                .map_err(|e| Error::Http(e.to_string()))?
+                .into(),
+            None => Bytes::new(),
+        };
+
+        Ok(Some((status, body)))
+    }
+
+    /// Saves the interaction to the file in record mode.
+    pub(crate) fn record_response(
+        &self,
+        method: &Method,
+        path: &str,
+        query: Option<&str>,
+        status: StatusCode,
+        body: &[u8],
+    ) -> Result<()> {
+        let mut state = self.state();
+
+        if state.mode == Mode::Replay {
+            return Ok(());
+        }
+
+        // Non-JSON bodies (which Spotify only sends for some errors) are kept as strings.
+        let body = (!body.is_empty()).then(|| match serde_json::from_slice(body) {
+            Ok(mut body) => {
+                redact_users(&mut body);
+                if let Some(redact) = &state.redact {
+                    redact(&mut body);
+                }
+                body
+            }
+            Err(_) => Value::String(String::from_utf8_lossy(body).into_owned()),
+        });
+
+        state.interactions.push(Interaction {
+            method: method.to_string(),
+            path: path.split('?').next().unwrap_or(path).to_owned(),
+            query: query.map(ToOwned::to_owned),
+            status: status.as_u16(),
+            body,
+        });
+
+        let json = serde_json::to_vec_pretty(&state.interactions)
+            .map_err(|e| Error::Http(e.to_string()))?;
+
+        fs::write(&state.path, json).map_err(|e| {
+            Error::Http(format!(
+                "Failed to write the cassette to {}: {e}",
+                state.path.display()
+            ))
+        })
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+// Replace the personal data in every user object (one with a `type` of `user`) in `value`.
+fn redact_users(value: &mut Value) {
+    match value {
+        Value::Array(values) => values.iter_mut().for_each(redact_users),
+        Value::Object(object) => {
+            if object.get("type").and_then(Value::as_str) == Some("user") {
+                // The ID is also part of the user's URI, `href` and external URLs.
+                if let Some(id) = object
+                    .get("id")
+                    .and_then(Value::as_str)
+                    .filter(|id| !id.is_empty())
+                    .map(str::to_owned)
+                {
+                    object.values_mut().for_each(|v| replace_in_strings(v, &id));
+                }
+
+                for field in ["display_name", "email", "country", "birthdate"] {
+                    if let Some(value @ Value::String(_)) = object.get_mut(field) {
+                        *value = REDACTED.into();
+                    }
+                }
+                if let Some(images) = object.get_mut("images") {
+                    *images = Value::Array(Vec::new());
+                }
+            }
+
+            object.values_mut().for_each(redact_users);
+        }
+        _ => {}
+    }
+}
+
+fn replace_in_strings(value: &mut Value, from: &str) {
+    match value {
+        Value::String(s) => *s = s.replace(from, REDACTED),
+        Value::Array(values) => values.iter_mut().for_each(|v| replace_in_strings(v, from)),
+        Value::Object(object) => object
+            .values_mut()
+            .for_each(|v| replace_in_strings(v, from)),
+        _ => {}
+    }
+}
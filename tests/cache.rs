@@ -0,0 +1,51 @@
+//! The response cache, which only keeps responses that are the same for every user, as it
+//! can be shared by clients for different users.
+
+mod common;
+
+use common::{serve, Response};
+use spotify_rs::{cache::MemoryCache, AuthCodeFlow, ClientBuilder};
+
+#[tokio::test]
+async fn only_public_responses_are_cached() {
+    let (url, requests) = serve(|request| {
+        let path = request.split(' ').nth(1).unwrap_or_default();
+
+        match path.split('?').next().unwrap_or_default() {
+            "/v1/markets" => Response::new("200 OK", r#"{"markets":["GB"]}"#)
+                .header("cache-control", "public, max-age=3600"),
+            "/v1/recommendations/available-genre-seeds" => {
+                Response::new("200 OK", r#"{"genres":["rock"]}"#)
+                    .header("cache-control", "private, max-age=3600")
+            }
+            "/v1/me/tracks/contains" => {
+                Response::new("200 OK", "[true]").header("cache-control", "public, max-age=3600")
+            }
+            _ => Response::new("404 Not Found", ""),
+        }
+    })
+    .await;
+
+    let cache = MemoryCache::new(100);
+    let auth_flow = AuthCodeFlow::new("client_id", "client_secret", ["user-library-read"]);
+    let spotify = ClientBuilder::new(auth_flow)
+        .api_url(format!("{url}/v1"))
+        .cache(cache.clone())
+        .from_access_token("token", 3600, None)
+        .await
+        .unwrap();
+
+    for _ in 0..2 {
+        spotify.get_available_markets().await.unwrap();
+        spotify.get_genre_seeds().await.unwrap();
+        spotify.check_saved_tracks(&["a"]).await.unwrap();
+    }
+
+    let requests = requests.lock().unwrap();
+    let count = |path: &str| requests.iter().filter(|r| r.contains(path)).count();
+
+    assert_eq!(count("/v1/markets"), 1);
+    assert_eq!(count("/v1/recommendations/available-genre-seeds"), 2);
+    assert_eq!(count("/v1/me/tracks/contains"), 2);
+    assert_eq!(cache.len(), 1);
+}
@@ -0,0 +1,77 @@
+//! Endpoints that take a list of IDs, which are split into several requests if needed,
+//! or sent one at a time with `fetch_many`.
+
+#![cfg(feature = "mock")]
+
+use serde_json::json;
+use spotify_rs::{mock::Mock, model::user::User, AuthCodeClient, Error};
+
+#[tokio::test]
+async fn empty_id_lists_send_no_requests() {
+    let mock = Mock::new();
+    let spotify = AuthCodeClient::mock(mock.clone());
+    let none: &[&str] = &[];
+
+    assert!(spotify.tracks(none).get().await.unwrap().is_empty());
+    assert!(spotify.albums(none).get().await.unwrap().is_empty());
+    assert!(spotify.artists(none).get().await.unwrap().is_empty());
+    assert!(mock.requests().is_empty());
+}
+
+#[tokio::test]
+async fn long_id_lists_are_split() {
+    let mock = Mock::new().on_get("/artists", json!({ "artists": [] }));
+    let spotify = AuthCodeClient::mock(mock.clone());
+    let ids: Vec<_> = (0..120).map(|i| format!("{i:022}")).collect();
+
+    spotify.artists(&ids).get().await.unwrap();
+
+    let requests = mock.requests();
+    assert_eq!(requests.len(), 3);
+    assert!(requests[0].query.as_deref().unwrap().starts_with("ids=000"));
+}
+
+#[tokio::test]
+async fn meta_is_none_without_a_request() {
+    let mock = Mock::new().on_get("/tracks", json!({ "tracks": [] }));
+    let spotify = AuthCodeClient::mock(mock.clone());
+    let none: &[&str] = &[];
+
+    let (tracks, meta) = spotify.tracks(none).get_with_meta().await.unwrap();
+    assert!(tracks.is_empty());
+    assert!(meta.is_none());
+
+    let (_, meta) = spotify.tracks(&["a"]).get_with_meta().await.unwrap();
+    assert_eq!(meta.unwrap().status, 200);
+}
+
+#[tokio::test]
+async fn fetch_many_keeps_the_order_and_failures() {
+    let user = |id: &str| {
+        json!({
+            "display_name": null,
+            "external_urls": { "spotify": format!("https://open.spotify.com/user/{id}") },
+            "followers": { "href": null, "total": 0 },
+            "href": format!("https://api.spotify.com/v1/users/{id}"),
+            "id": id,
+            "images": [],
+            "type": "user",
+            "uri": format!("spotify:user:{id}"),
+        })
+    };
+    let mock = Mock::new()
+        .on_get("/users/a", user("a"))
+        .on_error("GET", "/users/b", 404, "No such user")
+        .on_get("/users/c", user("c"));
+    let spotify = AuthCodeClient::mock(mock.clone());
+
+    let users = spotify
+        .fetch_many::<User, _>(&["a", "b", "c"], 2)
+        .await
+        .unwrap();
+
+    assert_eq!(users[0].as_ref().unwrap().id, "a");
+    assert!(matches!(users[1], Err(Error::Spotify { status: 404, .. })));
+    assert_eq!(users[2].as_ref().unwrap().id, "c");
+    assert_eq!(mock.requests().len(), 3);
+}
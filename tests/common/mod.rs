@@ -0,0 +1,71 @@
+//! A minimal HTTP server standing in for the Spotify API and accounts service, for tests
+//! that need real responses (with headers and status codes the mock can't produce).
+
+#![allow(dead_code)]
+
+use std::sync::{Arc, Mutex};
+
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+};
+
+/// A response to send: its status line (e.g. `200 OK`), extra headers and body.
+pub struct Response {
+    pub status: &'static str,
+    pub headers: Vec<(&'static str, &'static str)>,
+    pub body: String,
+}
+
+impl Response {
+    pub fn new(status: &'static str, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: body.into(),
+        }
+    }
+
+    pub fn header(mut self, name: &'static str, value: &'static str) -> Self {
+        self.headers.push((name, value));
+        self
+    }
+}
+
+/// Start a server that answers every request with `respond`, which is given the raw
+/// request (request line and headers). Returns its base URL and the request lines
+/// received so far, e.g. `GET /v1/markets HTTP/1.1`.
+pub async fn serve(
+    respond: impl Fn(&str) -> Response + Send + Sync + 'static,
+) -> (String, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let received = requests.clone();
+
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let mut buf = vec![0; 8192];
+            let len = stream.read(&mut buf).await.unwrap();
+            let request = String::from_utf8_lossy(&buf[..len]).into_owned();
+            let line = request.lines().next().unwrap_or_default().to_owned();
+            received.lock().unwrap().push(line);
+
+            let response = respond(&request);
+            let mut head = format!(
+                "HTTP/1.1 {}\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\
+                 connection: close\r\n",
+                response.status,
+                response.body.len()
+            );
+            for (name, value) in response.headers {
+                head.push_str(&format!("{name}: {value}\r\n"));
+            }
+
+            let raw = format!("{head}\r\n{}", response.body);
+            stream.write_all(raw.as_bytes()).await.unwrap();
+        }
+    });
+
+    (url, requests)
+}
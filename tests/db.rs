@@ -0,0 +1,185 @@
+//! Round trips of the typed IDs and markets through SQLite, with sqlx and Diesel.
+
+#![cfg(any(feature = "sqlx", feature = "diesel"))]
+
+use spotify_rs::{
+    id::{PlaylistId, TrackId},
+    market::Market,
+};
+
+const TRACK: &str = "6rqhFgbbKwnb9MLmUQDhG6";
+const PLAYLIST: &str = "37i9dQZF1DXcBWIGoYBM5M";
+
+#[cfg(feature = "sqlx")]
+mod sqlx_round_trip {
+    use sqlx::{Connection, Row, SqliteConnection};
+
+    use super::*;
+
+    async fn connect() -> SqliteConnection {
+        let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+
+        sqlx::query(
+            "CREATE TABLE plays (track_id TEXT NOT NULL, playlist_id TEXT, market TEXT NOT NULL)",
+        )
+        .execute(&mut conn)
+        .await
+        .unwrap();
+
+        conn
+    }
+
+    #[tokio::test]
+    async fn ids_and_markets() {
+        let mut conn = connect().await;
+        let track = TrackId::from_id(TRACK).unwrap();
+        let playlist = PlaylistId::from_id(PLAYLIST).unwrap();
+
+        sqlx::query("INSERT INTO plays VALUES (?, ?, ?)")
+            .bind(&track)
+            .bind(Some(&playlist))
+            .bind(Market::GB)
+            .execute(&mut conn)
+            .await
+            .unwrap();
+
+        let row = sqlx::query("SELECT track_id, playlist_id, market FROM plays")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+
+        // They're stored as plain text.
+        assert_eq!(row.get::<String, _>("track_id"), TRACK);
+        assert_eq!(row.get::<String, _>("market"), "GB");
+
+        assert_eq!(row.get::<TrackId, _>("track_id"), track);
+        assert_eq!(
+            row.get::<Option<PlaylistId>, _>("playlist_id"),
+            Some(playlist)
+        );
+        assert_eq!(row.get::<Market, _>("market"), Market::GB);
+    }
+
+    #[tokio::test]
+    async fn uris_are_decoded() {
+        let mut conn = connect().await;
+
+        sqlx::query("INSERT INTO plays VALUES (?, NULL, 'from_token')")
+            .bind(format!("spotify:track:{TRACK}"))
+            .execute(&mut conn)
+            .await
+            .unwrap();
+
+        let (track, market): (TrackId, Market) =
+            sqlx::query_as("SELECT track_id, market FROM plays")
+                .fetch_one(&mut conn)
+                .await
+                .unwrap();
+
+        assert_eq!(track.id(), TRACK);
+        assert_eq!(market, Market::FromToken);
+    }
+
+    #[tokio::test]
+    async fn invalid_values_fail_to_decode() {
+        let mut conn = connect().await;
+
+        sqlx::query("INSERT INTO plays VALUES ('not an id', NULL, 'UK')")
+            .execute(&mut conn)
+            .await
+            .unwrap();
+
+        let row = sqlx::query("SELECT track_id, market FROM plays")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+
+        assert!(row.try_get::<TrackId, _>("track_id").is_err());
+        assert!(row.try_get::<Market, _>("market").is_err());
+    }
+}
+
+#[cfg(feature = "diesel")]
+mod diesel_round_trip {
+    use diesel::{prelude::*, sql_query, sql_types::Text, IntoSql};
+
+    use super::*;
+
+    diesel::table! {
+        plays (rowid) {
+            rowid -> Integer,
+            track_id -> Text,
+            playlist_id -> Nullable<Text>,
+            market -> Text,
+        }
+    }
+
+    fn connect() -> SqliteConnection {
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+
+        sql_query(
+            "CREATE TABLE plays (track_id TEXT NOT NULL, playlist_id TEXT, market TEXT NOT NULL)",
+        )
+        .execute(&mut conn)
+        .unwrap();
+
+        conn
+    }
+
+    #[test]
+    fn ids_and_markets() {
+        let mut conn = connect();
+        let track = TrackId::from_id(TRACK).unwrap();
+        let playlist = PlaylistId::from_id(PLAYLIST).unwrap();
+
+        diesel::insert_into(plays::table)
+            .values((
+                plays::track_id.eq(&track),
+                plays::playlist_id.eq(Some(&playlist)),
+                plays::market.eq(Market::GB),
+            ))
+            .execute(&mut conn)
+            .unwrap();
+
+        let raw: (String, String) = plays::table
+            .select((plays::track_id, plays::market))
+            .first(&mut conn)
+            .unwrap();
+
+        // They're stored as plain text.
+        assert_eq!(raw, (TRACK.to_owned(), "GB".to_owned()));
+
+        let row: (TrackId, Option<PlaylistId>, Market) = plays::table
+            .select((plays::track_id, plays::playlist_id, plays::market))
+            .filter(plays::track_id.eq(&track))
+            .first(&mut conn)
+            .unwrap();
+
+        assert_eq!(row, (track, Some(playlist), Market::GB));
+    }
+
+    #[test]
+    fn uris_are_decoded() {
+        let mut conn = connect();
+        let uri = format!("spotify:track:{TRACK}");
+
+        let (track, market): (TrackId, Market) =
+            diesel::select((uri.into_sql::<Text>(), "from_token".into_sql::<Text>()))
+                .get_result(&mut conn)
+                .unwrap();
+
+        assert_eq!(track.id(), TRACK);
+        assert_eq!(market, Market::FromToken);
+    }
+
+    #[test]
+    fn invalid_values_fail_to_decode() {
+        let mut conn = connect();
+
+        let track = diesel::select("not an id".into_sql::<Text>()).get_result::<TrackId>(&mut conn);
+        let market = diesel::select("UK".into_sql::<Text>()).get_result::<Market>(&mut conn);
+
+        assert!(track.is_err());
+        assert!(market.is_err());
+    }
+}
@@ -0,0 +1,43 @@
+//! The base URLs set with environment variables. These are process-wide, so every case is
+//! in a single test.
+
+use spotify_rs::{AuthCodeClient, AuthCodeFlow, RedirectUrl};
+
+fn authorisation_url() -> String {
+    let auth_flow = AuthCodeFlow::new("client_id", "client_secret", ["user-read-private"]);
+    let redirect_uri = RedirectUrl::new("http://localhost:3000/callback".to_owned()).unwrap();
+
+    let (_, url) = AuthCodeClient::new(auth_flow, redirect_uri, false);
+    url.to_string()
+}
+
+#[test]
+fn base_urls_from_the_environment() {
+    std::env::set_var("SPOTIFY_API_URL", "http://localhost:8080/v1/");
+    std::env::set_var("SPOTIFY_ACCOUNTS_URL", "http://localhost:8080/");
+    assert!(authorisation_url().starts_with("http://localhost:8080/authorize?"));
+
+    for local in ["http://127.0.0.1:8080", "http://[::1]:8080"] {
+        std::env::set_var("SPOTIFY_ACCOUNTS_URL", local);
+        assert!(authorisation_url().starts_with(&format!("{local}/authorize?")));
+    }
+
+    // Invalid URLs, and ones for other hosts, are ignored instead of making the constructor
+    // panic or sending the client's secrets elsewhere.
+    for invalid in [
+        "not a url",
+        "://missing-scheme",
+        "ftp://localhost",
+        "mailto:me",
+        "https://example.com",
+        "http://192.168.1.10:8080",
+    ] {
+        std::env::set_var("SPOTIFY_API_URL", invalid);
+        std::env::set_var("SPOTIFY_ACCOUNTS_URL", invalid);
+        assert!(authorisation_url().starts_with("https://accounts.spotify.com/authorize?"));
+    }
+
+    std::env::remove_var("SPOTIFY_API_URL");
+    std::env::remove_var("SPOTIFY_ACCOUNTS_URL");
+    assert!(authorisation_url().starts_with("https://accounts.spotify.com/authorize?"));
+}
@@ -0,0 +1,43 @@
+//! Values Spotify added after this version of the crate, which are kept in an `Other`
+//! variant with the `lenient` feature, and fail to deserialise without it.
+
+use serde_json::json;
+use spotify_rs::model::{album::AlbumType, audio::Mode, player::RepeatState};
+
+#[test]
+fn other_values_serialize_as_themselves() {
+    let value = serde_json::to_value(AlbumType::Other("mixtape".to_owned())).unwrap();
+    assert_eq!(value, json!("mixtape"));
+
+    let value = serde_json::to_value(RepeatState::Other("shuffle".to_owned())).unwrap();
+    assert_eq!(value, json!("shuffle"));
+
+    assert_eq!(serde_json::to_value(Mode::Other(-1)).unwrap(), json!(-1));
+    assert_eq!(serde_json::to_value(Mode::Major).unwrap(), json!(1));
+}
+
+#[test]
+fn known_values_deserialize() {
+    let state: RepeatState = serde_json::from_value(json!("context")).unwrap();
+    assert!(matches!(state, RepeatState::Context));
+
+    let mode: Mode = serde_json::from_value(json!(0)).unwrap();
+    assert!(matches!(mode, Mode::Minor));
+}
+
+#[cfg(feature = "lenient")]
+#[test]
+fn unknown_values_are_kept() {
+    let state: RepeatState = serde_json::from_value(json!("shuffle")).unwrap();
+    assert!(matches!(state, RepeatState::Other(s) if s == "shuffle"));
+
+    let mode: Mode = serde_json::from_value(json!(-1)).unwrap();
+    assert!(matches!(mode, Mode::Other(-1)));
+}
+
+#[cfg(not(feature = "lenient"))]
+#[test]
+fn unknown_values_are_rejected() {
+    assert!(serde_json::from_value::<RepeatState>(json!("shuffle")).is_err());
+    assert!(serde_json::from_value::<Mode>(json!(-1)).is_err());
+}
@@ -0,0 +1,94 @@
+//! Playback shortcuts, which take IDs, URIs or URLs.
+
+#![cfg(all(feature = "mock", feature = "player"))]
+
+use serde_json::{json, Value};
+use spotify_rs::{endpoint::player::QueueOutcome, mock::Mock, AuthCodeClient, Error};
+
+fn body(mock: &Mock) -> Value {
+    let requests = mock.requests();
+    let request = requests.last().unwrap();
+    assert_eq!(request.path, "/me/player/play");
+    serde_json::from_slice(request.body.as_deref().unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn play_accepts_uris() {
+    // Spotify responds with an empty `204`, which the mock can't, so the responses are
+    // ignored and only the requests are checked.
+    let mock = Mock::new().on("PUT", "/me/player/play", json!({}));
+    let spotify = AuthCodeClient::mock(mock.clone());
+
+    spotify
+        .play_album("spotify:album:4aawyAB9vmqN3uQ7FjRGTy", None)
+        .await
+        .ok();
+    assert_eq!(
+        body(&mock)["context_uri"],
+        "spotify:album:4aawyAB9vmqN3uQ7FjRGTy"
+    );
+
+    spotify
+        .play_playlist(
+            "https://open.spotify.com/playlist/37i9dQZF1DXcBWIGoYBM5M",
+            None,
+        )
+        .await
+        .ok();
+    assert_eq!(
+        body(&mock)["context_uri"],
+        "spotify:playlist:37i9dQZF1DXcBWIGoYBM5M"
+    );
+
+    spotify
+        .play_tracks(
+            &[
+                "spotify:track:4iV5W9uYEdYUVa79Axb7Rh",
+                "1301WleyT98MSxVHPZCA6M",
+            ],
+            None,
+        )
+        .await
+        .ok();
+    assert_eq!(
+        body(&mock)["uris"],
+        json!([
+            "spotify:track:4iV5W9uYEdYUVa79Axb7Rh",
+            "spotify:track:1301WleyT98MSxVHPZCA6M"
+        ])
+    );
+}
+
+#[tokio::test]
+async fn play_rejects_uris_of_another_kind() {
+    let mock = Mock::new();
+    let spotify = AuthCodeClient::mock(mock.clone());
+
+    let result = spotify
+        .play_album("spotify:track:4iV5W9uYEdYUVa79Axb7Rh", None)
+        .await;
+
+    assert!(matches!(result, Err(Error::InvalidId(_))));
+    assert!(mock.requests().is_empty());
+}
+
+#[tokio::test]
+async fn queued_items_are_not_added_again() {
+    let track: Value = serde_json::from_str(include_str!("../src/fixtures/track.json")).unwrap();
+    let mock = Mock::new().on_get(
+        "/me/player/queue",
+        json!({ "currently_playing": null, "queue": [track] }),
+    );
+    let spotify = AuthCodeClient::mock(mock.clone());
+
+    let outcome = spotify
+        .add_item_to_queue("spotify:track:3n3Ppam7vgaVa1iaRUc9Lp")
+        .send_unless_queued()
+        .await
+        .unwrap();
+
+    assert_eq!(outcome, QueueOutcome::AlreadyQueued);
+    let requests = mock.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "GET");
+}
@@ -0,0 +1,86 @@
+//! Tunable audio features of recommendations, which are checked before the request is sent.
+
+#![cfg(feature = "mock")]
+
+use serde_json::json;
+use spotify_rs::{
+    endpoint::track::{Feature, Seed},
+    mock::Mock,
+    AuthCodeClient, Error,
+};
+
+fn mock() -> Mock {
+    Mock::new().on_get("/recommendations", json!({ "seeds": [], "tracks": [] }))
+}
+
+#[tokio::test]
+async fn boundary_values_are_sent() {
+    let mock = mock();
+    let spotify = AuthCodeClient::mock(mock.clone());
+
+    spotify
+        .recommendations(Seed::genres(&["rock"]))
+        .min_energy(0.0)
+        .max_energy(1.0)
+        .target_key(11)
+        .min_time_signature(3)
+        .max_time_signature(7)
+        .target_tempo(300.0)
+        .get()
+        .await
+        .unwrap();
+
+    let requests = mock.requests();
+    assert_eq!(requests.len(), 1);
+    let query = requests[0].query.as_deref().unwrap();
+    assert!(query.contains("min_energy=0"));
+    assert!(query.contains("max_energy=1"));
+    assert!(query.contains("target_key=11"));
+}
+
+#[tokio::test]
+async fn invalid_values_are_rejected() {
+    let mock = mock();
+    let spotify = AuthCodeClient::mock(mock.clone());
+    let seed = || Seed::genres(&["rock"]);
+
+    let builders = [
+        spotify.recommendations(seed()).target_energy(1.01),
+        spotify.recommendations(seed()).min_energy(-0.1),
+        spotify.recommendations(seed()).target_energy(5.0),
+        spotify.recommendations(seed()).target_energy(f32::NAN),
+        spotify.recommendations(seed()).target_key(12),
+        spotify.recommendations(seed()).min_time_signature(2),
+        spotify.recommendations(seed()).max_popularity(101),
+        spotify.recommendations(seed()).target_tempo(f32::NAN),
+        spotify.recommendations(seed()).max_loudness(f32::INFINITY),
+    ];
+
+    for builder in builders {
+        let result = builder.get().await;
+        assert!(
+            matches!(result, Err(Error::InvalidArgument(_))),
+            "{result:?}"
+        );
+    }
+    assert!(mock.requests().is_empty());
+}
+
+#[tokio::test]
+async fn features_are_checked_too() {
+    let mock = mock();
+    let spotify = AuthCodeClient::mock(mock.clone());
+
+    let err = spotify
+        .recommendations(Seed::genres(&["rock"]))
+        .features(&[Feature::TargetValence(1.5)])
+        .get()
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "`target_valence` must be from 0.0 to 1.0, not 1.5"
+    );
+    assert!(mock.requests().is_empty());
+}
@@ -0,0 +1,69 @@
+//! Requests that need several scopes, which fail before they're sent unless the user
+//! granted all of them.
+
+#![cfg(feature = "playlists")]
+
+mod common;
+
+use common::{serve, Response};
+use spotify_rs::{AuthCodeFlow, ClientBuilder, Error};
+
+const JPEG: &[u8] = &[0xFF, 0xD8, 0xFF, 0xE0];
+
+// Upload a playlist cover with a token for `scope`, returning the result and the requests
+// the API received.
+async fn upload_cover(scope: &'static str) -> (Result<(), Error>, Vec<String>) {
+    let (url, requests) = serve(move |request| {
+        if request.starts_with("POST /api/token") {
+            Response::new(
+                "200 OK",
+                format!(
+                    r#"{{"access_token":"new","token_type":"Bearer","expires_in":3600,"scope":"{scope}"}}"#
+                ),
+            )
+        } else {
+            Response::new("202 Accepted", "")
+        }
+    })
+    .await;
+
+    let auth_flow = AuthCodeFlow::new("client_id", "client_secret", ["ugc-image-upload"]);
+    let spotify = ClientBuilder::new(auth_flow)
+        .api_url(format!("{url}/v1"))
+        .accounts_url(&url)
+        .from_access_token("old", 3600, Some("refresh_token".to_owned()))
+        .await
+        .unwrap();
+
+    // The refreshed token says which scopes were granted.
+    spotify.request_refresh_token().await.unwrap();
+
+    let result = spotify
+        .add_playlist_image("37i9dQZF1DXcBWIGoYBM5M", JPEG)
+        .await
+        .map(|_| ());
+    let requests = requests.lock().unwrap().clone();
+
+    (result, requests)
+}
+
+#[tokio::test]
+async fn cover_upload_needs_permission_to_modify_the_playlist() {
+    let (result, requests) = upload_cover("ugc-image-upload").await;
+
+    assert!(
+        matches!(&result, Err(Error::MissingScope(scope)) if scope == "playlist-modify-public or playlist-modify-private"),
+        "{result:?}"
+    );
+    assert!(!requests.iter().any(|r| r.starts_with("PUT")));
+}
+
+#[tokio::test]
+async fn cover_upload_is_sent_with_both_scopes() {
+    let (result, requests) = upload_cover("ugc-image-upload playlist-modify-private").await;
+
+    result.unwrap();
+    assert!(requests
+        .iter()
+        .any(|r| r.starts_with("PUT /v1/playlists/37i9dQZF1DXcBWIGoYBM5M/images")));
+}
@@ -0,0 +1,101 @@
+//! The events the client emits within a request's span when it retries or is rate limited.
+
+#![cfg(feature = "tracing")]
+
+mod common;
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use common::{serve, Response};
+use spotify_rs::{
+    client::{RateLimiter, RetryPolicy},
+    AuthCodeFlow, ClientBuilder,
+};
+use tracing::{
+    field::{Field, Visit},
+    span, Event, Metadata, Subscriber,
+};
+
+/// Collects every event as its fields, e.g. `message=retry attempt=1`.
+#[derive(Clone, Default)]
+struct Events(Arc<Mutex<Vec<String>>>);
+
+struct Fields(String);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0 += &format!(" {}={value:?}", field.name());
+    }
+}
+
+impl Subscriber for Events {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+        span::Id::from_u64(1)
+    }
+
+    fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+    fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields(String::new());
+        event.record(&mut fields);
+        self.0.lock().unwrap().push(fields.0.trim().to_owned());
+    }
+
+    fn enter(&self, _: &span::Id) {}
+
+    fn exit(&self, _: &span::Id) {}
+}
+
+#[tokio::test]
+async fn retries_and_rate_limiting_are_logged() {
+    // The first request is rate limited by Spotify, and the retry by the client's limiter.
+    let limited = AtomicBool::new(false);
+    let (url, _) = serve(move |_| {
+        if limited.swap(true, Ordering::SeqCst) {
+            Response::new("200 OK", r#"{"markets":["GB"]}"#)
+        } else {
+            Response::new("429 Too Many Requests", "").header("Retry-After", "0")
+        }
+    })
+    .await;
+
+    let auth_flow = AuthCodeFlow::new("client_id", "client_secret", ["user-read-private"]);
+    let spotify = ClientBuilder::new(auth_flow)
+        .api_url(format!("{url}/v1"))
+        .retry_policy(RetryPolicy::new(|_| async {}))
+        .rate_limit(RateLimiter::new(1, Duration::from_millis(50), |_| async {}))
+        .from_access_token("token", 3600, None)
+        .await
+        .unwrap();
+
+    let events = Events::default();
+    let _guard = tracing::subscriber::set_default(events.clone());
+
+    spotify.get_available_markets().await.unwrap();
+
+    let events = events.0.lock().unwrap();
+    assert!(
+        events
+            .iter()
+            .any(|e| e.starts_with("message=retry attempt=1 status=429 delay_ms=0")),
+        "{events:?}"
+    );
+    assert!(
+        events
+            .iter()
+            .any(|e| e.starts_with("message=rate_limited delay_ms=")),
+        "{events:?}"
+    );
+}
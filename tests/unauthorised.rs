@@ -0,0 +1,41 @@
+//! Requests that Spotify rejects with a `401` are sent once more after refreshing the token,
+//! whether or not the response has a JSON body.
+
+mod common;
+
+use common::{serve, Response};
+use spotify_rs::{AuthCodeFlow, ClientBuilder};
+
+#[tokio::test]
+async fn empty_401_is_replayed_after_a_refresh() {
+    // The token endpoint hands out the `new` token, which is the only one the API accepts.
+    let (url, requests) = serve(|request| {
+        if request.starts_with("POST /api/token") {
+            Response::new(
+                "200 OK",
+                r#"{"access_token":"new","token_type":"Bearer","expires_in":3600}"#,
+            )
+        } else if request.contains("Bearer new") {
+            Response::new("200 OK", r#"{"markets":["GB"]}"#)
+        } else {
+            Response::new("401 Unauthorized", "")
+        }
+    })
+    .await;
+
+    let auth_flow = AuthCodeFlow::new("client_id", "client_secret", ["user-read-private"]);
+    let spotify = ClientBuilder::new(auth_flow)
+        .auto_refresh(true)
+        .api_url(format!("{url}/v1"))
+        .accounts_url(&url)
+        .from_access_token("revoked", 3600, Some("refresh_token".to_owned()))
+        .await
+        .unwrap();
+
+    let markets = spotify.get_available_markets().await.unwrap();
+
+    assert_eq!(markets, ["GB"]);
+    assert_eq!(spotify.access_token(), "new");
+    // The rejected request, the refresh and the replayed request.
+    assert_eq!(requests.lock().unwrap().len(), 3);
+}
@@ -0,0 +1,55 @@
+//! Recorded fixtures, which have the personal data in response bodies redacted.
+
+#![cfg(all(feature = "vcr", feature = "user"))]
+
+mod common;
+
+use common::{serve, Response};
+use serde_json::Value;
+use spotify_rs::{vcr::Cassette, AuthCodeFlow, ClientBuilder};
+
+const PROFILE: &str = r#"{
+    "id": "someone",
+    "type": "user",
+    "uri": "spotify:user:someone",
+    "href": "https://api.spotify.com/v1/users/someone",
+    "external_urls": { "spotify": "https://open.spotify.com/user/someone" },
+    "display_name": "Someone",
+    "email": "someone@example.com",
+    "country": "GB",
+    "product": "premium",
+    "followers": { "href": null, "total": 3 },
+    "images": [{ "url": "https://i.scdn.co/image/someone", "height": 64, "width": 64 }]
+}"#;
+
+#[tokio::test]
+async fn personal_data_is_redacted() {
+    let (url, _) = serve(|_| Response::new("200 OK", PROFILE)).await;
+    let path = std::env::temp_dir().join(format!("spotify-rs-vcr-{}.json", std::process::id()));
+
+    let auth_flow = AuthCodeFlow::new("client_id", "client_secret", ["user-read-email"]);
+    let spotify = ClientBuilder::new(auth_flow)
+        .api_url(format!("{url}/v1"))
+        .cassette(Cassette::record(&path).redact(|body| body["product"] = "free".into()))
+        .from_access_token("token", 3600, None)
+        .await
+        .unwrap();
+
+    // The client still gets the real response.
+    let user = spotify.get_current_user_profile().await.unwrap();
+    assert_eq!(user.id, "someone");
+
+    let fixture = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(!fixture.contains("someone"), "{fixture}");
+    assert!(!fixture.contains("Someone"), "{fixture}");
+
+    let interactions: Value = serde_json::from_str(&fixture).unwrap();
+    let body = &interactions[0]["body"];
+    assert_eq!(body["id"], "redacted");
+    assert_eq!(body["uri"], "spotify:user:redacted");
+    assert_eq!(body["email"], "redacted");
+    assert_eq!(body["images"], Value::Array(Vec::new()));
+    assert_eq!(body["product"], "free");
+    assert_eq!(body["followers"]["total"], 3);
+}